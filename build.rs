@@ -0,0 +1,115 @@
+// generates the TypeScript definitions of nodes from src/cst/schema.rs
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+#[path = "src/cst/schema.rs"]
+#[allow(dead_code)]
+mod schema;
+
+use schema::{ChildKind, ChildSchema, NodeSchema, ABSTRACT_NODES, NODES};
+
+// `Error` and `Symbol` conflict with the built-in types of TypeScript
+fn ts_name(name: &str) -> String {
+    match name {
+        "Error" | "Symbol" => format!("{}_", name),
+        _ => name.to_string(),
+    }
+}
+
+fn ts_child(child: &ChildSchema) -> String {
+    let ty = match (child.kind, child.ts_type) {
+        (ChildKind::Node, None) => "NodeChild".to_string(),
+        (ChildKind::Node, Some(t)) => format!("{{ Node: {} }}", t),
+        (ChildKind::NodeVec, None) => "NodeVecChild".to_string(),
+        (ChildKind::NodeVec, Some(t)) => format!("{{ NodeVec: {}[] }}", t),
+        (ChildKind::Never, _) => "undefined".to_string(),
+    };
+    let optional = child.optional && child.kind != ChildKind::Never;
+    let key = format!("{}{}: {};", child.key, if optional { "?" } else { "" }, ty);
+    match child.doc {
+        "" => key,
+        doc => format!("{}\n    {}", jsdoc(doc), key),
+    }
+}
+
+// shown by editors on hover, unlike `//` comments
+fn jsdoc(doc: &str) -> String {
+    format!("/** {} */", doc)
+}
+
+fn ts_node(res: &mut String, node: &NodeSchema, is_abstract: bool) {
+    if !node.doc.is_empty() {
+        writeln!(res, "{}", jsdoc(node.doc)).unwrap();
+    }
+    let extends = node.extends.join(" & ");
+    writeln!(res, "export type {} = {} & {{", ts_name(node.name), extends).unwrap();
+    if node.token {
+        writeln!(res, "  token: Token;").unwrap();
+    }
+    if !is_abstract {
+        writeln!(res, "  node_type: \"{}\";", node.name).unwrap();
+    }
+    if !node.children.is_empty() {
+        writeln!(res, "  children: {{").unwrap();
+        for child in node.children {
+            writeln!(res, "    {}", ts_child(child)).unwrap();
+        }
+        writeln!(res, "  }};").unwrap();
+    }
+    writeln!(res, "}};").unwrap();
+}
+
+fn typescript() -> String {
+    let mut res = String::new();
+    res.push_str("export type UnknownNode =");
+    for node in NODES {
+        write!(res, "\n  | {}", ts_name(node.name)).unwrap();
+    }
+    res.push_str(";\n\n");
+
+    // BaseNode is an interface so that any node can be assigned to it
+    let base = &ABSTRACT_NODES[0];
+    res.push_str("export interface BaseNode {\n");
+    res.push_str("  token: Token | null;\n");
+    res.push_str("  node_type: string;\n");
+    res.push_str("  children: {\n");
+    for child in base.children {
+        writeln!(res, "    {}", ts_child(child)).unwrap();
+    }
+    res.push_str("  };\n}\n\n");
+    res.push_str("export type NodeChild = { Node: BaseNode };\n");
+    res.push_str("export type NodeVecChild = { NodeVec: BaseNode[] };\n\n");
+
+    res.push_str("// ----- sub types of BaseNode (abstract) -----\n");
+    for node in &ABSTRACT_NODES[1..] {
+        ts_node(&mut res, node, true);
+        res.push('\n');
+    }
+    res.push_str("// ----- sub types of BaseNode (concrete) -----\n");
+    let concrete: Vec<String> = NODES
+        .iter()
+        .map(|node| {
+            let mut res = String::new();
+            ts_node(&mut res, node, false);
+            res
+        })
+        .collect();
+    res.push_str(&concrete.join("\n"));
+    res
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/cst/schema.rs");
+    assert_eq!(ABSTRACT_NODES[0].name, "BaseNode");
+
+    // `typescript_custom_section` requires a literal
+    let code = format!(
+        "#[wasm_bindgen(typescript_custom_section)]\nconst NODES: &'static str = r#\"\n{}\"#;\n",
+        typescript()
+    );
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("nodes.rs"), code).unwrap();
+}
@@ -0,0 +1,551 @@
+mod lower;
+#[cfg(test)]
+mod tests;
+
+pub use lower::{lower, LowerError, LowerResult};
+
+use crate::cst::Node;
+use crate::token::Span;
+use std::fmt;
+
+// typed view of the CST produced by `lower()`
+// NOTE keywords, commas, parentheses and comments are dropped, use the CST to format code
+// NOTE `large_enum_variant` is allowed because every node has its `Span`
+
+#[allow(clippy::large_enum_variant)]
+#[derive(PartialEq, Debug, Clone)]
+pub enum Statement {
+    Select(Select),
+    SetOperation(SetOperation),
+    Grouped(GroupedStatement), // (SELECT 1)
+    Insert(Insert),
+    Update(Update),
+    Delete(Delete),
+    Merge(Merge),
+    Other(Node), // DDL, DCL, scripting, ... are left as they are
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Select {
+    pub with: Option<With>,
+    pub as_struct_or_value: Option<SelectAs>,
+    pub distinct: bool,
+    pub exprs: Vec<SelectItem>,
+    pub from: Option<TableExpr>,
+    pub where_: Option<Expr>,
+    pub groupby: Vec<Expr>,
+    pub having: Option<Expr>,
+    pub qualify: Option<Expr>,
+    pub window: Vec<NamedWindow>,
+    pub orderby: Vec<OrderByExpr>,
+    pub limit: Option<Limit>,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum SelectAs {
+    Struct,
+    Value,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum SelectItem {
+    Expr(AliasedExpr),
+    // * | t.* EXCEPT (a) REPLACE (1 AS b)
+    Wildcard {
+        qualifier: Option<Expr>,
+        except: Vec<Ident>,
+        replace: Vec<AliasedExpr>,
+        span: Span,
+    },
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct AliasedExpr {
+    pub expr: Expr,
+    pub alias: Option<Ident>,
+    pub name: Option<String>, // the alias or the name BigQuery gives (None if anonymous)
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct SetOperation {
+    pub with: Option<With>,
+    pub operator: SetOperator,
+    pub distinct: bool,
+    pub left: Box<Statement>,
+    pub right: Box<Statement>,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum SetOperator {
+    Union,
+    Intersect,
+    Except,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct GroupedStatement {
+    pub stmt: Box<Statement>,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct With {
+    pub ctes: Vec<Cte>,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Cte {
+    pub name: Ident,
+    pub stmt: Statement,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct NamedWindow {
+    pub name: Ident,
+    pub window: Window,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct OrderByExpr {
+    pub expr: Expr,
+    pub order: Option<SortOrder>,
+    pub null_order: Option<NullOrder>,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum NullOrder {
+    First,
+    Last,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Limit {
+    pub count: Expr,
+    pub offset: Option<Expr>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Insert {
+    pub table: Option<Path>, // None in MERGE statement
+    pub columns: Vec<Ident>,
+    pub input: InsertInput,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum InsertInput {
+    Values(Vec<Vec<Expr>>),
+    Query(Box<Statement>),
+    Row, // INSERT ROW in MERGE statement
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Update {
+    pub table: Option<TableName>, // None in MERGE statement
+    pub set: Vec<Assignment>,
+    pub from: Option<TableExpr>,
+    pub where_: Option<Expr>,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Assignment {
+    pub target: Expr,
+    pub value: Expr,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Delete {
+    pub table: TableName,
+    pub where_: Option<Expr>,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Merge {
+    pub table: TableName,
+    pub using: TableExpr,
+    pub on: Expr,
+    pub whens: Vec<MergeWhen>,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct MergeWhen {
+    pub matched: MergeMatch,
+    pub condition: Option<Expr>,
+    pub action: MergeAction,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum MergeMatch {
+    Matched,
+    NotMatchedByTarget,
+    NotMatchedBySource,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(PartialEq, Debug, Clone)]
+pub enum MergeAction {
+    Update(Update),
+    Insert(Insert),
+    Delete,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct TableName {
+    pub name: Path,
+    pub alias: Option<Ident>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum TableExpr {
+    Table {
+        name: Path,
+        alias: Option<Ident>,
+        for_system_time_as_of: Option<Expr>,
+        span: Span,
+    },
+    Subquery {
+        stmt: Box<Statement>,
+        alias: Option<Ident>,
+        span: Span,
+    },
+    Unnest {
+        expr: Box<Expr>,
+        alias: Option<Ident>,
+        with_offset: bool,
+        offset_alias: Option<Ident>,
+        span: Span,
+    },
+    // table function
+    Call {
+        func: Path,
+        args: Vec<Expr>,
+        alias: Option<Ident>,
+        span: Span,
+    },
+    Join {
+        join_type: JoinType,
+        left: Box<TableExpr>,
+        right: Box<TableExpr>,
+        condition: Option<JoinCondition>,
+        span: Span,
+    },
+}
+
+impl TableExpr {
+    pub fn span(&self) -> &Span {
+        match self {
+            TableExpr::Table { span, .. }
+            | TableExpr::Subquery { span, .. }
+            | TableExpr::Unnest { span, .. }
+            | TableExpr::Call { span, .. }
+            | TableExpr::Join { span, .. } => span,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Full,
+    Cross,
+    Comma, // FROM t1, t2
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(PartialEq, Debug, Clone)]
+pub enum JoinCondition {
+    On(Expr),
+    Using(Vec<Ident>),
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Expr {
+    Path(Path), // col | t.col | `project.dataset.table`
+    Literal(Literal),
+    // DATE '2000-01-01'
+    TypedLiteral {
+        data_type: String,
+        value: Literal,
+        span: Span,
+    },
+    // INTERVAL 1 DAY
+    Interval {
+        value: Box<Expr>,
+        date_part: String,
+        span: Span,
+    },
+    Parameter {
+        name: String, // @param | ?
+        span: Span,
+    },
+    // COUNT(*)
+    Wildcard {
+        span: Span,
+    },
+    Unary {
+        op: UnaryOperator,
+        expr: Box<Expr>,
+        span: Span,
+    },
+    Binary {
+        op: BinaryOperator,
+        left: Box<Expr>,
+        right: Box<Expr>,
+        span: Span,
+    },
+    Between {
+        expr: Box<Expr>,
+        not: bool,
+        low: Box<Expr>,
+        high: Box<Expr>,
+        span: Span,
+    },
+    // `list` is a single `Expr::Subquery` in the case of `x IN (SELECT 1)`
+    In {
+        expr: Box<Expr>,
+        not: bool,
+        list: Vec<Expr>,
+        span: Span,
+    },
+    Case {
+        operand: Option<Box<Expr>>,
+        whens: Vec<CaseWhen>,
+        else_: Option<Box<Expr>>,
+        span: Span,
+    },
+    Call {
+        func: Path,
+        distinct: bool,
+        args: Vec<Expr>,
+        ignore_nulls: Option<bool>, // Some(false) means RESPECT NULLS
+        orderby: Vec<OrderByExpr>,
+        limit: Option<Box<Expr>>,
+        over: Option<Box<Window>>,
+        span: Span,
+    },
+    // ST_GEOGFROMTEXT(wkt, oriented => TRUE)
+    NamedArgument {
+        name: Ident,
+        value: Box<Expr>,
+        span: Span,
+    },
+    // CAST(x AS STRING FORMAT 'YYYY') | SAFE.CAST(x AS INT64)
+    Cast {
+        safe: bool,
+        expr: Box<Expr>,
+        data_type: Type,
+        format: Option<Box<Expr>>,
+        span: Span,
+    },
+    // EXTRACT(DAY FROM x AT TIME ZONE 'UTC')
+    Extract {
+        part: Ident,
+        expr: Box<Expr>,
+        time_zone: Option<Box<Expr>>,
+        span: Span,
+    },
+    Array {
+        data_type: Option<Type>,
+        elements: Vec<Expr>,
+        span: Span,
+    },
+    Struct {
+        data_type: Option<Type>,
+        fields: Vec<AliasedExpr>,
+        span: Span,
+    },
+    // arr[OFFSET(1)] | json['key']
+    Index {
+        expr: Box<Expr>,
+        index: Box<Expr>,
+        span: Span,
+    },
+    // f(x).field
+    Field {
+        expr: Box<Expr>,
+        field: Ident,
+        span: Span,
+    },
+    Subquery {
+        stmt: Box<Statement>,
+        span: Span,
+    },
+}
+
+impl Expr {
+    pub fn span(&self) -> &Span {
+        match self {
+            Expr::Path(path) => &path.span,
+            Expr::Literal(literal) => &literal.span,
+            Expr::TypedLiteral { span, .. }
+            | Expr::Interval { span, .. }
+            | Expr::Parameter { span, .. }
+            | Expr::Wildcard { span }
+            | Expr::Unary { span, .. }
+            | Expr::Binary { span, .. }
+            | Expr::Between { span, .. }
+            | Expr::In { span, .. }
+            | Expr::Case { span, .. }
+            | Expr::Call { span, .. }
+            | Expr::NamedArgument { span, .. }
+            | Expr::Cast { span, .. }
+            | Expr::Extract { span, .. }
+            | Expr::Array { span, .. }
+            | Expr::Struct { span, .. }
+            | Expr::Index { span, .. }
+            | Expr::Field { span, .. }
+            | Expr::Subquery { span, .. } => span,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum UnaryOperator {
+    Plus,
+    Minus,
+    BitNot,
+    Not,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum BinaryOperator {
+    Multiply,
+    Divide,
+    Concat,
+    Plus,
+    Minus,
+    ShiftLeft,
+    ShiftRight,
+    BitAnd,
+    BitXor,
+    BitOr,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    Like,
+    NotLike,
+    Is,
+    IsNot,
+    And,
+    Or,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct CaseWhen {
+    pub condition: Expr,
+    pub result: Expr,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(PartialEq, Debug, Clone)]
+pub enum Window {
+    Named(Ident), // OVER w
+    Spec(WindowSpec),
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct WindowSpec {
+    pub name: Option<Ident>, // OVER (w ORDER BY x)
+    pub partitionby: Vec<Expr>,
+    pub orderby: Vec<OrderByExpr>,
+    pub frame: Option<WindowFrame>,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct WindowFrame {
+    pub unit: FrameUnit,
+    pub start: FrameBound,
+    pub end: Option<FrameBound>,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum FrameUnit {
+    Rows,
+    Range,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(Expr),
+    CurrentRow,
+    Following(Expr),
+    UnboundedFollowing,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Type {
+    Named { name: String, parameters: Vec<Expr> }, // INT64 | STRING(10) | ...
+    Array(Box<Type>),
+    Struct(Vec<StructFieldType>),
+    Any, // ANY TYPE
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct StructFieldType {
+    pub name: Option<Ident>,
+    pub data_type: Type,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Literal {
+    pub kind: LiteralKind,
+    pub value: String, // as it is written, including quotes and prefixes
+    pub span: Span,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum LiteralKind {
+    Null,
+    Boolean,
+    Numeric,
+    String,
+    Bytes,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Ident {
+    pub value: String, // without backquotes
+    pub quoted: bool,
+    pub span: Span,
+}
+
+impl fmt::Display for Ident {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+// NOTE `project.dataset.table` is a path of one identifier
+#[derive(PartialEq, Debug, Clone)]
+pub struct Path {
+    pub parts: Vec<Ident>,
+    pub span: Span,
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parts: Vec<&str> = self.parts.iter().map(|p| p.value.as_str()).collect();
+        write!(f, "{}", parts.join("."))
+    }
+}
@@ -0,0 +1,1108 @@
+use super::*;
+use crate::cst::{ContentType, NodeType};
+use crate::names;
+use crate::token::{Token, TokenKind};
+use std::convert::TryFrom;
+
+pub type LowerResult<T> = Result<T, LowerError>;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct LowerError {
+    pub line: usize,
+    pub column: usize,
+    pub node_type: NodeType,
+    pub message: String,
+}
+
+impl LowerError {
+    fn new(node: &Node, message: String) -> LowerError {
+        let (line, column) = match (&node.token, node.span()) {
+            (Some(t), _) => (t.line, t.column),
+            (None, Some(s)) => (s.line, s.column),
+            (None, None) => (0, 0),
+        };
+        LowerError {
+            line,
+            column,
+            node_type: node.node_type.clone(),
+            message,
+        }
+    }
+    fn unexpected(node: &Node) -> LowerError {
+        let literal = match &node.token {
+            Some(t) => t.literal.as_str(),
+            None => "",
+        };
+        LowerError::new(
+            node,
+            format!("Unexpected {:?} `{}`", node.node_type, literal),
+        )
+    }
+}
+
+impl fmt::Display for LowerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for LowerError {}
+
+// EOF is skipped
+pub fn lower(stmts: &[Node]) -> LowerResult<Vec<Statement>> {
+    stmts
+        .iter()
+        .filter(|n| n.node_type != NodeType::EOF)
+        .map(Statement::try_from)
+        .collect()
+}
+
+impl TryFrom<&Node> for Statement {
+    type Error = LowerError;
+    fn try_from(node: &Node) -> LowerResult<Statement> {
+        lower_statement(node)
+    }
+}
+
+impl TryFrom<&Node> for Expr {
+    type Error = LowerError;
+    fn try_from(node: &Node) -> LowerResult<Expr> {
+        lower_expr(node)
+    }
+}
+
+// ----- helpers -----
+
+const COMMENT_KEYS: [&str; 2] = ["leading_comments", "trailing_comments"];
+// punctuations and the keys read by the parent node (e.g. `alias` of select items)
+const CONTEXT_KEYS: [&str; 6] = ["comma", "semicolon", "as", "alias", "order", "null_order"];
+
+// every child should be lowered, or explicitly ignored
+fn check_keys(node: &Node, keys: &[&str]) -> LowerResult<()> {
+    for (key, child) in node.children() {
+        let key = key.as_str();
+        if keys.contains(&key)
+            || key == "rparen"
+            || COMMENT_KEYS.contains(&key)
+            || CONTEXT_KEYS.contains(&key)
+        {
+            continue;
+        }
+        let child = match child {
+            ContentType::Node(n) => n,
+            ContentType::NodeVec(ns) => ns.first().unwrap_or(node),
+        };
+        return Err(LowerError::new(
+            child,
+            format!("`{}` is not supported", key),
+        ));
+    }
+    Ok(())
+}
+
+fn expect_type(node: &Node, node_type: NodeType) -> LowerResult<()> {
+    if node.node_type == node_type {
+        Ok(())
+    } else {
+        Err(LowerError::unexpected(node))
+    }
+}
+
+fn child<'a>(node: &'a Node, key: &str) -> LowerResult<&'a Node> {
+    node.get_node(key)
+        .ok_or_else(|| LowerError::new(node, format!("`{}` is missing", key)))
+}
+
+fn child_vec<'a>(node: &'a Node, key: &str) -> &'a [Node] {
+    match node.get_node_vec(key) {
+        Some(ns) => ns,
+        None => &[],
+    }
+}
+
+fn token(node: &Node) -> LowerResult<&Token> {
+    node.token
+        .as_ref()
+        .ok_or_else(|| LowerError::new(node, "token is missing".to_string()))
+}
+
+fn is(node: &Node, literal: &str) -> bool {
+    node.token.as_ref().is_some_and(|t| t.is(literal))
+}
+
+// unlike `Node::span()`, trailing commas, aliases and comments are not included
+fn span(node: &Node) -> LowerResult<Span> {
+    let mut tokens = Vec::new();
+    collect_tokens(node, true, &mut tokens);
+    let first = tokens.iter().min_by_key(|t| t.start);
+    let last = tokens.iter().max_by_key(|t| t.end);
+    match (first, last) {
+        (Some(first), Some(last)) => Ok(Span {
+            line: first.line,
+            column: first.column,
+            end_line: last.end_line,
+            end_column: last.end_column,
+            start: first.start,
+            end: last.end,
+            start_utf16: first.start_utf16,
+            end_utf16: last.end_utf16,
+        }),
+        _ => Err(LowerError::new(node, "token is missing".to_string())),
+    }
+}
+
+fn collect_tokens<'a>(node: &'a Node, top: bool, tokens: &mut Vec<&'a Token>) {
+    tokens.extend(&node.token);
+    for (key, child) in node.children() {
+        let key = key.as_str();
+        if COMMENT_KEYS.contains(&key) || top && CONTEXT_KEYS.contains(&key) {
+            continue;
+        }
+        match child {
+            ContentType::Node(n) => collect_tokens(n, false, tokens),
+            ContentType::NodeVec(ns) => {
+                for n in ns {
+                    collect_tokens(n, false, tokens);
+                }
+            }
+        }
+    }
+}
+
+fn ident(node: &Node) -> LowerResult<Ident> {
+    let token = token(node)?;
+    let quoted = token.kind == TokenKind::QuotedIdent;
+    let value = if quoted {
+        token.literal[1..token.literal.len() - 1].to_string()
+    } else {
+        token.literal.clone()
+    };
+    Ok(Ident {
+        value,
+        quoted,
+        span: token.span(),
+    })
+}
+
+fn alias(node: &Node) -> LowerResult<Option<Ident>> {
+    node.get_node("alias").map(ident).transpose()
+}
+
+// `a.b.c` is parsed as nested `DotOperator`
+fn is_path(node: &Node) -> bool {
+    match node.node_type {
+        NodeType::Identifier => true,
+        NodeType::DotOperator => match (node.get_node("left"), node.get_node("right")) {
+            (Some(left), Some(right)) => is_path(left) && right.node_type == NodeType::Identifier,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn path(node: &Node) -> LowerResult<Path> {
+    fn collect(node: &Node, parts: &mut Vec<Ident>) -> LowerResult<()> {
+        match node.node_type {
+            NodeType::Identifier => parts.push(ident(node)?),
+            NodeType::DotOperator => {
+                collect(child(node, "left")?, parts)?;
+                collect(child(node, "right")?, parts)?;
+            }
+            _ => return Err(LowerError::unexpected(node)),
+        }
+        Ok(())
+    }
+    let mut parts = Vec::new();
+    collect(node, &mut parts)?;
+    Ok(Path {
+        parts,
+        span: span(node)?,
+    })
+}
+
+fn exprs(nodes: &[Node]) -> LowerResult<Vec<Expr>> {
+    nodes.iter().map(lower_expr).collect()
+}
+
+fn boxed(node: &Node) -> LowerResult<Box<Expr>> {
+    Ok(Box::new(lower_expr(node)?))
+}
+
+// WHERE expr | HAVING expr | ...
+fn keyword_with_expr(node: &Node, key: &str) -> LowerResult<Option<Expr>> {
+    match node.get_node(key) {
+        Some(keyword) => {
+            check_keys(keyword, &["expr"])?;
+            Ok(Some(lower_expr(child(keyword, "expr")?)?))
+        }
+        None => Ok(None),
+    }
+}
+
+// ----- statements -----
+
+fn lower_statement(node: &Node) -> LowerResult<Statement> {
+    let stmt = match node.node_type {
+        NodeType::SelectStatement | NodeType::SetOperator | NodeType::GroupedStatement => {
+            lower_query(node)?
+        }
+        NodeType::InsertStatement => Statement::Insert(lower_insert(node)?),
+        NodeType::UpdateStatement => Statement::Update(lower_update(node)?),
+        NodeType::DeleteStatement => Statement::Delete(lower_delete(node)?),
+        NodeType::MergeStatement => Statement::Merge(lower_merge(node)?),
+        NodeType::EOF | NodeType::Error => return Err(LowerError::unexpected(node)),
+        _ => Statement::Other(node.clone()),
+    };
+    Ok(stmt)
+}
+
+fn lower_query(node: &Node) -> LowerResult<Statement> {
+    let stmt = match node.node_type {
+        NodeType::SelectStatement => Statement::Select(lower_select(node)?),
+        NodeType::SetOperator => {
+            check_keys(node, &["with", "distinct_or_all", "left", "right"])?;
+            let operator = match token(node)?.literal.to_uppercase().as_str() {
+                "UNION" => SetOperator::Union,
+                "INTERSECT" => SetOperator::Intersect,
+                "EXCEPT" => SetOperator::Except,
+                _ => return Err(LowerError::unexpected(node)),
+            };
+            Statement::SetOperation(SetOperation {
+                with: node.get_node("with").map(lower_with).transpose()?,
+                operator,
+                distinct: is(child(node, "distinct_or_all")?, "DISTINCT"),
+                left: Box::new(lower_query(child(node, "left")?)?),
+                right: Box::new(lower_query(child(node, "right")?)?),
+                span: span(node)?,
+            })
+        }
+        NodeType::GroupedStatement => {
+            check_keys(node, &["stmt"])?;
+            Statement::Grouped(GroupedStatement {
+                stmt: Box::new(lower_query(child(node, "stmt")?)?),
+                span: span(node)?,
+            })
+        }
+        _ => return Err(LowerError::unexpected(node)),
+    };
+    Ok(stmt)
+}
+
+fn lower_select(node: &Node) -> LowerResult<Select> {
+    check_keys(
+        node,
+        &[
+            "with",
+            "as_struct_or_value",
+            "distinct_or_all",
+            "exprs",
+            "from",
+            "where",
+            "groupby",
+            "having",
+            "qualify",
+            "window",
+            "orderby",
+            "limit",
+        ],
+    )?;
+    let as_struct_or_value = match child_vec(node, "as_struct_or_value").last() {
+        Some(n) if is(n, "STRUCT") => Some(SelectAs::Struct),
+        Some(n) if is(n, "VALUE") => Some(SelectAs::Value),
+        Some(n) => return Err(LowerError::unexpected(n)),
+        None => None,
+    };
+    let from = match node.get_node("from") {
+        Some(from) => {
+            check_keys(from, &["expr"])?;
+            Some(lower_table_expr(child(from, "expr")?)?)
+        }
+        None => None,
+    };
+    let mut window = Vec::new();
+    if let Some(clause) = node.get_node("window") {
+        check_keys(clause, &["window_exprs"])?;
+        for n in child_vec(clause, "window_exprs") {
+            check_keys(n, &["window"])?;
+            window.push(NamedWindow {
+                name: ident(n)?,
+                window: lower_window(child(n, "window")?)?,
+            });
+        }
+    }
+    let limit = match node.get_node("limit") {
+        Some(limit) => {
+            check_keys(limit, &["expr", "offset"])?;
+            Some(Limit {
+                count: lower_expr(child(limit, "expr")?)?,
+                offset: keyword_with_expr(limit, "offset")?,
+            })
+        }
+        None => None,
+    };
+    Ok(Select {
+        with: node.get_node("with").map(lower_with).transpose()?,
+        as_struct_or_value,
+        distinct: node
+            .get_node("distinct_or_all")
+            .is_some_and(|n| is(n, "DISTINCT")),
+        exprs: child_vec(node, "exprs")
+            .iter()
+            .map(lower_select_item)
+            .collect::<LowerResult<_>>()?,
+        from,
+        where_: keyword_with_expr(node, "where")?,
+        groupby: by_exprs(node, "groupby")?,
+        having: keyword_with_expr(node, "having")?,
+        qualify: keyword_with_expr(node, "qualify")?,
+        window,
+        orderby: orderby(node, "orderby")?,
+        limit,
+        span: span(node)?,
+    })
+}
+
+fn lower_with(node: &Node) -> LowerResult<With> {
+    check_keys(node, &["queries"])?;
+    let mut ctes = Vec::new();
+    for query in child_vec(node, "queries") {
+        check_keys(query, &["stmt"])?;
+        let grouped = child(query, "stmt")?;
+        expect_type(grouped, NodeType::GroupedStatement)?;
+        check_keys(grouped, &["stmt"])?;
+        ctes.push(Cte {
+            name: ident(query)?,
+            stmt: lower_query(child(grouped, "stmt")?)?,
+            span: span(query)?,
+        });
+    }
+    Ok(With {
+        ctes,
+        span: span(node)?,
+    })
+}
+
+fn aliased_expr(node: &Node) -> LowerResult<AliasedExpr> {
+    let name = names::implicit_name(node);
+    Ok(AliasedExpr {
+        expr: lower_expr(node)?,
+        alias: alias(node)?,
+        name: Some(name).filter(|n| !n.is_empty()),
+    })
+}
+
+fn lower_select_item(node: &Node) -> LowerResult<SelectItem> {
+    let (qualifier, asterisk) = match node.node_type {
+        NodeType::Asterisk => (None, node),
+        NodeType::DotOperator if child(node, "right")?.node_type == NodeType::Asterisk => (
+            Some(lower_expr(child(node, "left")?)?),
+            child(node, "right")?,
+        ),
+        _ => return Ok(SelectItem::Expr(aliased_expr(node)?)),
+    };
+    check_keys(asterisk, &["except", "replace"])?;
+    let except = match asterisk.get_node("except") {
+        Some(except) => grouped_exprs(except)?
+            .iter()
+            .map(ident)
+            .collect::<LowerResult<_>>()?,
+        None => Vec::new(),
+    };
+    let replace = match asterisk.get_node("replace") {
+        Some(replace) => grouped_exprs(replace)?
+            .iter()
+            .map(aliased_expr)
+            .collect::<LowerResult<_>>()?,
+        None => Vec::new(),
+    };
+    Ok(SelectItem::Wildcard {
+        qualifier,
+        except,
+        replace,
+        span: span(node)?,
+    })
+}
+
+// EXCEPT (a, b) | REPLACE (1 AS a)
+fn grouped_exprs(keyword: &Node) -> LowerResult<&[Node]> {
+    check_keys(keyword, &["group"])?;
+    let group = child(keyword, "group")?;
+    expect_type(group, NodeType::GroupedExprs)?;
+    check_keys(group, &["exprs"])?;
+    Ok(child_vec(group, "exprs"))
+}
+
+// GROUP BY expr | PARTITION BY expr
+fn by_exprs(node: &Node, key: &str) -> LowerResult<Vec<Expr>> {
+    match node.get_node(key) {
+        Some(by) => {
+            check_keys(by, &["by", "exprs"])?;
+            exprs(child_vec(by, "exprs"))
+        }
+        None => Ok(Vec::new()),
+    }
+}
+
+fn orderby(node: &Node, key: &str) -> LowerResult<Vec<OrderByExpr>> {
+    let by = match node.get_node(key) {
+        Some(by) => by,
+        None => return Ok(Vec::new()),
+    };
+    check_keys(by, &["by", "exprs"])?;
+    let mut res = Vec::new();
+    for n in child_vec(by, "exprs") {
+        let order = match n.get_node("order") {
+            Some(o) if is(o, "ASC") => Some(SortOrder::Asc),
+            Some(o) if is(o, "DESC") => Some(SortOrder::Desc),
+            Some(o) => return Err(LowerError::unexpected(o)),
+            None => None,
+        };
+        let null_order = match child_vec(n, "null_order").last() {
+            Some(o) if is(o, "FIRST") => Some(NullOrder::First),
+            Some(o) if is(o, "LAST") => Some(NullOrder::Last),
+            Some(o) => return Err(LowerError::unexpected(o)),
+            None => None,
+        };
+        res.push(OrderByExpr {
+            expr: lower_expr(n)?,
+            order,
+            null_order,
+        });
+    }
+    Ok(res)
+}
+
+fn lower_insert(node: &Node) -> LowerResult<Insert> {
+    check_keys(node, &["into", "target_name", "columns", "input"])?;
+    let columns = match node.get_node("columns") {
+        Some(columns) => {
+            check_keys(columns, &["exprs"])?;
+            child_vec(columns, "exprs")
+                .iter()
+                .map(ident)
+                .collect::<LowerResult<_>>()?
+        }
+        None => Vec::new(),
+    };
+    let input = child(node, "input")?;
+    let input = match input.node_type {
+        NodeType::KeywordWithExprs if is(input, "VALUES") => {
+            check_keys(input, &["exprs"])?;
+            let mut rows = Vec::new();
+            for row in child_vec(input, "exprs") {
+                expect_type(row, NodeType::GroupedExprs)?;
+                check_keys(row, &["exprs"])?;
+                rows.push(exprs(child_vec(row, "exprs"))?);
+            }
+            InsertInput::Values(rows)
+        }
+        NodeType::Keyword if is(input, "ROW") => InsertInput::Row,
+        _ => InsertInput::Query(Box::new(lower_query(input)?)),
+    };
+    Ok(Insert {
+        table: node.get_node("target_name").map(path).transpose()?,
+        columns,
+        input,
+        span: span(node)?,
+    })
+}
+
+fn lower_update(node: &Node) -> LowerResult<Update> {
+    check_keys(node, &["table_name", "set", "from", "where"])?;
+    let set = child(node, "set")?;
+    check_keys(set, &["exprs"])?;
+    let mut assignments = Vec::new();
+    for n in child_vec(set, "exprs") {
+        if !(n.node_type == NodeType::BinaryOperator && is(n, "=")) {
+            return Err(LowerError::unexpected(n));
+        }
+        check_keys(n, &["left", "right"])?;
+        assignments.push(Assignment {
+            target: lower_expr(child(n, "left")?)?,
+            value: lower_expr(child(n, "right")?)?,
+        });
+    }
+    let from = match node.get_node("from") {
+        Some(from) => {
+            check_keys(from, &["expr"])?;
+            Some(lower_table_expr(child(from, "expr")?)?)
+        }
+        None => None,
+    };
+    Ok(Update {
+        table: node.get_node("table_name").map(table_name).transpose()?,
+        set: assignments,
+        from,
+        where_: keyword_with_expr(node, "where")?,
+        span: span(node)?,
+    })
+}
+
+fn lower_delete(node: &Node) -> LowerResult<Delete> {
+    check_keys(node, &["from", "table_name", "where"])?;
+    Ok(Delete {
+        table: table_name(child(node, "table_name")?)?,
+        where_: keyword_with_expr(node, "where")?,
+        span: span(node)?,
+    })
+}
+
+fn lower_merge(node: &Node) -> LowerResult<Merge> {
+    check_keys(node, &["into", "table_name", "using", "on", "whens"])?;
+    let using = child(node, "using")?;
+    check_keys(using, &["expr"])?;
+    let mut whens = Vec::new();
+    for when in child_vec(node, "whens") {
+        check_keys(
+            when,
+            &["not", "matched", "by_target_or_source", "and", "then"],
+        )?;
+        let matched = if when.get_node("not").is_none() {
+            MergeMatch::Matched
+        } else if child_vec(when, "by_target_or_source")
+            .last()
+            .is_some_and(|n| is(n, "SOURCE"))
+        {
+            MergeMatch::NotMatchedBySource
+        } else {
+            MergeMatch::NotMatchedByTarget
+        };
+        let then = child(when, "then")?;
+        check_keys(then, &["stmt"])?;
+        let stmt = child(then, "stmt")?;
+        let action = match stmt.node_type {
+            NodeType::UpdateStatement => MergeAction::Update(lower_update(stmt)?),
+            NodeType::InsertStatement => MergeAction::Insert(lower_insert(stmt)?),
+            NodeType::SingleTokenStatement if is(stmt, "DELETE") => MergeAction::Delete,
+            _ => return Err(LowerError::unexpected(stmt)),
+        };
+        whens.push(MergeWhen {
+            matched,
+            condition: keyword_with_expr(when, "and")?,
+            action,
+            span: span(when)?,
+        });
+    }
+    Ok(Merge {
+        table: table_name(child(node, "table_name")?)?,
+        using: lower_table_expr(child(using, "expr")?)?,
+        on: keyword_with_expr(node, "on")?
+            .ok_or_else(|| LowerError::new(node, "`on` is missing".to_string()))?,
+        whens,
+        span: span(node)?,
+    })
+}
+
+fn table_name(node: &Node) -> LowerResult<TableName> {
+    check_keys(node, &["left", "right"])?;
+    Ok(TableName {
+        name: path(node)?,
+        alias: alias(node)?,
+    })
+}
+
+// ----- FROM clause -----
+
+fn lower_table_expr(node: &Node) -> LowerResult<TableExpr> {
+    let table_expr = match node.node_type {
+        NodeType::Identifier | NodeType::DotOperator => {
+            check_keys(node, &["left", "right", "for_system_time_as_of"])?;
+            let for_system_time_as_of = match node.get_node("for_system_time_as_of") {
+                Some(clause) => {
+                    check_keys(clause, &["system_time_as_of", "expr"])?;
+                    Some(lower_expr(child(clause, "expr")?)?)
+                }
+                None => None,
+            };
+            TableExpr::Table {
+                name: path(node)?,
+                alias: alias(node)?,
+                for_system_time_as_of,
+                span: span(node)?,
+            }
+        }
+        NodeType::GroupedStatement => {
+            check_keys(node, &["stmt"])?;
+            TableExpr::Subquery {
+                stmt: Box::new(lower_query(child(node, "stmt")?)?),
+                alias: alias(node)?,
+                span: span(node)?,
+            }
+        }
+        NodeType::CallingUnnest => {
+            check_keys(
+                node,
+                &["func", "args", "with_offset", "offset_as", "offset_alias"],
+            )?;
+            let expr = match child_vec(node, "args") {
+                [arg] => boxed(arg)?,
+                _ => {
+                    return Err(LowerError::new(
+                        node,
+                        "UNNEST takes exactly one argument".to_string(),
+                    ))
+                }
+            };
+            TableExpr::Unnest {
+                expr,
+                alias: alias(node)?,
+                with_offset: node.children().contains_key("with_offset"),
+                offset_alias: node.get_node("offset_alias").map(ident).transpose()?,
+                span: span(node)?,
+            }
+        }
+        NodeType::CallingTableFunction => {
+            check_keys(node, &["func", "args"])?;
+            TableExpr::Call {
+                func: path(child(node, "func")?)?,
+                args: exprs(child_vec(node, "args"))?,
+                alias: alias(node)?,
+                span: span(node)?,
+            }
+        }
+        NodeType::JoinOperator => {
+            check_keys(
+                node,
+                &["join_type", "outer", "left", "right", "on", "using"],
+            )?;
+            let join_type = if is(node, ",") {
+                JoinType::Comma
+            } else {
+                match node.get_node("join_type") {
+                    None => JoinType::Inner,
+                    Some(t) if is(t, "INNER") => JoinType::Inner,
+                    Some(t) if is(t, "LEFT") => JoinType::Left,
+                    Some(t) if is(t, "RIGHT") => JoinType::Right,
+                    Some(t) if is(t, "FULL") => JoinType::Full,
+                    Some(t) if is(t, "CROSS") => JoinType::Cross,
+                    Some(t) => return Err(LowerError::unexpected(t)),
+                }
+            };
+            let condition = if let Some(on) = keyword_with_expr(node, "on")? {
+                Some(JoinCondition::On(on))
+            } else if let Some(using) = node.get_node("using") {
+                // USING (id) is parsed as `CallingFunction`
+                check_keys(using, &["func", "args"])?;
+                let columns = child_vec(using, "args")
+                    .iter()
+                    .map(ident)
+                    .collect::<LowerResult<_>>()?;
+                Some(JoinCondition::Using(columns))
+            } else {
+                None
+            };
+            TableExpr::Join {
+                join_type,
+                left: Box::new(lower_table_expr(child(node, "left")?)?),
+                right: Box::new(lower_table_expr(child(node, "right")?)?),
+                condition,
+                span: span(node)?,
+            }
+        }
+        // FROM (t1 JOIN t2)
+        NodeType::GroupedExpr => {
+            check_keys(node, &["expr"])?;
+            lower_table_expr(child(node, "expr")?)?
+        }
+        _ => return Err(LowerError::unexpected(node)),
+    };
+    Ok(table_expr)
+}
+
+// ----- expressions -----
+
+fn lower_expr(node: &Node) -> LowerResult<Expr> {
+    let expr = match node.node_type {
+        NodeType::Identifier => {
+            check_keys(node, &[])?;
+            Expr::Path(path(node)?)
+        }
+        NodeType::DotOperator => {
+            check_keys(node, &["left", "right"])?;
+            if is_path(node) {
+                Expr::Path(path(node)?)
+            } else {
+                let right = child(node, "right")?;
+                expect_type(right, NodeType::Identifier)?;
+                Expr::Field {
+                    expr: boxed(child(node, "left")?)?,
+                    field: ident(right)?,
+                    span: span(node)?,
+                }
+            }
+        }
+        NodeType::NullLiteral
+        | NodeType::BooleanLiteral
+        | NodeType::NumericLiteral
+        | NodeType::StringLiteral => Expr::Literal(literal(node)?),
+        NodeType::Parameter => {
+            check_keys(node, &[])?;
+            Expr::Parameter {
+                name: token(node)?.literal.clone(),
+                span: span(node)?,
+            }
+        }
+        NodeType::Asterisk => {
+            check_keys(node, &[])?;
+            Expr::Wildcard { span: span(node)? }
+        }
+        NodeType::UnaryOperator => {
+            check_keys(node, &["right"])?;
+            let right = child(node, "right")?;
+            let op = match token(node)?.literal.to_uppercase().as_str() {
+                "+" => UnaryOperator::Plus,
+                "-" => UnaryOperator::Minus,
+                "~" => UnaryOperator::BitNot,
+                "NOT" => UnaryOperator::Not,
+                // DATE '2000-01-01', TIMESTAMP '...', ...
+                data_type if right.node_type == NodeType::StringLiteral => {
+                    return Ok(Expr::TypedLiteral {
+                        data_type: data_type.to_string(),
+                        value: literal(right)?,
+                        span: span(node)?,
+                    })
+                }
+                _ => return Err(LowerError::unexpected(node)),
+            };
+            Expr::Unary {
+                op,
+                expr: boxed(right)?,
+                span: span(node)?,
+            }
+        }
+        NodeType::BinaryOperator if is(node, "=>") => {
+            check_keys(node, &["left", "right"])?;
+            Expr::NamedArgument {
+                name: ident(child(node, "left")?)?,
+                value: boxed(child(node, "right")?)?,
+                span: span(node)?,
+            }
+        }
+        NodeType::BinaryOperator => {
+            check_keys(node, &["not", "left", "right"])?;
+            let not = node.children().contains_key("not");
+            let op = match (token(node)?.literal.to_uppercase().as_str(), not) {
+                ("*", _) => BinaryOperator::Multiply,
+                ("/", _) => BinaryOperator::Divide,
+                ("||", _) => BinaryOperator::Concat,
+                ("+", _) => BinaryOperator::Plus,
+                ("-", _) => BinaryOperator::Minus,
+                ("<<", _) => BinaryOperator::ShiftLeft,
+                (">>", _) => BinaryOperator::ShiftRight,
+                ("&", _) => BinaryOperator::BitAnd,
+                ("^", _) => BinaryOperator::BitXor,
+                ("|", _) => BinaryOperator::BitOr,
+                ("=", _) => BinaryOperator::Eq,
+                ("!=", _) | ("<>", _) => BinaryOperator::NotEq,
+                ("<", _) => BinaryOperator::Lt,
+                ("<=", _) => BinaryOperator::LtEq,
+                (">", _) => BinaryOperator::Gt,
+                (">=", _) => BinaryOperator::GtEq,
+                ("LIKE", false) => BinaryOperator::Like,
+                ("LIKE", true) => BinaryOperator::NotLike,
+                ("IS", false) => BinaryOperator::Is,
+                ("IS", true) => BinaryOperator::IsNot,
+                ("AND", _) => BinaryOperator::And,
+                ("OR", _) => BinaryOperator::Or,
+                _ => return Err(LowerError::unexpected(node)),
+            };
+            Expr::Binary {
+                op,
+                left: boxed(child(node, "left")?)?,
+                right: boxed(child(node, "right")?)?,
+                span: span(node)?,
+            }
+        }
+        NodeType::BetweenOperator => {
+            check_keys(node, &["left", "not", "right_min", "and", "right_max"])?;
+            Expr::Between {
+                expr: boxed(child(node, "left")?)?,
+                not: node.children().contains_key("not"),
+                low: boxed(child(node, "right_min")?)?,
+                high: boxed(child(node, "right_max")?)?,
+                span: span(node)?,
+            }
+        }
+        NodeType::InOperator => {
+            check_keys(node, &["left", "not", "right"])?;
+            let right = child(node, "right")?;
+            expect_type(right, NodeType::GroupedExprs)?;
+            check_keys(right, &["exprs"])?;
+            Expr::In {
+                expr: boxed(child(node, "left")?)?,
+                not: node.children().contains_key("not"),
+                list: exprs(child_vec(right, "exprs"))?,
+                span: span(node)?,
+            }
+        }
+        NodeType::CaseExpr => {
+            check_keys(node, &["expr", "arms", "end"])?;
+            let mut whens = Vec::new();
+            let mut else_ = None;
+            for arm in child_vec(node, "arms") {
+                check_keys(arm, &["expr", "then", "result"])?;
+                let result = lower_expr(child(arm, "result")?)?;
+                if is(arm, "ELSE") {
+                    else_ = Some(Box::new(result));
+                } else {
+                    whens.push(CaseWhen {
+                        condition: lower_expr(child(arm, "expr")?)?,
+                        result,
+                    });
+                }
+            }
+            Expr::Case {
+                operand: node.get_node("expr").map(boxed).transpose()?,
+                whens,
+                else_,
+                span: span(node)?,
+            }
+        }
+        NodeType::CallingFunction | NodeType::CallingArrayAccessingFunction => lower_call(node)?,
+        NodeType::ArrayAccessing => {
+            check_keys(node, &["left", "right"])?;
+            Expr::Index {
+                expr: boxed(child(node, "left")?)?,
+                index: boxed(child(node, "right")?)?,
+                span: span(node)?,
+            }
+        }
+        NodeType::ArrayLiteral => {
+            check_keys(node, &["type", "exprs"])?;
+            Expr::Array {
+                data_type: literal_type(node)?,
+                elements: exprs(child_vec(node, "exprs"))?,
+                span: span(node)?,
+            }
+        }
+        NodeType::StructLiteral => {
+            check_keys(node, &["type", "exprs"])?;
+            let fields = child_vec(node, "exprs")
+                .iter()
+                .map(aliased_expr)
+                .collect::<LowerResult<_>>()?;
+            Expr::Struct {
+                data_type: literal_type(node)?,
+                fields,
+                span: span(node)?,
+            }
+        }
+        NodeType::IntervalLiteral => {
+            check_keys(node, &["right", "date_part"])?;
+            Expr::Interval {
+                value: boxed(child(node, "right")?)?,
+                date_part: token(child(node, "date_part")?)?.literal.to_uppercase(),
+                span: span(node)?,
+            }
+        }
+        // parentheses are not preserved
+        NodeType::GroupedExpr => {
+            check_keys(node, &["expr"])?;
+            lower_expr(child(node, "expr")?)?
+        }
+        NodeType::SelectStatement | NodeType::SetOperator | NodeType::GroupedStatement => {
+            Expr::Subquery {
+                stmt: Box::new(lower_query(node)?),
+                span: span(node)?,
+            }
+        }
+        _ => return Err(LowerError::unexpected(node)),
+    };
+    Ok(expr)
+}
+
+fn literal(node: &Node) -> LowerResult<Literal> {
+    check_keys(node, &[])?;
+    let token = token(node)?;
+    let kind = match (&node.node_type, &token.kind) {
+        (NodeType::NullLiteral, _) => LiteralKind::Null,
+        (NodeType::BooleanLiteral, _) => LiteralKind::Boolean,
+        (NodeType::NumericLiteral, _) => LiteralKind::Numeric,
+        (NodeType::StringLiteral, TokenKind::String { bytes: true, .. }) => LiteralKind::Bytes,
+        (NodeType::StringLiteral, _) => LiteralKind::String,
+        _ => return Err(LowerError::unexpected(node)),
+    };
+    Ok(Literal {
+        kind,
+        value: token.literal.clone(),
+        span: span(node)?,
+    })
+}
+
+fn lower_call(node: &Node) -> LowerResult<Expr> {
+    check_keys(
+        node,
+        &[
+            "func",
+            "distinct",
+            "args",
+            "ignore_nulls",
+            "orderby",
+            "limit",
+            "over",
+        ],
+    )?;
+    let func = path(child(node, "func")?)?;
+    let args = child_vec(node, "args");
+    match args.first().map(|n| &n.node_type) {
+        Some(NodeType::CastArgument) => {
+            let arg = &args[0];
+            check_keys(arg, &["cast_from", "cast_to", "format"])?;
+            let safe = func.parts.len() == 2 && func.parts[0].value.eq_ignore_ascii_case("SAFE");
+            return Ok(Expr::Cast {
+                safe,
+                expr: boxed(child(arg, "cast_from")?)?,
+                data_type: lower_type(child(arg, "cast_to")?)?,
+                format: keyword_with_expr(arg, "format")?.map(Box::new),
+                span: span(node)?,
+            });
+        }
+        Some(NodeType::ExtractArgument) => {
+            let arg = &args[0];
+            check_keys(
+                arg,
+                &[
+                    "extract_datepart",
+                    "extract_from",
+                    "at_time_zone",
+                    "time_zone",
+                ],
+            )?;
+            return Ok(Expr::Extract {
+                part: ident(child(arg, "extract_datepart")?)?,
+                expr: boxed(child(arg, "extract_from")?)?,
+                time_zone: arg.get_node("time_zone").map(boxed).transpose()?,
+                span: span(node)?,
+            });
+        }
+        _ => (),
+    }
+    let over = match node.get_node("over") {
+        Some(over) => {
+            check_keys(over, &["window"])?;
+            Some(Box::new(lower_window(child(over, "window")?)?))
+        }
+        None => None,
+    };
+    Ok(Expr::Call {
+        func,
+        distinct: node.children().contains_key("distinct"),
+        args: exprs(args)?,
+        ignore_nulls: child_vec(node, "ignore_nulls")
+            .first()
+            .map(|n| is(n, "IGNORE")),
+        orderby: orderby(node, "orderby")?,
+        limit: keyword_with_expr(node, "limit")?.map(Box::new),
+        over,
+        span: span(node)?,
+    })
+}
+
+fn lower_window(node: &Node) -> LowerResult<Window> {
+    if node.node_type == NodeType::Identifier {
+        return Ok(Window::Named(ident(node)?));
+    }
+    expect_type(node, NodeType::WindowSpecification)?;
+    check_keys(node, &["name", "partitionby", "orderby", "frame"])?;
+    let frame = match node.get_node("frame") {
+        Some(frame) => {
+            check_keys(frame, &["between", "start", "and", "end"])?;
+            let unit = if is(frame, "ROWS") {
+                FrameUnit::Rows
+            } else if is(frame, "RANGE") {
+                FrameUnit::Range
+            } else {
+                return Err(LowerError::unexpected(frame));
+            };
+            let end = match frame.get_node_vec("end") {
+                Some(end) => Some(frame_bound(frame, end)?),
+                None => None,
+            };
+            Some(WindowFrame {
+                unit,
+                start: frame_bound(frame, child_vec(frame, "start"))?,
+                end,
+            })
+        }
+        None => None,
+    };
+    Ok(Window::Spec(WindowSpec {
+        name: node.get_node("name").map(ident).transpose()?,
+        partitionby: by_exprs(node, "partitionby")?,
+        orderby: orderby(node, "orderby")?,
+        frame,
+        span: span(node)?,
+    }))
+}
+
+// UNBOUNDED PRECEDING | 1 FOLLOWING | CURRENT ROW | ...
+fn frame_bound(frame: &Node, nodes: &[Node]) -> LowerResult<FrameBound> {
+    let (first, second) = match nodes {
+        [first, second] => (first, second),
+        _ => return Err(LowerError::new(frame, "invalid window frame".to_string())),
+    };
+    let preceding = is(second, "PRECEDING");
+    let bound = if is(first, "CURRENT") {
+        FrameBound::CurrentRow
+    } else if is(first, "UNBOUNDED") && first.node_type == NodeType::Keyword {
+        if preceding {
+            FrameBound::UnboundedPreceding
+        } else {
+            FrameBound::UnboundedFollowing
+        }
+    } else if preceding {
+        FrameBound::Preceding(lower_expr(first)?)
+    } else {
+        FrameBound::Following(lower_expr(first)?)
+    };
+    Ok(bound)
+}
+
+// only `ARRAY<INT64>[]` and `STRUCT<INT64>()` have explicit types
+fn literal_type(node: &Node) -> LowerResult<Option<Type>> {
+    match node.get_node("type") {
+        Some(t) if t.children().contains_key("type_declaration") => Ok(Some(lower_type(t)?)),
+        _ => Ok(None),
+    }
+}
+
+fn lower_type(node: &Node) -> LowerResult<Type> {
+    expect_type(node, NodeType::Type)?;
+    check_keys(node, &["type", "type_declaration", "parameter"])?;
+    let name = token(node)?.literal.to_uppercase();
+    let res = match name.as_str() {
+        "ANY" => Type::Any,
+        "ARRAY" => {
+            let declaration = child(node, "type_declaration")?;
+            check_keys(declaration, &["type"])?;
+            Type::Array(Box::new(lower_type(child(declaration, "type")?)?))
+        }
+        "STRUCT" => {
+            let declaration = child(node, "type_declaration")?;
+            check_keys(declaration, &["declarations"])?;
+            let mut fields = Vec::new();
+            for n in child_vec(declaration, "declarations") {
+                check_keys(n, &["type"])?;
+                fields.push(StructFieldType {
+                    name: match n.token {
+                        Some(_) => Some(ident(n)?),
+                        None => None,
+                    },
+                    data_type: lower_type(child(n, "type")?)?,
+                });
+            }
+            Type::Struct(fields)
+        }
+        _ => {
+            let parameters = match node.get_node("parameter") {
+                Some(p) => {
+                    check_keys(p, &["exprs"])?;
+                    exprs(child_vec(p, "exprs"))?
+                }
+                None => Vec::new(),
+            };
+            Type::Named { name, parameters }
+        }
+    };
+    Ok(res)
+}
@@ -0,0 +1,461 @@
+use super::*;
+use crate::parser::Parser;
+use std::convert::TryFrom;
+
+fn lower_code(code: &str) -> LowerResult<Vec<Statement>> {
+    let stmts = Parser::new(code.to_string()).unwrap().parse_code().unwrap();
+    lower(&stmts)
+}
+
+fn lower_select(code: &str) -> Select {
+    match lower_code(code).unwrap().remove(0) {
+        Statement::Select(select) => select,
+        stmt => panic!("{:?}", stmt),
+    }
+}
+
+// the first expression of `SELECT expr`
+fn lower_expr(code: &str) -> Expr {
+    match lower_select(&format!("SELECT {}", code)).exprs.remove(0) {
+        SelectItem::Expr(e) => e.expr,
+        item => panic!("{:?}", item),
+    }
+}
+
+fn path(expr: &Expr) -> String {
+    match expr {
+        Expr::Path(p) => p.to_string(),
+        _ => panic!("{:?}", expr),
+    }
+}
+
+#[test]
+fn test_lower_select() {
+    let select = lower_select(
+        "\
+WITH cte AS (SELECT 1)
+SELECT DISTINCT t.* EXCEPT (x), a AS b, c d
+FROM `project.dataset.table` t
+WHERE a = 1
+GROUP BY 1
+HAVING TRUE
+ORDER BY a DESC NULLS LAST
+LIMIT 10 OFFSET 5;",
+    );
+    let with = select.with.unwrap();
+    assert_eq!("cte", with.ctes[0].name.value);
+    assert!(matches!(with.ctes[0].stmt, Statement::Select(_)));
+    assert!(select.distinct);
+    match &select.exprs[..] {
+        [SelectItem::Wildcard {
+            qualifier: Some(q),
+            except,
+            ..
+        }, SelectItem::Expr(b), SelectItem::Expr(d)] => {
+            assert_eq!("t", path(q));
+            assert_eq!("x", except[0].value);
+            assert_eq!("b", b.alias.as_ref().unwrap().value);
+            assert_eq!("d", d.alias.as_ref().unwrap().value);
+        }
+        exprs => panic!("{:?}", exprs),
+    }
+    match select.from.unwrap() {
+        TableExpr::Table { name, alias, .. } => {
+            assert_eq!("project.dataset.table", name.to_string());
+            assert!(name.parts[0].quoted);
+            assert_eq!("t", alias.unwrap().value);
+        }
+        from => panic!("{:?}", from),
+    }
+    assert!(matches!(
+        select.where_,
+        Some(Expr::Binary {
+            op: BinaryOperator::Eq,
+            ..
+        })
+    ));
+    assert_eq!(1, select.groupby.len());
+    assert!(select.having.is_some());
+    assert_eq!(Some(SortOrder::Desc), select.orderby[0].order);
+    assert_eq!(Some(NullOrder::Last), select.orderby[0].null_order);
+    let limit = select.limit.unwrap();
+    assert!(matches!(limit.count, Expr::Literal(_)));
+    assert!(limit.offset.is_some());
+    // the semicolon is not a part of the statement
+    assert_eq!((8, 18), (select.span.end_line, select.span.end_column));
+}
+
+#[test]
+fn test_lower_select_names() {
+    let select = lower_select("SELECT a, t.b, f(x).c, 1 AS d, 2, STRUCT(e, 3)");
+    let names: Vec<Option<&str>> = select
+        .exprs
+        .iter()
+        .map(|item| match item {
+            SelectItem::Expr(e) => e.name.as_deref(),
+            item => panic!("{:?}", item),
+        })
+        .collect();
+    assert_eq!(
+        vec![Some("a"), Some("b"), Some("c"), Some("d"), None, None],
+        names
+    );
+    match &select.exprs[5] {
+        SelectItem::Expr(AliasedExpr {
+            expr: Expr::Struct { fields, .. },
+            ..
+        }) => {
+            assert_eq!(Some("e"), fields[0].name.as_deref());
+            assert_eq!(None, fields[1].name);
+        }
+        item => panic!("{:?}", item),
+    }
+}
+
+#[test]
+fn test_lower_set_operation() {
+    match lower_code("SELECT 1 UNION ALL (SELECT 2) EXCEPT DISTINCT SELECT 3")
+        .unwrap()
+        .remove(0)
+    {
+        Statement::SetOperation(SetOperation {
+            operator: SetOperator::Except,
+            distinct: true,
+            left,
+            ..
+        }) => match *left {
+            Statement::SetOperation(SetOperation {
+                operator: SetOperator::Union,
+                distinct: false,
+                right,
+                ..
+            }) => assert!(matches!(*right, Statement::Grouped(_))),
+            stmt => panic!("{:?}", stmt),
+        },
+        stmt => panic!("{:?}", stmt),
+    }
+}
+
+#[test]
+fn test_lower_from() {
+    let select = lower_select(
+        "\
+SELECT *
+FROM t1
+LEFT OUTER JOIN t2 USING (id)
+CROSS JOIN UNNEST(t1.arr) AS x WITH OFFSET AS i,
+  (SELECT 1) AS s
+JOIN ds.f(1) ON TRUE",
+    );
+    let (left, right) = match select.from.unwrap() {
+        TableExpr::Join {
+            join_type: JoinType::Inner,
+            left,
+            right,
+            condition: Some(JoinCondition::On(_)),
+            ..
+        } => (left, right),
+        from => panic!("{:?}", from),
+    };
+    match *right {
+        TableExpr::Call { func, args, .. } => {
+            assert_eq!("ds.f", func.to_string());
+            assert_eq!(1, args.len());
+        }
+        right => panic!("{:?}", right),
+    }
+    let (left, right) = match *left {
+        TableExpr::Join {
+            join_type: JoinType::Comma,
+            left,
+            right,
+            ..
+        } => (left, right),
+        left => panic!("{:?}", left),
+    };
+    assert!(matches!(*right, TableExpr::Subquery { alias: Some(_), .. }));
+    let (left, right) = match *left {
+        TableExpr::Join {
+            join_type: JoinType::Cross,
+            left,
+            right,
+            condition: None,
+            ..
+        } => (left, right),
+        left => panic!("{:?}", left),
+    };
+    match *right {
+        TableExpr::Unnest {
+            expr,
+            alias,
+            with_offset,
+            offset_alias,
+            ..
+        } => {
+            assert_eq!("t1.arr", path(&expr));
+            assert_eq!("x", alias.unwrap().value);
+            assert!(with_offset);
+            assert_eq!("i", offset_alias.unwrap().value);
+        }
+        right => panic!("{:?}", right),
+    }
+    match *left {
+        TableExpr::Join {
+            join_type: JoinType::Left,
+            condition: Some(JoinCondition::Using(columns)),
+            ..
+        } => assert_eq!("id", columns[0].value),
+        left => panic!("{:?}", left),
+    }
+    let select = lower_select("SELECT * FROM t FOR SYSTEM_TIME AS OF ts");
+    assert!(matches!(
+        select.from,
+        Some(TableExpr::Table {
+            for_system_time_as_of: Some(_),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_lower_expr() {
+    match lower_expr("NOT a + 1 BETWEEN 2 AND 3") {
+        Expr::Unary {
+            op: UnaryOperator::Not,
+            expr,
+            ..
+        } => match *expr {
+            Expr::Between {
+                expr, not: false, ..
+            } => assert!(matches!(
+                *expr,
+                Expr::Binary {
+                    op: BinaryOperator::Plus,
+                    ..
+                }
+            )),
+            expr => panic!("{:?}", expr),
+        },
+        expr => panic!("{:?}", expr),
+    }
+    assert!(matches!(
+        lower_expr("x IS NOT NULL"),
+        Expr::Binary {
+            op: BinaryOperator::IsNot,
+            ..
+        }
+    ));
+    match lower_expr("x NOT IN (SELECT 1)") {
+        Expr::In {
+            not: true, list, ..
+        } => assert!(matches!(list[..], [Expr::Subquery { .. }])),
+        expr => panic!("{:?}", expr),
+    }
+    match lower_expr("CASE x WHEN 1 THEN 'a' ELSE 'b' END") {
+        Expr::Case {
+            operand: Some(_),
+            whens,
+            else_: Some(_),
+            ..
+        } => assert_eq!(1, whens.len()),
+        expr => panic!("{:?}", expr),
+    }
+    match lower_expr("SAFE.CAST(x AS ARRAY<STRUCT<a STRING(10), INT64>>)") {
+        Expr::Cast {
+            safe: true,
+            data_type: Type::Array(t),
+            ..
+        } => match *t {
+            Type::Struct(fields) => {
+                assert_eq!("a", fields[0].name.as_ref().unwrap().value);
+                assert!(matches!(
+                    &fields[0].data_type,
+                    Type::Named { name, parameters } if name == "STRING" && parameters.len() == 1
+                ));
+                assert_eq!(None, fields[1].name);
+            }
+            t => panic!("{:?}", t),
+        },
+        expr => panic!("{:?}", expr),
+    }
+    match lower_expr("ARRAY_AGG(DISTINCT x IGNORE NULLS ORDER BY y LIMIT 1) OVER (PARTITION BY z ROWS BETWEEN 1 PRECEDING AND CURRENT ROW)") {
+        Expr::Call {
+            func,
+            distinct: true,
+            ignore_nulls: Some(true),
+            orderby,
+            limit: Some(_),
+            over: Some(over),
+            ..
+        } => {
+            assert_eq!("ARRAY_AGG", func.to_string());
+            assert_eq!(1, orderby.len());
+            match *over {
+                Window::Spec(WindowSpec {
+                    partitionby,
+                    frame: Some(frame),
+                    ..
+                }) => {
+                    assert_eq!(1, partitionby.len());
+                    assert_eq!(FrameUnit::Rows, frame.unit);
+                    assert!(matches!(frame.start, FrameBound::Preceding(_)));
+                    assert_eq!(Some(FrameBound::CurrentRow), frame.end);
+                }
+                over => panic!("{:?}", over),
+            }
+        }
+        expr => panic!("{:?}", expr),
+    }
+    match lower_expr("ST_GEOGFROMTEXT(p, oriented => TRUE)") {
+        Expr::Call { args, .. } => match &args[1] {
+            Expr::NamedArgument { name, .. } => assert_eq!("oriented", name.value),
+            arg => panic!("{:?}", arg),
+        },
+        expr => panic!("{:?}", expr),
+    }
+    match lower_expr("f(x).y[OFFSET(1)]") {
+        Expr::Index { expr, index, .. } => {
+            assert!(matches!(*expr, Expr::Field { .. }));
+            assert!(matches!(*index, Expr::Call { .. }));
+        }
+        expr => panic!("{:?}", expr),
+    }
+    match lower_expr("DATE '2020-01-01'") {
+        Expr::TypedLiteral {
+            data_type, value, ..
+        } => {
+            assert_eq!("DATE", data_type);
+            assert_eq!("'2020-01-01'", value.value);
+        }
+        expr => panic!("{:?}", expr),
+    }
+    assert!(matches!(
+        lower_expr("b'x'"),
+        Expr::Literal(Literal {
+            kind: LiteralKind::Bytes,
+            ..
+        })
+    ));
+    match lower_expr("STRUCT<a INT64>(1)") {
+        Expr::Struct {
+            data_type: Some(Type::Struct(_)),
+            fields,
+            ..
+        } => assert_eq!(1, fields.len()),
+        expr => panic!("{:?}", expr),
+    }
+    // parentheses and aliases are not a part of the expression
+    let expr = lower_expr("(1 + 2) AS x");
+    assert_eq!(
+        (1, 9, 14),
+        (expr.span().line, expr.span().column, expr.span().end_column)
+    );
+    // TryFrom<&Node>
+    let stmts = Parser::new("SELECT 1".to_string())
+        .unwrap()
+        .parse_code()
+        .unwrap();
+    let node = &stmts[0].get_node_vec("exprs").unwrap()[0];
+    assert!(matches!(Expr::try_from(node), Ok(Expr::Literal(_))));
+}
+
+#[test]
+fn test_lower_dml() {
+    let stmts = lower_code(
+        "\
+INSERT INTO t (a, b) VALUES (1, 2), (3, 4);
+INSERT t SELECT 1, 2;
+UPDATE t AS u SET a = 1, u.b = 2 FROM s WHERE TRUE;
+DELETE t WHERE a IS NULL;
+MERGE t USING s ON t.id = s.id
+WHEN MATCHED AND s.x THEN UPDATE SET x = s.x
+WHEN NOT MATCHED BY SOURCE THEN DELETE
+WHEN NOT MATCHED THEN INSERT ROW;
+CREATE TABLE t (x INT64);",
+    )
+    .unwrap();
+    match &stmts[0] {
+        Statement::Insert(Insert {
+            table: Some(table),
+            columns,
+            input: InsertInput::Values(rows),
+            ..
+        }) => {
+            assert_eq!("t", table.to_string());
+            assert_eq!(2, columns.len());
+            assert_eq!(2, rows.len());
+        }
+        stmt => panic!("{:?}", stmt),
+    }
+    assert!(matches!(
+        &stmts[1],
+        Statement::Insert(Insert {
+            input: InsertInput::Query(_),
+            ..
+        })
+    ));
+    match &stmts[2] {
+        Statement::Update(Update {
+            table: Some(table),
+            set,
+            from: Some(_),
+            where_: Some(_),
+            ..
+        }) => {
+            assert_eq!("u", table.alias.as_ref().unwrap().value);
+            assert_eq!("u.b", path(&set[1].target));
+        }
+        stmt => panic!("{:?}", stmt),
+    }
+    assert!(matches!(
+        &stmts[3],
+        Statement::Delete(Delete {
+            where_: Some(_),
+            ..
+        })
+    ));
+    match &stmts[4] {
+        Statement::Merge(Merge { whens, .. }) => {
+            assert!(matches!(
+                whens[0],
+                MergeWhen {
+                    matched: MergeMatch::Matched,
+                    condition: Some(_),
+                    action: MergeAction::Update(_),
+                    ..
+                }
+            ));
+            assert!(matches!(
+                whens[1],
+                MergeWhen {
+                    matched: MergeMatch::NotMatchedBySource,
+                    action: MergeAction::Delete,
+                    ..
+                }
+            ));
+            assert!(matches!(
+                whens[2],
+                MergeWhen {
+                    matched: MergeMatch::NotMatchedByTarget,
+                    action: MergeAction::Insert(Insert {
+                        input: InsertInput::Row,
+                        ..
+                    }),
+                    ..
+                }
+            ));
+        }
+        stmt => panic!("{:?}", stmt),
+    }
+    assert!(matches!(&stmts[5], Statement::Other(_)));
+}
+
+#[test]
+fn test_lower_error() {
+    let error =
+        lower_code("SELECT 1;\nSELECT * FROM t TABLESAMPLE SYSTEM (10 PERCENT)").unwrap_err();
+    assert_eq!("2:17: `tablesample` is not supported", error.to_string());
+    assert_eq!(crate::cst::NodeType::TableSampleClause, error.node_type);
+    let error = lower_code("SELECT * FROM 1").unwrap_err();
+    assert_eq!("1:15: Unexpected NumericLiteral `1`", error.to_string());
+}
@@ -0,0 +1,342 @@
+// the features of a single text document
+// positions of LSP are 0-based and counted in UTF-16 code units
+use bq2cst::resolve::{resolve, Binding, Resolution};
+use bq2cst::token::{Span, Token};
+use bq2cst::{
+    format_str, parse_str_with_recovery, reparse_str_with_recovery, Error, FormatOptions, Node,
+    NodeType, TextEdit,
+};
+use serde_json::{json, Value};
+
+// SymbolKind
+const MODULE: u8 = 2;
+const CLASS: u8 = 5;
+const FUNCTION: u8 = 12;
+const VARIABLE: u8 = 13;
+const STRUCT: u8 = 23;
+
+// DiagnosticSeverity
+const ERROR: u8 = 1;
+
+pub struct Document {
+    text: String,
+    line_starts: Vec<usize>, // UTF-16 offsets
+    stmts: Vec<Node>,
+    errors: Vec<Error>,
+    resolution: Resolution,
+}
+
+impl Document {
+    pub fn new(text: String) -> Document {
+        // lexical errors cannot be recovered
+        let (stmts, errors) = match parse_str_with_recovery(&text) {
+            Ok(res) => res,
+            Err(error) => (Vec::new(), vec![error]),
+        };
+        Document::with_stmts(text, stmts, errors)
+    }
+    fn with_stmts(text: String, stmts: Vec<Node>, errors: Vec<Error>) -> Document {
+        let mut line_starts = vec![0];
+        let mut offset = 0;
+        for ch in text.chars() {
+            offset += ch.len_utf16();
+            if ch == '\n' {
+                line_starts.push(offset);
+            }
+        }
+        let resolution = resolve(&stmts);
+        Document {
+            text,
+            line_starts,
+            stmts,
+            errors,
+            resolution,
+        }
+    }
+
+    // only the edited statements are reparsed
+    pub fn edit(mut self, range: &Value, text: &str) -> Document {
+        let (start, end) = match (
+            self.byte_offset(&range["start"]),
+            self.byte_offset(&range["end"]),
+        ) {
+            (Some(start), Some(end)) if start <= end => (start, end),
+            _ => return self, // invalid range
+        };
+        let edit = TextEdit::new(start, end, text);
+        let new_text = edit.apply(&self.text);
+        // there is no statement to be reused after a lexical error
+        if self.stmts.is_empty() {
+            return Document::new(new_text);
+        }
+        let stmts = std::mem::take(&mut self.stmts);
+        let (stmts, errors) = match reparse_str_with_recovery(&self.text, stmts, &edit) {
+            Ok(res) => res,
+            Err(error) => (Vec::new(), vec![error]),
+        };
+        Document::with_stmts(new_text, stmts, errors)
+    }
+
+    // ----- positions -----
+    fn position(&self, line: usize, offset_utf16: usize) -> Value {
+        let line = line.saturating_sub(1).min(self.line_starts.len() - 1);
+        let character = offset_utf16.saturating_sub(self.line_starts[line]);
+        json!({ "line": line, "character": character })
+    }
+    fn range(&self, span: &Span) -> Value {
+        json!({
+            "start": self.position(span.line, span.start_utf16),
+            "end": self.position(span.end_line, span.end_utf16),
+        })
+    }
+    // `ParseError` raised by the lexer does not have a token
+    fn error_range(&self, error: &Error) -> Value {
+        if let Some(token) = &error.token {
+            return self.range(&token.span());
+        }
+        let line = error.line.saturating_sub(1);
+        let text = self.text.split('\n').nth(line).unwrap_or("");
+        let character: usize = text
+            .chars()
+            .take(error.column.saturating_sub(1))
+            .map(char::len_utf16)
+            .sum();
+        let position = json!({ "line": line, "character": character });
+        json!({ "start": position, "end": position })
+    }
+    // None if the position is out of the document
+    fn offset(&self, position: &Value) -> Option<usize> {
+        let line = position["line"].as_u64()? as usize;
+        let character = position["character"].as_u64()? as usize;
+        Some(self.line_starts.get(line)? + character)
+    }
+    // the byte offset in `text` (the position is clamped to the end of the line)
+    fn byte_offset(&self, position: &Value) -> Option<usize> {
+        let line = position["line"].as_u64()? as usize;
+        let mut character = position["character"].as_u64()? as usize;
+        let text = match self.text.split('\n').nth(line) {
+            Some(text) => text,
+            None => return Some(self.text.len()),
+        };
+        let mut offset: usize = self.text.split('\n').take(line).map(|t| t.len() + 1).sum();
+        for ch in text.chars() {
+            if character == 0 {
+                break;
+            }
+            character = character.saturating_sub(ch.len_utf16());
+            offset += ch.len_utf8();
+        }
+        Some(offset)
+    }
+    fn end(&self) -> Value {
+        let offset = self.text.chars().map(char::len_utf16).sum();
+        self.position(self.line_starts.len(), offset)
+    }
+
+    // ----- features -----
+    pub fn diagnostics(&self) -> Value {
+        let diagnostics: Vec<Value> = self
+            .errors
+            .iter()
+            .map(|e| {
+                json!({
+                    "range": self.error_range(e),
+                    "severity": ERROR,
+                    "source": "bq2cst",
+                    "message": e.message,
+                })
+            })
+            .collect();
+        Value::Array(diagnostics)
+    }
+
+    pub fn symbols(&self) -> Value {
+        let symbols: Vec<Value> = self
+            .stmts
+            .iter()
+            .flat_map(|stmt| self.statement_symbols(stmt))
+            .collect();
+        Value::Array(symbols)
+    }
+    fn symbol(&self, name: &str, detail: &str, kind: u8, range: &Span, selection: &Span) -> Value {
+        json!({
+            "name": name,
+            "detail": detail,
+            "kind": kind,
+            "range": self.range(range),
+            "selectionRange": self.range(selection),
+        })
+    }
+    fn statement_symbols(&self, stmt: &Node) -> Vec<Value> {
+        let span = match code_span(stmt) {
+            Some(span) => span,
+            None => return Vec::new(), // EOF
+        };
+        let mut symbol = match stmt.node_type {
+            NodeType::EOF | NodeType::Error => return Vec::new(),
+            // DECLARE x, y INT64;
+            NodeType::DeclareStatement => {
+                let idents = stmt.get_node_vec("idents").into_iter().flatten();
+                return idents
+                    .filter_map(|ident| {
+                        let token = ident.token.as_ref()?;
+                        let symbol =
+                            self.symbol(&token.literal, "DECLARE", VARIABLE, &span, &token.span());
+                        Some(symbol)
+                    })
+                    .collect();
+            }
+            NodeType::CreateTableStatement
+            | NodeType::CreateViewStatement
+            | NodeType::CreateFunctionStatement
+            | NodeType::CreateProcedureStatement => {
+                let ident = stmt.get_node("ident").and_then(code_span);
+                let what = stmt.get_node("what").and_then(|n| n.token.as_ref());
+                let (ident, what) = match (ident, what) {
+                    (Some(ident), Some(what)) => (ident, what.literal.to_uppercase()),
+                    _ => return Vec::new(),
+                };
+                let kind = match what.as_str() {
+                    "FUNCTION" | "PROCEDURE" => FUNCTION,
+                    _ => CLASS, // TABLE, VIEW, MATERIALIZED VIEW, ...
+                };
+                let name = &self.text[ident.start..ident.end];
+                self.symbol(name, &format!("CREATE {}", what), kind, &span, &ident)
+            }
+            // the first line of the statement
+            _ => {
+                let rest = &self.text[span.start..span.end];
+                let name = rest.lines().next().unwrap_or("");
+                let name = name.trim_end().trim_end_matches(';');
+                let selection = Span {
+                    end_line: span.line,
+                    end_column: span.column + name.chars().count(),
+                    end: span.start + name.len(),
+                    end_utf16: span.start_utf16 + name.encode_utf16().count(),
+                    ..span.clone()
+                };
+                self.symbol(name, "", MODULE, &span, &selection)
+            }
+        };
+        let children = self.child_symbols(stmt);
+        if !children.is_empty() {
+            symbol["children"] = Value::Array(children);
+        }
+        vec![symbol]
+    }
+    // CTEs and the statements in the block
+    fn child_symbols(&self, node: &Node) -> Vec<Value> {
+        let mut symbols = Vec::new();
+        for (key, child) in node.children_in_source_order() {
+            let is_stmt = key == "stmts"
+                || (key == "stmt" && node.node_type == NodeType::CreateProcedureStatement);
+            if is_stmt {
+                symbols.extend(self.statement_symbols(child));
+            } else if child.node_type == NodeType::WithQuery {
+                let (span, token) = match (code_span(child), &child.token) {
+                    (Some(span), Some(token)) => (span, token),
+                    _ => continue,
+                };
+                let mut symbol = self.symbol(&token.literal, "WITH", STRUCT, &span, &token.span());
+                let children = self.child_symbols(child);
+                if !children.is_empty() {
+                    symbol["children"] = Value::Array(children);
+                }
+                symbols.push(symbol);
+            } else {
+                symbols.extend(self.child_symbols(child));
+            }
+        }
+        symbols
+    }
+
+    // CTEs, aliases, variables, ...
+    pub fn definition(&self, uri: &str, position: &Value) -> Value {
+        let offset = match self.offset(position) {
+            Some(offset) => offset,
+            None => return Value::Null,
+        };
+        let reference = self
+            .resolution
+            .references
+            .iter()
+            .find(|r| r.span.start_utf16 <= offset && offset <= r.span.end_utf16);
+        let span = match reference.map(|r| &r.binding) {
+            Some(Binding::Cte(span))
+            | Some(Binding::Relation(span))
+            | Some(Binding::SelectAlias(span))
+            | Some(Binding::Window(span))
+            | Some(Binding::Variable(span))
+            | Some(Binding::Parameter(span)) => span,
+            _ => return Value::Null,
+        };
+        json!({ "uri": uri, "range": self.range(span) })
+    }
+
+    pub fn folding_ranges(&self) -> Value {
+        let mut ranges = Vec::new();
+        for stmt in &self.stmts {
+            self.collect_folding_ranges(stmt, &mut ranges);
+        }
+        Value::Array(ranges)
+    }
+    fn collect_folding_ranges(&self, node: &Node, ranges: &mut Vec<Value>) {
+        let folded = match node.node_type {
+            NodeType::BeginStatement
+            | NodeType::IfStatement
+            | NodeType::LoopStatement
+            | NodeType::WhileStatement => Some(node),
+            // the body of CTE
+            NodeType::WithQuery => node.get_node("stmt"),
+            _ => None,
+        };
+        if let Some(span) = folded.and_then(code_span) {
+            if span.line < span.end_line {
+                ranges.push(json!({
+                    "startLine": span.line - 1,
+                    "endLine": span.end_line - 1,
+                }));
+            }
+        }
+        for (_, child) in node.children_in_source_order() {
+            self.collect_folding_ranges(child, ranges);
+        }
+    }
+
+    // None if the document cannot be parsed
+    pub fn format(&self, options: &Value) -> Value {
+        let mut format_options = FormatOptions::default();
+        if let Some(tab_size) = options["tabSize"].as_u64() {
+            format_options.indent_width = tab_size as usize;
+        }
+        let formatted = match format_str(&self.text, &format_options) {
+            Ok(formatted) => formatted,
+            Err(_) => return Value::Null,
+        };
+        if formatted == self.text {
+            return json!([]);
+        }
+        json!([{
+            "range": { "start": { "line": 0, "character": 0 }, "end": self.end() },
+            "newText": formatted,
+        }])
+    }
+}
+
+// the range of the node excluding comments
+fn code_span(node: &Node) -> Option<Span> {
+    let tokens: Vec<&Token> = node
+        .tokens()
+        .into_iter()
+        .filter(|t| !t.is_comment())
+        .collect();
+    let first = tokens.first()?;
+    let last = tokens.last()?;
+    Some(Span {
+        end_line: last.end_line,
+        end_column: last.end_column,
+        end: last.end,
+        end_utf16: last.end_utf16,
+        ..first.span()
+    })
+}
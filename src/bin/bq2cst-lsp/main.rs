@@ -0,0 +1,148 @@
+// Language Server Protocol over stdio
+// documents are synchronized incrementally (`TextDocumentSyncKind.Incremental`)
+mod document;
+mod rpc;
+
+use document::Document;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufWriter};
+use std::process;
+
+#[derive(Default)]
+struct Server {
+    documents: HashMap<String, Document>,
+    shutdown: bool,
+}
+
+type RequestResult = Result<Value, (i64, String)>;
+
+fn main() {
+    let stdin = io::stdin();
+    let mut reader = stdin.lock();
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+    let mut server = Server::default();
+    loop {
+        let message = match rpc::read_message(&mut reader) {
+            Ok(Some(message)) => message,
+            Ok(None) => process::exit(1), // the client exited without `exit`
+            Err(e) => {
+                eprintln!("bq2cst-lsp: {}", e);
+                process::exit(1);
+            }
+        };
+        let method = message["method"].as_str().unwrap_or("");
+        let params = &message["params"];
+        let outgoing = match message.get("id") {
+            // responses to the server are ignored because it sends no request
+            Some(_) if method.is_empty() => Vec::new(),
+            Some(id) => {
+                let response = match server.request(method, params) {
+                    Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    Err((code, message)) => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": { "code": code, "message": message },
+                    }),
+                };
+                vec![response]
+            }
+            None if method == "exit" => process::exit(if server.shutdown { 0 } else { 1 }),
+            None => server.notify(method, params),
+        };
+        for message in outgoing {
+            if let Err(e) = rpc::write_message(&mut writer, &message) {
+                eprintln!("bq2cst-lsp: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+impl Server {
+    fn request(&mut self, method: &str, params: &Value) -> RequestResult {
+        if self.shutdown {
+            return Err((rpc::INVALID_REQUEST, "the server is shut down".to_string()));
+        }
+        match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": 2,
+                    "documentSymbolProvider": true,
+                    "definitionProvider": true,
+                    "foldingRangeProvider": true,
+                    "documentFormattingProvider": true,
+                },
+                "serverInfo": { "name": "bq2cst-lsp", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            "textDocument/documentSymbol" => Ok(self.document(params)?.symbols()),
+            "textDocument/definition" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or("");
+                Ok(self.document(params)?.definition(uri, &params["position"]))
+            }
+            "textDocument/foldingRange" => Ok(self.document(params)?.folding_ranges()),
+            "textDocument/formatting" => Ok(self.document(params)?.format(&params["options"])),
+            _ => Err((
+                rpc::METHOD_NOT_FOUND,
+                format!("unsupported method `{}`", method),
+            )),
+        }
+    }
+    fn document(&self, params: &Value) -> Result<&Document, (i64, String)> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or("");
+        self.documents
+            .get(uri)
+            .ok_or_else(|| (rpc::INVALID_PARAMS, format!("unknown document `{}`", uri)))
+    }
+    // returns notifications to the client
+    fn notify(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = match params["textDocument"]["uri"].as_str() {
+            Some(uri) => uri.to_string(),
+            None => return Vec::new(), // `initialized`, `$/cancelRequest`, ...
+        };
+        let document = match method {
+            "textDocument/didOpen" => params["textDocument"]["text"]
+                .as_str()
+                .map(|text| Document::new(text.to_string())),
+            "textDocument/didChange" => {
+                let mut document = self.documents.remove(&uri);
+                let changes = params["contentChanges"].as_array().into_iter().flatten();
+                for change in changes {
+                    let text = change["text"].as_str().unwrap_or("");
+                    document = match (document, change.get("range")) {
+                        (Some(document), Some(range)) => Some(document.edit(range, text)),
+                        (None, Some(_)) => None, // unknown document
+                        // the whole text
+                        (_, None) => Some(Document::new(text.to_string())),
+                    };
+                }
+                document
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                return vec![publish_diagnostics(&uri, json!([]))];
+            }
+            _ => None,
+        };
+        let document = match document {
+            Some(document) => document,
+            None => return Vec::new(),
+        };
+        let diagnostics = document.diagnostics();
+        self.documents.insert(uri.clone(), document);
+        vec![publish_diagnostics(&uri, diagnostics)]
+    }
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
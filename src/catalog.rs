@@ -0,0 +1,433 @@
+// table schemas for semantic validation
+// tables are loaded from `bq show --schema` (JSON) or `CREATE TABLE` statements
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{Node, NodeType};
+use crate::functions;
+use crate::infer;
+use crate::lineage::Schema;
+use crate::names;
+use crate::token::Span;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Type {
+    Int64,
+    Float64,
+    Numeric,
+    BigNumeric,
+    Bool,
+    String,
+    Bytes,
+    Date,
+    Datetime,
+    Time,
+    Timestamp,
+    Interval,
+    Geography,
+    Json,
+    Array(Box<Type>),
+    Struct(Vec<Field>),
+    Unknown, // e.g. the columns of tables without schema
+}
+
+impl Type {
+    // INT64, INTEGER, STRING(10), ... (case insensitive)
+    // None if it is not a scalar type
+    pub fn from_name(name: &str) -> Option<Type> {
+        let name = name.split('(').next().unwrap_or("").trim();
+        let ty = match name.to_uppercase().as_str() {
+            "INT64" | "INT" | "SMALLINT" | "INTEGER" | "BIGINT" | "TINYINT" | "BYTEINT" => {
+                Type::Int64
+            }
+            "FLOAT64" | "FLOAT" => Type::Float64,
+            "NUMERIC" | "DECIMAL" => Type::Numeric,
+            "BIGNUMERIC" | "BIGDECIMAL" => Type::BigNumeric,
+            "BOOL" | "BOOLEAN" => Type::Bool,
+            "STRING" => Type::String,
+            "BYTES" => Type::Bytes,
+            "DATE" => Type::Date,
+            "DATETIME" => Type::Datetime,
+            "TIME" => Type::Time,
+            "TIMESTAMP" => Type::Timestamp,
+            "INTERVAL" => Type::Interval,
+            "GEOGRAPHY" => Type::Geography,
+            "JSON" => Type::Json,
+            _ => return None,
+        };
+        Some(ty)
+    }
+    // `Type` node (e.g. column definitions of `CREATE TABLE`)
+    pub fn from_node(node: &Node) -> Type {
+        let literal = match &node.token {
+            Some(token) => token.literal.to_uppercase(),
+            None => return Type::Unknown,
+        };
+        let declaration = node.get_node("type_declaration");
+        match literal.as_str() {
+            "ARRAY" => {
+                let element = declaration.and_then(|d| d.get_node("type"));
+                Type::Array(Box::new(element.map_or(Type::Unknown, Type::from_node)))
+            }
+            "STRUCT" => {
+                let fields = declaration.and_then(|d| d.get_node_vec("declarations"));
+                Type::Struct(fields.into_iter().flatten().map(field).collect())
+            }
+            _ => Type::from_name(&literal).unwrap_or(Type::Unknown),
+        }
+    }
+    // the field of STRUCT (case insensitive)
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        match self {
+            Type::Struct(fields) => fields.iter().find(|f| f.name.eq_ignore_ascii_case(name)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Type::Int64 => "INT64",
+            Type::Float64 => "FLOAT64",
+            Type::Numeric => "NUMERIC",
+            Type::BigNumeric => "BIGNUMERIC",
+            Type::Bool => "BOOL",
+            Type::String => "STRING",
+            Type::Bytes => "BYTES",
+            Type::Date => "DATE",
+            Type::Datetime => "DATETIME",
+            Type::Time => "TIME",
+            Type::Timestamp => "TIMESTAMP",
+            Type::Interval => "INTERVAL",
+            Type::Geography => "GEOGRAPHY",
+            Type::Json => "JSON",
+            Type::Array(element) => return write!(f, "ARRAY<{}>", element),
+            Type::Struct(fields) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|field| match field.name.as_str() {
+                        "" => field.data_type.to_string(),
+                        name => format!("{} {}", name, field.data_type),
+                    })
+                    .collect();
+                return write!(f, "STRUCT<{}>", fields.join(", "));
+            }
+            Type::Unknown => "UNKNOWN",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "SchemaField", into = "SchemaField")
+)]
+pub struct Field {
+    pub name: String, // empty if anonymous (e.g. STRUCT<INT64>)
+    pub data_type: Type,
+}
+
+impl Field {
+    pub fn new(name: &str, data_type: Type) -> Field {
+        Field {
+            name: name.to_string(),
+            data_type,
+        }
+    }
+}
+
+// `TypeDeclaration` node
+fn field(node: &Node) -> Field {
+    let name = match &node.token {
+        Some(_) => names::unquote(node),
+        None => String::new(),
+    };
+    let data_type = node.get_node("type").map_or(Type::Unknown, Type::from_node);
+    Field { name, data_type }
+}
+
+// an element of `bq show --schema`
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct SchemaField {
+    name: String,
+    #[serde(rename = "type")]
+    data_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<SchemaField>,
+}
+
+#[cfg(feature = "serde")]
+impl From<SchemaField> for Field {
+    fn from(field: SchemaField) -> Field {
+        let data_type = match field.data_type.to_uppercase().as_str() {
+            "RECORD" | "STRUCT" => {
+                Type::Struct(field.fields.into_iter().map(Field::from).collect())
+            }
+            name => Type::from_name(name).unwrap_or(Type::Unknown),
+        };
+        let data_type = match field.mode.as_deref() {
+            Some(mode) if mode.eq_ignore_ascii_case("REPEATED") => Type::Array(Box::new(data_type)),
+            _ => data_type,
+        };
+        Field {
+            name: field.name,
+            data_type,
+        }
+    }
+}
+
+// unknown types are written as `UNKNOWN`
+#[cfg(feature = "serde")]
+impl From<Field> for SchemaField {
+    fn from(field: Field) -> SchemaField {
+        let (data_type, mode) = match field.data_type {
+            Type::Array(element) => (*element, Some("REPEATED".to_string())),
+            data_type => (data_type, None),
+        };
+        let (data_type, fields) = match data_type {
+            Type::Struct(fields) => {
+                let fields = fields.into_iter().map(SchemaField::from).collect();
+                ("RECORD".to_string(), fields)
+            }
+            data_type => (data_type.to_string(), Vec::new()),
+        };
+        SchemaField {
+            name: field.name,
+            data_type,
+            mode,
+            fields,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Table {
+    pub name: String,                // `project.dataset.table`, `dataset.table`, ...
+    pub columns: Option<Vec<Field>>, // None if unknown (e.g. `CREATE TABLE t AS SELECT * FROM u`)
+}
+
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Catalog {
+    tables: Vec<Table>,
+}
+
+impl Catalog {
+    pub fn new() -> Catalog {
+        Catalog::default()
+    }
+    // the table of the same name is replaced
+    pub fn add_table(&mut self, name: &str, columns: Vec<Field>) {
+        self.push(Table {
+            name: name.to_string(),
+            columns: Some(columns),
+        });
+    }
+    // `json` is the output of `bq show --schema`
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    pub fn add_table_json(&mut self, name: &str, json: &str) -> Result<(), String> {
+        let columns: Vec<Field> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        self.add_table(name, columns);
+        Ok(())
+    }
+    // `CREATE TABLE` and `CREATE VIEW` statements (including those in `BEGIN ... END`)
+    // the columns of `CREATE TABLE ... AS SELECT` are inferred from the tables in the catalog
+    pub fn add_ddl(&mut self, stmts: &[Node]) {
+        for table in infer::analyze(stmts, Some(self)).tables {
+            self.push(table);
+        }
+    }
+    fn push(&mut self, table: Table) {
+        self.tables.retain(|t| t.name != table.name);
+        self.tables.push(table);
+    }
+    // `dataset.table` matches `project.dataset.table` and vice versa
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.iter().rev().find(|t| same_table(&t.name, name))
+    }
+    pub fn tables(&self) -> &[Table] {
+        &self.tables
+    }
+}
+
+// used to expand `SELECT *` in `lineage()`
+impl Schema for Catalog {
+    fn columns(&self, table: &str) -> Option<Vec<String>> {
+        let columns = self.table(table)?.columns.as_ref()?;
+        Some(columns.iter().map(|c| c.name.clone()).collect())
+    }
+}
+
+pub(crate) fn same_table(a: &str, b: &str) -> bool {
+    // `project:dataset.table` is the legacy syntax
+    let a = a.replace(':', ".");
+    let b = b.replace(':', ".");
+    let (long, short) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    long == short || long.ends_with(&format!(".{}", short))
+}
+
+// the table created by `CREATE TABLE` or `CREATE VIEW`
+// `query` is the columns of `AS SELECT ...`
+pub(crate) fn created_table(node: &Node, query: Option<Vec<Field>>) -> Option<Table> {
+    let columns = match node.node_type {
+        NodeType::CreateTableStatement => match node.get_node("column_schema_group") {
+            Some(group) => {
+                let declarations = group.get_node_vec("declarations").into_iter().flatten();
+                Some(declarations.map(field).collect())
+            }
+            None => query,
+        },
+        // CREATE VIEW v (x, y) AS SELECT ...
+        NodeType::CreateViewStatement => match node.get_node("column_name_list") {
+            Some(list) => {
+                let names: &[Node] = list.get_node_vec("exprs").map_or(&[], |e| e);
+                let types: Vec<Type> = match query {
+                    Some(query) if query.len() == names.len() => {
+                        query.into_iter().map(|c| c.data_type).collect()
+                    }
+                    _ => vec![Type::Unknown; names.len()],
+                };
+                let columns = names.iter().zip(types);
+                Some(
+                    columns
+                        .map(|(n, ty)| Field::new(&names::unquote(n), ty))
+                        .collect(),
+                )
+            }
+            None => query,
+        },
+        _ => return None,
+    };
+    let name = names::table_name(node.get_node("ident")?)?;
+    Some(Table { name, columns })
+}
+
+// a problem found by `validate()`
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ValidationError {
+    pub message: String,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub span: Span,
+}
+
+// a problem found while names are resolved and types are inferred
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) enum Issue {
+    UnknownTable {
+        name: String,
+        span: Span,
+    },
+    UnknownName {
+        name: String,
+        span: Span,
+    },
+    UnknownColumn {
+        relation: String,
+        column: String,
+        span: Span,
+    }, // t.col
+    AmbiguousColumn {
+        name: String,
+        span: Span,
+    },
+    UnknownInsertColumn {
+        table: String,
+        column: String,
+        span: Span,
+    }, // INSERT t (col)
+    UnknownField {
+        field: String,
+        ty: Type,
+        span: Span,
+    },
+    NotStruct {
+        field: String,
+        ty: Type,
+        span: Span,
+    },
+    NotArray {
+        ty: Type,
+        span: Span,
+    },
+}
+
+// columns which every table has
+const PSEUDO_COLUMNS: &[&str] = &["_PARTITIONTIME", "_PARTITIONDATE", "_TABLE_SUFFIX"];
+
+fn is_pseudo_column(name: &str) -> bool {
+    PSEUDO_COLUMNS.iter().any(|c| c.eq_ignore_ascii_case(name))
+}
+
+// INFORMATION_SCHEMA views and wildcard tables are not listed in catalogs
+fn is_special_table(name: &str) -> bool {
+    name.to_uppercase().contains("INFORMATION_SCHEMA") || name.ends_with('*')
+}
+
+impl Issue {
+    // None if it is not an error in BigQuery (pseudo columns and special tables)
+    fn into_error(self) -> Option<ValidationError> {
+        let (message, span) = match self {
+            Issue::UnknownTable { name, .. } if is_special_table(&name) => return None,
+            Issue::UnknownTable { name, span } => (format!("Table not found: {}", name), span),
+            Issue::UnknownName { name, .. } if is_pseudo_column(&name) => return None,
+            Issue::UnknownName { name, span } => (format!("Unrecognized name: {}", name), span),
+            Issue::UnknownColumn { column, .. } if is_pseudo_column(&column) => return None,
+            Issue::UnknownColumn {
+                relation,
+                column,
+                span,
+            } => (
+                format!("Name {} not found inside {}", column, relation),
+                span,
+            ),
+            Issue::AmbiguousColumn { name, span } => {
+                (format!("Column name {} is ambiguous", name), span)
+            }
+            Issue::UnknownInsertColumn {
+                table,
+                column,
+                span,
+            } => (
+                format!("Column {} is not present in table {}", column, table),
+                span,
+            ),
+            Issue::UnknownField { field, ty, span } => (
+                format!("Field name {} does not exist in {}", field, ty),
+                span,
+            ),
+            Issue::NotStruct { field, ty, span } => (
+                format!("Cannot access field {} on a value with type {}", field, ty),
+                span,
+            ),
+            Issue::NotArray { ty, span } => (
+                format!(
+                    "Element access using [] is not supported on values of type {}",
+                    ty
+                ),
+                span,
+            ),
+        };
+        Some(ValidationError { message, span })
+    }
+}
+
+// unknown tables and columns, ambiguous columns, field access on non-STRUCT values, ...
+// tables created in the statements are also available after their creation
+// calls of built-in functions are validated by `functions::validate()`
+pub fn validate(stmts: &[Node], catalog: &Catalog) -> Vec<ValidationError> {
+    let issues = infer::analyze(stmts, Some(catalog)).issues;
+    let mut errors: Vec<ValidationError> =
+        issues.into_iter().filter_map(Issue::into_error).collect();
+    errors.extend(functions::validate(stmts));
+    errors.sort_by_key(|e| e.span.start);
+    errors
+}
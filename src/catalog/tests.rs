@@ -0,0 +1,263 @@
+use super::*;
+use crate::parser::Parser;
+
+fn parse(code: &str) -> Vec<Node> {
+    Parser::new(code.to_string()).unwrap().parse_code().unwrap()
+}
+
+const DDL: &str = "\
+CREATE TABLE ds.users (
+  id INT64,
+  name STRING,
+  address STRUCT<city STRING, zip STRING>,
+  tags ARRAY<STRING>,
+  events ARRAY<STRUCT<ts TIMESTAMP, kind STRING>>,
+  payload JSON
+);
+CREATE TABLE ds.orders (id INT64, user_id INT64, amount NUMERIC(10, 2));
+";
+
+// one line per error: `line:column message`
+fn test_validate(code: &str, expected: &[&str]) {
+    let mut catalog = Catalog::new();
+    catalog.add_ddl(&parse(DDL));
+    let result: Vec<String> = validate(&parse(code), &catalog)
+        .iter()
+        .map(|e| format!("{}:{} {}", e.span.line, e.span.column, e.message))
+        .collect();
+    assert_eq!(expected, result, "{}", code);
+}
+
+#[test]
+fn test_type() {
+    assert_eq!(Some(Type::Int64), Type::from_name("integer"));
+    assert_eq!(Some(Type::String), Type::from_name("STRING(10)"));
+    assert_eq!(None, Type::from_name("RECORD"));
+    let mut catalog = Catalog::new();
+    catalog.add_ddl(&parse(DDL));
+    let users = catalog.table("users").unwrap();
+    let types: Vec<String> = users
+        .columns
+        .iter()
+        .flatten()
+        .map(|c| format!("{} {}", c.name, c.data_type))
+        .collect();
+    assert_eq!(
+        vec![
+            "id INT64",
+            "name STRING",
+            "address STRUCT<city STRING, zip STRING>",
+            "tags ARRAY<STRING>",
+            "events ARRAY<STRUCT<ts TIMESTAMP, kind STRING>>",
+            "payload JSON",
+        ],
+        types
+    );
+    assert_eq!(
+        Type::Numeric,
+        catalog
+            .table("ds.orders")
+            .unwrap()
+            .columns
+            .as_ref()
+            .unwrap()[2]
+            .data_type
+    );
+}
+
+#[test]
+fn test_table() {
+    let mut catalog = Catalog::new();
+    catalog.add_table("project.ds.t", vec![Field::new("a", Type::Int64)]);
+    assert!(catalog.table("project.ds.t").is_some());
+    assert!(catalog.table("ds.t").is_some());
+    assert!(catalog.table("project:ds.t").is_some());
+    assert!(catalog.table("other.t").is_none());
+    assert!(catalog.table("s.t").is_none());
+    // replaced
+    catalog.add_table("project.ds.t", vec![]);
+    assert_eq!(1, catalog.tables().len());
+    assert_eq!(Some(vec![]), catalog.columns("ds.t"));
+    // views and CTAS
+    catalog.add_ddl(&parse(
+        "\
+CREATE VIEW ds.v (x, y) AS SELECT 1, 2;
+BEGIN
+  CREATE TABLE ds.u AS SELECT a, b AS c FROM ds.t;
+  CREATE TABLE ds.w AS SELECT * FROM ds.x;
+END;",
+    ));
+    let names = vec!["x".to_string(), "y".to_string()];
+    assert_eq!(Some(names), catalog.columns("ds.v"));
+    let names = vec!["a".to_string(), "c".to_string()];
+    assert_eq!(Some(names), catalog.columns("ds.u"));
+    assert_eq!(None, catalog.columns("ds.w"));
+    assert!(catalog.table("ds.w").is_some());
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_add_table_json() {
+    let json = r#"[
+  {"name": "id", "type": "INTEGER", "mode": "REQUIRED"},
+  {"name": "tags", "type": "STRING", "mode": "REPEATED"},
+  {"name": "address", "type": "RECORD", "mode": "NULLABLE", "fields": [
+    {"name": "city", "type": "STRING"},
+    {"name": "points", "type": "RECORD", "mode": "REPEATED", "fields": [
+      {"name": "lat", "type": "FLOAT"}
+    ]}
+  ]}
+]"#;
+    let mut catalog = Catalog::new();
+    catalog.add_table_json("ds.t", json).unwrap();
+    let columns = catalog.table("ds.t").unwrap().columns.clone().unwrap();
+    assert_eq!(Type::Int64, columns[0].data_type);
+    assert_eq!("ARRAY<STRING>", columns[1].data_type.to_string());
+    assert_eq!(
+        "STRUCT<city STRING, points ARRAY<STRUCT<lat FLOAT64>>>",
+        columns[2].data_type.to_string()
+    );
+    assert!(catalog.add_table_json("ds.u", "{}").is_err());
+    // the same format is used to serialize
+    let json = serde_json::to_string(&columns).unwrap();
+    catalog.add_table_json("ds.u", &json).unwrap();
+    assert_eq!(
+        Some(&columns),
+        catalog.table("ds.u").unwrap().columns.as_ref()
+    );
+}
+
+#[test]
+fn test_validate_tables_and_columns() {
+    test_validate(
+        "SELECT id, nmae, u.nam FROM ds.users AS u",
+        &[
+            "1:12 Unrecognized name: nmae",
+            "1:20 Name nam not found inside u",
+        ],
+    );
+    test_validate(
+        "SELECT * FROM ds.user JOIN `ds`.orders USING (id)",
+        &["1:15 Table not found: ds.user"],
+    );
+    test_validate(
+        "\
+SELECT id, u.id, user_id
+FROM ds.users AS u JOIN ds.orders AS o ON u.id = o.user_id",
+        &["1:8 Column name id is ambiguous"],
+    );
+    test_validate(
+        "SELECT id, amount FROM ds.users JOIN ds.orders USING (id)",
+        &[],
+    );
+    // special tables and pseudo columns
+    test_validate(
+        "\
+SELECT _TABLE_SUFFIX FROM `ds.events_*`;
+SELECT * FROM ds.INFORMATION_SCHEMA.TABLES;
+SELECT _PARTITIONTIME, o._PARTITIONDATE FROM ds.orders AS o;",
+        &[],
+    );
+    // CTE and subquery
+    test_validate(
+        "\
+WITH c AS (SELECT id FROM ds.users)
+SELECT name, c.name, s.x, s.y FROM c, (SELECT 1 AS x) AS s",
+        &[
+            "2:8 Unrecognized name: name",
+            "2:16 Name name not found inside c",
+            "2:29 Name y not found inside s",
+        ],
+    );
+    // `SELECT *` is expanded
+    test_validate(
+        "WITH c AS (SELECT * FROM ds.orders) SELECT amount, amnt FROM c",
+        &["1:52 Unrecognized name: amnt"],
+    );
+    // correlated subquery (the inner table is preferred)
+    test_validate(
+        "SELECT (SELECT MAX(amount) FROM ds.orders WHERE user_id = u.id AND id > 0 AND nme IS NULL) FROM ds.users AS u",
+        &["1:79 Unrecognized name: nme"],
+    );
+}
+
+#[test]
+fn test_validate_struct_and_array() {
+    test_validate(
+        "\
+SELECT
+  address.city,
+  address.country,
+  u.address.zip,
+  name.first,
+  tags[OFFSET(0)],
+  name[OFFSET(0)],
+  payload.a.b,
+  payload['a'],
+  tags.x
+FROM ds.users AS u",
+        &[
+            "3:11 Field name country does not exist in STRUCT<city STRING, zip STRING>",
+            "5:8 Cannot access field first on a value with type STRING",
+            "7:3 Element access using [] is not supported on values of type STRING",
+            "10:8 Cannot access field x on a value with type ARRAY<STRING>",
+        ],
+    );
+    // UNNEST
+    test_validate(
+        "\
+SELECT e.kind, e.knd, t.x, kind, ts, o, o.x
+FROM ds.users, UNNEST(events) AS e, UNNEST(tags) AS t WITH OFFSET AS o, UNNEST(events)",
+        &[
+            "1:18 Field name knd does not exist in STRUCT<ts TIMESTAMP, kind STRING>",
+            "1:25 Cannot access field x on a value with type STRING",
+            "1:43 Cannot access field x on a value with type INT64",
+        ],
+    );
+    test_validate(
+        "SELECT e.ts, e.kind.x FROM ds.users AS u, u.events AS e",
+        &["1:21 Cannot access field x on a value with type STRING"],
+    );
+    // UNNEST of unknown arrays may have any column
+    test_validate("SELECT a FROM UNNEST([STRUCT(1 AS a)])", &[]);
+    // variables
+    test_validate(
+        "DECLARE s STRUCT<a INT64>; SELECT s.a, s.b;",
+        &["1:42 Field name b does not exist in STRUCT<a INT64>"],
+    );
+}
+
+#[test]
+fn test_validate_script() {
+    test_validate(
+        "\
+CREATE TEMP TABLE tmp AS SELECT id, name AS user_name FROM ds.users;
+SELECT name FROM tmp;
+INSERT ds.orders (id, amont) VALUES (1, 2);
+INSERT INTO tmp (id) SELECT 1;
+UPDATE ds.orders SET amont = 1 WHERE id = 1;
+MERGE ds.orders AS o USING tmp ON o.id = tmp.id
+WHEN NOT MATCHED THEN INSERT (id, amont) VALUES (1, 2)
+WHEN MATCHED THEN UPDATE SET amont = 1;
+DELETE ds.orders WHERE amont IS NULL;",
+        &[
+            "2:8 Unrecognized name: name",
+            "3:23 Column amont is not present in table ds.orders",
+            "5:22 Unrecognized name: amont",
+            "7:35 Column amont is not present in table o",
+            "8:30 Unrecognized name: amont",
+            "9:24 Unrecognized name: amont",
+        ],
+    );
+}
+
+#[test]
+fn test_validate_functions() {
+    test_validate(
+        "SELECT SUBSTR(nme), COUNT(DISTINCT id) OVER () FROM ds.users",
+        &[
+            "1:8 Number of arguments does not match for function SUBSTR. Supported signature: SUBSTR(STRING|BYTES, INT64, [INT64])",
+            "1:15 Unrecognized name: nme",
+        ],
+    );
+}
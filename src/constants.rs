@@ -96,3 +96,89 @@ pub const KEYWORDS: [&str; 95] = [
     "WITHIN",
 ];
 
+// keywords which can appear at the beginning of a statement
+pub const STATEMENT_KEYWORDS: [&str; 31] = [
+    "WITH", "SELECT", "(", "INSERT", "DELETE", "TRUNCATE", "UPDATE", "MERGE", "CREATE", "ALTER",
+    "DROP", "GRANT", "REVOKE", "DECLARE", "SET", "EXECUTE", "IF", "BEGIN", "LOOP", "WHILE",
+    "BREAK", "LEAVE", "CONTINUE", "ITERATE", "RETURN", "COMMIT", "ROLLBACK", "RAISE", "CALL",
+    "ASSERT", "EXPORT",
+];
+
+// words which are treated as keywords by the parser but are not reserved
+pub const NON_RESERVED_KEYWORDS: [&str; 75] = [
+    "ADD",
+    "ALTER",
+    "ASSERT",
+    "BEGIN",
+    "BIGDECIMAL",
+    "BIGNUMERIC",
+    "BREAK",
+    "CALL",
+    "CASCADE",
+    "CLONE",
+    "CLUSTER",
+    "COLUMN",
+    "COMMIT",
+    "CONTINUE",
+    "DATA",
+    "DATE",
+    "DATETIME",
+    "DECIMAL",
+    "DECLARE",
+    "DELETE",
+    "DETERMINISTIC",
+    "DROP",
+    "ELSEIF",
+    "EXCEPTION",
+    "EXECUTE",
+    "EXPORT",
+    "EXTERNAL",
+    "FORMAT",
+    "FUNCTION",
+    "GRANT",
+    "IMMEDIATE",
+    "INCLUDE",
+    "INOUT",
+    "INSERT",
+    "ITERATE",
+    "LANGUAGE",
+    "LEAVE",
+    "LOOP",
+    "MATCHED",
+    "MATERIALIZED",
+    "NUMERIC",
+    "OPTIONS",
+    "OUT",
+    "PERCENT",
+    "PIVOT",
+    "PROCEDURE",
+    "QUALIFY",
+    "RAISE",
+    "RENAME",
+    "REPLACE",
+    "RESTRICT",
+    "RETURN",
+    "RETURNS",
+    "REVOKE",
+    "ROLLBACK",
+    "ROW",
+    "SCHEMA",
+    "SNAPSHOT",
+    "SOURCE",
+    "SYSTEM",
+    "SYSTEM_TIME",
+    "TABLE",
+    "TARGET",
+    "TEMP",
+    "TEMPORARY",
+    "TIME",
+    "TIMESTAMP",
+    "TRANSACTION",
+    "TRUNCATE",
+    "TYPE",
+    "UNPIVOT",
+    "UPDATE",
+    "VALUES",
+    "VIEW",
+    "WHILE",
+];
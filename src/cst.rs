@@ -1,18 +1,31 @@
+pub mod schema;
 #[cfg(test)]
 mod tests;
+mod visitor;
 
-use crate::token::Token;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+pub use visitor::{walk, walk_mut, Visitor, VisitorMut};
+
+use schema::NodeSchema;
+
+use crate::token::{Span, Token};
+#[cfg(feature = "serde")]
+use serde::{
+    de::{MapAccess, Visitor as DeVisitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 use std::fmt;
 
-#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ContentType {
     Node(Node),
     NodeVec(Vec<Node>),
 }
 
-#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[allow(clippy::upper_case_acronyms)]
 pub enum NodeType {
     AddColumnClause, // ADD COLUMN x INT64 OPTIONS()
     AlterSchemaStatement,
@@ -48,6 +61,7 @@ pub enum NodeType {
     DropStatement,
     ElseIfClause, // ELSEIF true SELECT;
     EOF,
+    Error,            // tokens skipped by `parse_code_with_recovery()`
     ExecuteStatement, // EXECUTE IMMEDIATE 'SELECT 1;'
     ExportStatement,
     ExtractArgument,         // DAY FROM expr
@@ -113,11 +127,166 @@ pub enum NodeType {
     XXXByExprs,                 // ORDER BY expr
 }
 
-#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+impl NodeType {
+    // `None` if `Unknown`
+    pub fn schema(&self) -> Option<&'static NodeSchema> {
+        NodeSchema::find(&format!("{:?}", self))
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "RawNode"))]
 pub struct Node {
     pub token: Option<Token>,
     pub node_type: NodeType,
-    children: HashMap<String, ContentType>,
+    children: Children,
+    // the number of children that precede `token`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    token_index: usize,
+}
+
+// `Node` without `token_index` (it is restored from the positions of tokens)
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawNode {
+    token: Option<Token>,
+    node_type: NodeType,
+    children: Children,
+}
+
+#[cfg(feature = "serde")]
+impl From<RawNode> for Node {
+    fn from(raw: RawNode) -> Node {
+        let mut node = Node {
+            token: raw.token,
+            node_type: raw.node_type,
+            children: Children::default(),
+            token_index: 0,
+        };
+        for (key, value) in raw.children.entries {
+            node.push_content(key, value);
+        }
+        node
+    }
+}
+
+// children of `Node`, sorted by the position of their first token when they are pushed
+// (serialized as a map whose keys are in source order)
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Children {
+    entries: Vec<(String, ContentType)>,
+}
+
+impl Children {
+    pub fn get(&self, key: &str) -> Option<&ContentType> {
+        self.entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut ContentType> {
+        self.entries
+            .iter_mut()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+    // the old value is replaced in place if the key already exists
+    // (so that edited children keep their position)
+    pub fn insert(&mut self, key: String, value: ContentType) -> Option<ContentType> {
+        self.insert_at(key, value).1
+    }
+    // returns the index of the value
+    fn insert_at(&mut self, key: String, value: ContentType) -> (usize, Option<ContentType>) {
+        if let Some(idx) = self.entries.iter().position(|(k, _)| *k == key) {
+            let old = std::mem::replace(&mut self.entries[idx].1, value);
+            return (idx, Some(old));
+        }
+        let start = value.first_start();
+        // NOTE children without tokens are placed at the end
+        let idx = match start {
+            Some(start) => self
+                .entries
+                .iter()
+                .position(|(_, v)| v.first_start().is_none_or(|s| start < s))
+                .unwrap_or(self.entries.len()),
+            None => self.entries.len(),
+        };
+        self.entries.insert(idx, (key, value));
+        (idx, None)
+    }
+    pub fn remove(&mut self, key: &str) -> Option<ContentType> {
+        let idx = self.entries.iter().position(|(k, _)| k == key)?;
+        Some(self.entries.remove(idx).1)
+    }
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &ContentType)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+    pub fn values(&self) -> impl Iterator<Item = &ContentType> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut ContentType> {
+        self.entries.iter_mut().map(|(_, v)| v)
+    }
+}
+
+impl<'a> IntoIterator for &'a Children {
+    type Item = (&'a String, &'a ContentType);
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Children {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+        for (k, v) in &self.entries {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Children {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ChildrenVisitor;
+        impl<'de> DeVisitor<'de> for ChildrenVisitor {
+            type Value = Children;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a map of children")
+            }
+            fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Children, A::Error> {
+                let mut children = Children::default();
+                while let Some((k, v)) = access.next_entry()? {
+                    children.insert(k, v);
+                }
+                Ok(children)
+            }
+        }
+        deserializer.deserialize_map(ChildrenVisitor)
+    }
+}
+
+impl ContentType {
+    // the start of the first token (children are sorted, so it is cheap)
+    fn first_start(&self) -> Option<usize> {
+        match self {
+            ContentType::Node(n) => n.first_start(),
+            ContentType::NodeVec(ns) => ns.iter().filter_map(|n| n.first_start()).min(),
+        }
+    }
 }
 
 impl Node {
@@ -125,16 +294,94 @@ impl Node {
         Node {
             token: Some(token),
             node_type,
-            children: HashMap::new(),
+            children: Children::default(),
+            token_index: 0,
         }
     }
     pub fn empty(node_type: NodeType) -> Node {
         Node {
             token: None,
             node_type,
-            children: HashMap::new(),
+            children: Children::default(),
+            token_index: 0,
+        }
+    }
+    pub fn children(&self) -> &Children {
+        &self.children
+    }
+    // every child node (elements of `NodeVec` are flattened) in source order
+    pub fn children_in_source_order(&self) -> impl Iterator<Item = (&str, &Node)> {
+        let mut children = Vec::new();
+        for (key, child) in &self.children {
+            match child {
+                ContentType::Node(n) => children.push((key.as_str(), n)),
+                ContentType::NodeVec(ns) => children.extend(ns.iter().map(|n| (key.as_str(), n))),
+            }
+        }
+        children.into_iter()
+    }
+    fn first_start(&self) -> Option<usize> {
+        let token_start = self.token.as_ref().map(|t| t.start);
+        let children_start = self.children.values().next().and_then(|c| c.first_start());
+        match (token_start, children_start) {
+            (Some(t), Some(c)) => Some(t.min(c)),
+            (t, c) => t.or(c),
         }
     }
+    pub fn get_node(&self, key: &str) -> Option<&Node> {
+        match self.children.get(key) {
+            Some(ContentType::Node(n)) => Some(n),
+            _ => None,
+        }
+    }
+    pub fn get_node_vec(&self, key: &str) -> Option<&Vec<Node>> {
+        match self.children.get(key) {
+            Some(ContentType::NodeVec(ns)) => Some(ns),
+            _ => None,
+        }
+    }
+    // every token in the subtree (including comments) in source order
+    // NOTE the order comes from the structure of the tree (not from `Token::start`),
+    // so that edited trees are also handled
+    pub fn tokens(&self) -> Vec<&Token> {
+        let mut tokens = Vec::new();
+        self.collect_tokens(&mut tokens);
+        tokens
+    }
+    fn collect_tokens<'a>(&'a self, tokens: &mut Vec<&'a Token>) {
+        let (before, after) = self.children.entries.split_at(self.token_index);
+        let collect = |tokens: &mut Vec<&'a Token>, entries: &'a [(String, ContentType)]| {
+            for (_, child) in entries {
+                match child {
+                    ContentType::Node(n) => n.collect_tokens(tokens),
+                    ContentType::NodeVec(ns) => {
+                        for n in ns {
+                            n.collect_tokens(tokens);
+                        }
+                    }
+                }
+            }
+        };
+        collect(tokens, before);
+        tokens.extend(&self.token);
+        collect(tokens, after);
+    }
+    // the range from the first token to the last token in the subtree
+    pub fn span(&self) -> Option<Span> {
+        let tokens = self.tokens();
+        let first = tokens.first()?;
+        let last = tokens.last()?;
+        Some(Span {
+            line: first.line,
+            column: first.column,
+            end_line: last.end_line,
+            end_column: last.end_column,
+            start: first.start,
+            end: last.end,
+            start_utf16: first.start_utf16,
+            end_utf16: last.end_utf16,
+        })
+    }
     fn format(&self, indent: usize, is_array: bool) -> String {
         let mut res = Vec::new();
         // self & node_type
@@ -142,12 +389,11 @@ impl Node {
             Some(t) => t.literal,
             None => "None".to_string(),
         };
-        let self_;
-        if is_array {
-            self_ = format!("{}- self: {}", " ".repeat((indent - 1) * 2), literal);
+        let self_ = if is_array {
+            format!("{}- self: {}", " ".repeat((indent - 1) * 2), literal)
         } else {
-            self_ = format!("{}self: {}", " ".repeat(indent * 2), literal);
-        }
+            format!("{}self: {}", " ".repeat(indent * 2), literal)
+        };
         let type_ = format!("{:?}", self.node_type);
         res.push(format!("{} ({})", self_, type_));
         // children
@@ -170,18 +416,32 @@ impl Node {
         }
         res.join("\n")
     }
+    // the old child is replaced in place if the key already exists
     pub fn push_node(&mut self, key: &str, node: Node) {
-        self.children
-            .insert(key.to_string(), ContentType::Node(node));
+        self.push_content(key.to_string(), ContentType::Node(node));
     }
     pub fn push_node_vec(&mut self, key: &str, nodes: Vec<Node>) {
-        self.children
-            .insert(key.to_string(), ContentType::NodeVec(nodes));
+        self.push_content(key.to_string(), ContentType::NodeVec(nodes));
+    }
+    fn push_content(&mut self, key: String, value: ContentType) {
+        let start = value.first_start();
+        let (idx, old) = self.children.insert_at(key, value);
+        if old.is_some() {
+            return;
+        }
+        // new children are placed before `token` only if they appear before it in the code
+        let before_token = match (start, &self.token) {
+            (Some(start), Some(t)) => start < t.start,
+            _ => false,
+        };
+        if idx < self.token_index || (idx == self.token_index && before_token) {
+            self.token_index += 1;
+        }
     }
 }
 
 impl fmt::Display for Node {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}\n", self.format(0, false))
+        writeln!(f, "{}", self.format(0, false))
     }
 }
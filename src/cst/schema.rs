@@ -0,0 +1,1275 @@
+// machine-readable schema of CST
+// the TypeScript definitions of `parse()` are generated from this file by build.rs,
+// so this file should not depend on anything else in the crate
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum ChildKind {
+    Node,
+    NodeVec,
+    Never, // declared as `undefined` to cancel the key inherited from `extends`
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct ChildSchema {
+    pub key: &'static str,
+    pub kind: ChildKind,
+    pub optional: bool,
+    pub ts_type: Option<&'static str>, // `BaseNode` if None
+    pub doc: &'static str,
+}
+
+impl ChildSchema {
+    const fn ts_type(self, ts_type: &'static str) -> ChildSchema {
+        ChildSchema {
+            ts_type: Some(ts_type),
+            ..self
+        }
+    }
+    const fn doc(self, doc: &'static str) -> ChildSchema {
+        ChildSchema { doc, ..self }
+    }
+}
+
+const fn child(key: &'static str, kind: ChildKind, optional: bool) -> ChildSchema {
+    ChildSchema {
+        key,
+        kind,
+        optional,
+        ts_type: None,
+        doc: "",
+    }
+}
+const fn node(key: &'static str) -> ChildSchema {
+    child(key, ChildKind::Node, false)
+}
+const fn opt_node(key: &'static str) -> ChildSchema {
+    child(key, ChildKind::Node, true)
+}
+const fn node_vec(key: &'static str) -> ChildSchema {
+    child(key, ChildKind::NodeVec, false)
+}
+const fn opt_node_vec(key: &'static str) -> ChildSchema {
+    child(key, ChildKind::NodeVec, true)
+}
+const fn never(key: &'static str) -> ChildSchema {
+    child(key, ChildKind::Never, true)
+}
+
+#[derive(PartialEq, Debug)]
+pub struct NodeSchema {
+    pub name: &'static str, // the name of `NodeType` (or abstract type)
+    pub doc: &'static str,
+    pub extends: &'static [&'static str],
+    pub token: bool, // true if the token is never null
+    pub children: &'static [ChildSchema],
+}
+
+impl NodeSchema {
+    // `name` of `NodeType` or abstract type
+    pub fn find(name: &str) -> Option<&'static NodeSchema> {
+        ABSTRACT_NODES
+            .iter()
+            .chain(NODES.iter())
+            .find(|s| s.name == name)
+    }
+    // the schema of the key, including the ones inherited from `extends`
+    // (the key declared by the node itself takes precedence)
+    pub fn child(&'static self, key: &str) -> Option<&'static ChildSchema> {
+        self.children.iter().find(|c| c.key == key).or_else(|| {
+            self.extends
+                .iter()
+                .filter_map(|name| NodeSchema::find(name))
+                .find_map(|s| s.child(key))
+        })
+    }
+    // every key which can appear in the node
+    pub fn keys(&'static self) -> Vec<&'static str> {
+        let mut keys = Vec::new();
+        self.collect_keys(&mut keys);
+        keys.retain(|key| self.child(key).map(|c| c.kind) != Some(ChildKind::Never));
+        keys
+    }
+    fn collect_keys(&'static self, keys: &mut Vec<&'static str>) {
+        for name in self.extends {
+            if let Some(s) = NodeSchema::find(name) {
+                s.collect_keys(keys);
+            }
+        }
+        for c in self.children {
+            if !keys.contains(&c.key) {
+                keys.push(c.key);
+            }
+        }
+    }
+}
+
+// sub types of BaseNode (abstract)
+pub const ABSTRACT_NODES: &[NodeSchema] = &[
+    NodeSchema {
+        name: "BaseNode",
+        doc: "",
+        extends: &[],
+        token: false,
+        children: &[
+            opt_node_vec("leading_comments").ts_type("Comment"),
+            opt_node_vec("trailing_comments").ts_type("Comment"),
+        ],
+    },
+    NodeSchema {
+        name: "CallingFunctionGeneral",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[
+            node("func").ts_type("Identifier | DotOperator"),
+            opt_node("distinct"),
+            opt_node_vec("args").ts_type("Expr"),
+            opt_node_vec("ignore_nulls"),
+            opt_node("orderby"),
+            opt_node("limit"),
+            node("rparen"),
+            opt_node("over"),
+        ],
+    },
+    NodeSchema {
+        name: "Expr",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            opt_node("as"),
+            opt_node("alias"),
+            opt_node("comma"),
+            opt_node("order"),
+            opt_node_vec("null_order"),
+            opt_node("row_value_alias").doc("only in UNPIVOT operator"),
+        ],
+    },
+    NodeSchema {
+        name: "FromItemExpr",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[opt_node("pivot"), opt_node("unpivot")],
+    },
+    NodeSchema {
+        name: "IdentifierGeneral",
+        doc: "",
+        extends: &["FromItemExpr"],
+        token: false,
+        children: &[
+            opt_node("tablesample")
+                .doc("TABLESAMPLE SYSTEM can only be applied directly to base tables"),
+            opt_node("for_system_time_as_of"),
+        ],
+    },
+    NodeSchema {
+        name: "XXXStatement",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[opt_node("semicolon").ts_type("Symbol_")],
+    },
+];
+
+// sub types of BaseNode (concrete)
+// every `NodeType` except `Unknown` should be declared here
+pub const NODES: &[NodeSchema] = &[
+    NodeSchema {
+        name: "AddColumnClause",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            node("column"),
+            opt_node_vec("if_not_exists"),
+            node("type_declaration"),
+            opt_node("comma"),
+        ],
+    },
+    NodeSchema {
+        name: "AlterColumnStatement",
+        doc: "NOTE this is not XXXStatement!",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            node("what"),
+            opt_node_vec("if_exists"),
+            node("ident"),
+            opt_node("set").doc("SET"),
+            opt_node("options"),
+            opt_node_vec("drop_not_null").doc("DROP"),
+        ],
+    },
+    NodeSchema {
+        name: "AlterSchemaStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node("what"),
+            opt_node_vec("if_exists"),
+            node("ident"),
+            node("set"),
+            node("options"),
+        ],
+    },
+    NodeSchema {
+        name: "AlterTableStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node("what"),
+            opt_node_vec("if_exists"),
+            node("ident"),
+            opt_node("set").doc("SET"),
+            opt_node("options"),
+            opt_node_vec("add_columns").doc("ADD COLUMN"),
+            opt_node("rename").doc("RENAME TO"),
+            opt_node("to"),
+            opt_node_vec("drop_columns").doc("DROP COLUMN"),
+            opt_node("alter_column_stmt").doc("ALTER COLUMN statement"),
+        ],
+    },
+    NodeSchema {
+        name: "AlterViewStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node("materialized"),
+            node("what"),
+            opt_node_vec("if_exists"),
+            node("ident"),
+            node("set"),
+            node("options"),
+        ],
+    },
+    NodeSchema {
+        name: "ArrayAccessing",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[
+            never("not"),
+            node("left"),
+            node("right"),
+            node("rparen"),
+        ],
+    },
+    NodeSchema {
+        name: "ArrayLiteral",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[
+            opt_node("type"),
+            node_vec("exprs"),
+            node("rparen"),
+        ],
+    },
+    NodeSchema {
+        name: "AssertStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node("expr"),
+            opt_node("as"),
+            opt_node("description"),
+        ],
+    },
+    NodeSchema {
+        name: "Asterisk",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[
+            opt_node("except"),
+            opt_node("replace"),
+            never("order"),
+            never("null_order"),
+        ],
+    },
+    NodeSchema {
+        name: "BinaryOperator",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[
+            opt_node("not"),
+            node("left"),
+            node("right"),
+        ],
+    },
+    NodeSchema {
+        name: "BeginStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node_vec("stmts"),
+            opt_node_vec("exception_when_error"),
+            opt_node("then"),
+            node("end"),
+        ],
+    },
+    NodeSchema {
+        name: "BetweenOperator",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[
+            node("left"),
+            opt_node("not"),
+            node("right_min"),
+            node("right_max"),
+            node("and"),
+        ],
+    },
+    NodeSchema {
+        name: "BooleanLiteral",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[],
+    },
+    NodeSchema {
+        name: "CallingArrayAccessingFunction",
+        doc: "",
+        extends: &["CallingFunctionGeneral"],
+        token: false,
+        children: &[
+            never("distinct"),
+            never("ignore_nulls"),
+            never("orderby"),
+            never("limit"),
+            never("over"),
+            never("as"),
+            never("alias"),
+            never("comma"),
+            never("order"),
+            never("null_order"),
+        ],
+    },
+    NodeSchema {
+        name: "CallingFunction",
+        doc: "",
+        extends: &["CallingFunctionGeneral"],
+        token: false,
+        children: &[],
+    },
+    NodeSchema {
+        name: "CallingTableFunction",
+        doc: "",
+        extends: &["FromItemExpr", "CallingFunctionGeneral"],
+        token: false,
+        children: &[
+            never("distinct"),
+            never("ignore_nulls"),
+            never("orderby"),
+            never("limit"),
+            never("over"),
+            never("comma"),
+            never("order"),
+            never("null_order"),
+        ],
+    },
+    NodeSchema {
+        name: "CallingUnnest",
+        doc: "",
+        extends: &["FromItemExpr", "CallingFunctionGeneral"],
+        token: false,
+        children: &[
+            opt_node_vec("with_offset"),
+            opt_node("offset_as"),
+            opt_node("offset_alias"),
+            never("distinct"),
+            never("ignore_nulls"),
+            never("orderby"),
+            never("limit"),
+            never("over"),
+            never("order"),
+            never("null_order"),
+            never("comma"),
+        ],
+    },
+    NodeSchema {
+        name: "CallStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node("procedure"),
+        ],
+    },
+    NodeSchema {
+        name: "CaseArm",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            opt_node("expr"),
+            opt_node("then"),
+            node("result"),
+        ],
+    },
+    NodeSchema {
+        name: "CaseExpr",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[
+            opt_node("expr"),
+            node_vec("arms"),
+            node("end"),
+        ],
+    },
+    NodeSchema {
+        name: "CastArgument",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node("cast_from"),
+            node("cast_to"),
+            opt_node("format"),
+        ],
+    },
+    NodeSchema {
+        name: "Comment",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            never("leading_comments"),
+            never("trailing_comments"),
+        ],
+    },
+    NodeSchema {
+        name: "CreateFunctionStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node_vec("or_replace"),
+            opt_node("temp"),
+            opt_node("table"),
+            node("what"),
+            opt_node_vec("if_not_exists"),
+            node("ident"),
+            node("group"),
+            opt_node("returns"),
+            opt_node_vec("determinism"),
+            opt_node("language"),
+            opt_node("options"),
+            node("as"),
+        ],
+    },
+    NodeSchema {
+        name: "CreateProcedureStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node_vec("or_replace"),
+            node("what"),
+            opt_node_vec("if_not_exists"),
+            node("ident"),
+            node("group"),
+            opt_node("options"),
+            node("stmt"),
+        ],
+    },
+    NodeSchema {
+        name: "CreateSchemaStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node("what"),
+            opt_node_vec("if_not_exists"),
+            node("ident"),
+            opt_node("options"),
+        ],
+    },
+    NodeSchema {
+        name: "CreateTableStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node_vec("or_replace"),
+            opt_node("temp"),
+            opt_node("external"),
+            opt_node("snapshot"),
+            node("what"),
+            opt_node_vec("if_not_exists"),
+            node("ident"),
+            opt_node("column_schema_group"),
+            opt_node("clone"),
+            opt_node("partitionby"),
+            opt_node("clusterby"),
+            opt_node("with_partition_columns"),
+            opt_node("options"),
+            opt_node("as"),
+        ],
+    },
+    NodeSchema {
+        name: "CreateViewStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node_vec("or_replace"),
+            opt_node("materialized"),
+            node("what"),
+            opt_node_vec("if_not_exists"),
+            node("ident"),
+            opt_node("column_name_list"),
+            opt_node("partitionby"),
+            opt_node("clusterby"),
+            opt_node("options"),
+            node("as"),
+        ],
+    },
+    NodeSchema {
+        name: "DeclareStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node_vec("idents"),
+            opt_node("variable_type"),
+            opt_node("default"),
+        ],
+    },
+    NodeSchema {
+        name: "DeleteStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node("from"),
+            node("table_name"),
+            opt_node("where"),
+        ],
+    },
+    NodeSchema {
+        name: "DotOperator",
+        doc: "",
+        extends: &["IdentifierGeneral"],
+        token: false,
+        children: &[
+            node("left").ts_type("Identifier | DotOperator"),
+            node("right").ts_type("Identifier | DotOperator"),
+        ],
+    },
+    NodeSchema {
+        name: "DropColumnClause",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            node("column"),
+            opt_node_vec("if_exists"),
+            node("ident"),
+            opt_node("comma"),
+        ],
+    },
+    NodeSchema {
+        name: "DropStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node("external"),
+            opt_node("materialized"),
+            node("what"),
+            opt_node_vec("if_exists"),
+            node("ident"),
+            opt_node("cascade_or_restrict"),
+        ],
+    },
+    NodeSchema {
+        name: "ElseIfClause",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node("condition"),
+            node("then"),
+        ],
+    },
+    NodeSchema {
+        name: "EOF",
+        doc: "`token.literal` is always empty. `token.line` and `token.column` point at the end of the code (`token` was `null` before)",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            never("trailing_comments"),
+        ],
+    },
+    NodeSchema {
+        name: "Error",
+        doc: "tokens skipped by `parse_with_recovery()`",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node_vec("skipped_tokens"),
+        ],
+    },
+    NodeSchema {
+        name: "ExecuteStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node("immediate"),
+            node("sql_expr"),
+            opt_node("into"),
+            opt_node("using"),
+        ],
+    },
+    NodeSchema {
+        name: "ExportStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node("data"),
+            node("options"),
+            node("as"),
+        ],
+    },
+    NodeSchema {
+        name: "ExtractArgument",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node("extract_datepart"),
+            node("extract_from"),
+            opt_node_vec("at_time_zone"),
+            opt_node("time_zone"),
+        ],
+    },
+    NodeSchema {
+        name: "ForSystemTimeAsOfClause",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node_vec("system_time_as_of"),
+            node("expr"),
+        ],
+    },
+    NodeSchema {
+        name: "GrantStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node_vec("roles"),
+            node("on"),
+            node("resource_type"),
+            node("ident"),
+            node("to"),
+        ],
+    },
+    NodeSchema {
+        name: "GroupedExpr",
+        doc: "",
+        extends: &["FromItemExpr"],
+        token: false,
+        children: &[
+            node("expr"),
+            node("rparen"),
+        ],
+    },
+    NodeSchema {
+        name: "GroupedExprs",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            opt_node_vec("exprs"),
+            node("rparen"),
+            opt_node("as").doc("only in UNPIVOT operator"),
+            opt_node("row_value_alias"),
+            opt_node("comma").doc("only in INSERT statement"),
+        ],
+    },
+    NodeSchema {
+        name: "GroupedStatement",
+        doc: "",
+        extends: &["FromItemExpr", "XXXStatement"],
+        token: false,
+        children: &[
+            node("stmt"),
+            node("rparen"),
+        ],
+    },
+    NodeSchema {
+        name: "GroupedTypeDeclarations",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            opt_node_vec("declarations"),
+            node("rparen"),
+        ],
+    },
+    NodeSchema {
+        name: "GroupedType",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            node("type"),
+            node("rparen"),
+        ],
+    },
+    NodeSchema {
+        name: "Identifier",
+        doc: "",
+        extends: &["IdentifierGeneral"],
+        token: false,
+        children: &[],
+    },
+    NodeSchema {
+        name: "IfStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node("condition"),
+            node("then"),
+            opt_node_vec("elseifs"),
+            opt_node("else"),
+            node_vec("end_if"),
+        ],
+    },
+    NodeSchema {
+        name: "InOperator",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[
+            opt_node("not"),
+            node("left"),
+            node("right"),
+        ],
+    },
+    NodeSchema {
+        name: "InsertStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node("into"),
+            opt_node("target_name"),
+            opt_node("columns"),
+            node("input"),
+        ],
+    },
+    NodeSchema {
+        name: "IntervalLiteral",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[
+            node("date_part"),
+            node("right"),
+            never("order"),
+            never("null_order"),
+        ],
+    },
+    NodeSchema {
+        name: "JoinOperator",
+        doc: "",
+        extends: &["FromItemExpr"],
+        token: false,
+        children: &[
+            opt_node("join_type"),
+            opt_node("outer"),
+            node("left"),
+            node("right"),
+            opt_node("on"),
+            opt_node("using"),
+            never("order"),
+            never("null_order"),
+            never("comma"),
+        ],
+    },
+    NodeSchema {
+        name: "Keyword",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[],
+    },
+    NodeSchema {
+        name: "KeywordWithExpr",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            node("expr"),
+        ],
+    },
+    NodeSchema {
+        name: "KeywordWithExprs",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            opt_node_vec("exprs"),
+            opt_node_vec("users").doc("only in GRANT and REVOKE statement"),
+        ],
+    },
+    NodeSchema {
+        name: "KeywordWithGroupedXXX",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            node("group"),
+        ],
+    },
+    NodeSchema {
+        name: "KeywordWithStatement",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            node("stmt"),
+        ],
+    },
+    NodeSchema {
+        name: "KeywordWithStatements",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            opt_node_vec("stmts"),
+        ],
+    },
+    NodeSchema {
+        name: "KeywordWithType",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            node("type"),
+        ],
+    },
+    NodeSchema {
+        name: "LanguageSpecifier",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            node("language"),
+        ],
+    },
+    NodeSchema {
+        name: "LimitClause",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            node("expr"),
+            opt_node("offset"),
+        ],
+    },
+    NodeSchema {
+        name: "LoopStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node_vec("stmts"),
+            node_vec("end_loop"),
+        ],
+    },
+    NodeSchema {
+        name: "MergeStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node("into"),
+            node("table_name"),
+            node("using"),
+            node("on"),
+            node_vec("whens"),
+        ],
+    },
+    NodeSchema {
+        name: "NullLiteral",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[],
+    },
+    NodeSchema {
+        name: "NumericLiteral",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[],
+    },
+    NodeSchema {
+        name: "OverClause",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node("window"),
+        ],
+    },
+    NodeSchema {
+        name: "Parameter",
+        doc: "",
+        extends: &["IdentifierGeneral"],
+        token: false,
+        children: &[],
+    },
+    NodeSchema {
+        name: "PivotOperator",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node("config"),
+            opt_node("as"),
+            opt_node("alias"),
+        ],
+    },
+    NodeSchema {
+        name: "PivotConfig",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node_vec("exprs"),
+            node("for"),
+            node("in"),
+            node("rparen"),
+        ],
+    },
+    NodeSchema {
+        name: "SelectStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: true,
+        children: &[
+            opt_node("with"),
+            opt_node_vec("as_struct_or_value"),
+            opt_node("distinct_or_all"),
+            node_vec("exprs"),
+            opt_node("from"),
+            opt_node("where"),
+            opt_node("groupby"),
+            opt_node("having"),
+            opt_node("qualify"),
+            opt_node("window"),
+            opt_node("orderby"),
+            opt_node("limit"),
+        ],
+    },
+    NodeSchema {
+        name: "RaiseStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node("using"),
+        ],
+    },
+    NodeSchema {
+        name: "RevokeStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node_vec("roles"),
+            node("on"),
+            node("resource_type"),
+            node("ident"),
+            node("from"),
+        ],
+    },
+    NodeSchema {
+        name: "SetOperator",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node("distinct_or_all"),
+            node("left").ts_type("SetOperator | SelectStatement"),
+            node("right").ts_type("SetOperator | SelectStatement"),
+        ],
+    },
+    NodeSchema {
+        name: "SetStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node("expr"),
+        ],
+    },
+    NodeSchema {
+        name: "SingleTokenStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[],
+    },
+    NodeSchema {
+        name: "StringLiteral",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[],
+    },
+    NodeSchema {
+        name: "StructLiteral",
+        doc: "",
+        extends: &["Expr"],
+        token: false,
+        children: &[
+            opt_node("type"),
+            node_vec("exprs"),
+            node("rparen"),
+        ],
+    },
+    NodeSchema {
+        name: "Symbol",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[],
+    },
+    NodeSchema {
+        name: "TableSampleClause",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node("system"),
+            node("group"),
+        ],
+    },
+    NodeSchema {
+        name: "TableSampleRatio",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node("expr"),
+            node("percent"),
+            node("rparen"),
+        ],
+    },
+    NodeSchema {
+        name: "TransactionStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node("transaction"),
+        ],
+    },
+    NodeSchema {
+        name: "TruncateStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node("table"),
+            node("table_name"),
+        ],
+    },
+    NodeSchema {
+        name: "Type",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            opt_node("type").doc("ANY TYPE"),
+            opt_node("type_declaration"),
+            opt_node("parameter"),
+            opt_node_vec("not_null"),
+            opt_node("options"),
+        ],
+    },
+    NodeSchema {
+        name: "TypeDeclaration",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            opt_node("in_out"),
+            node("type"),
+            opt_node("comma"),
+        ],
+    },
+    NodeSchema {
+        name: "UnaryOperator",
+        doc: "",
+        extends: &["Expr"],
+        token: true,
+        children: &[
+            node("right"),
+        ],
+    },
+    NodeSchema {
+        name: "UnpivotConfig",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node("expr"),
+            node("for"),
+            node("in"),
+            node("rparen"),
+        ],
+    },
+    NodeSchema {
+        name: "UnpivotOperator",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            opt_node_vec("include_or_exclude_nulls"),
+            node("config"),
+            opt_node("as"),
+            opt_node("alias"),
+        ],
+    },
+    NodeSchema {
+        name: "UpdateStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            opt_node("table_name"),
+            node("set"),
+            opt_node("from"),
+            opt_node("where"),
+        ],
+    },
+    NodeSchema {
+        name: "WhenClause",
+        doc: "",
+        extends: &["BaseNode"],
+        token: false,
+        children: &[
+            opt_node("not"),
+            node("matched"),
+            opt_node_vec("by_target_or_source"),
+            opt_node("and"),
+            node("then"),
+        ],
+    },
+    NodeSchema {
+        name: "WhileStatement",
+        doc: "",
+        extends: &["XXXStatement"],
+        token: false,
+        children: &[
+            node("condition"),
+            node("do"),
+            node_vec("end_while"),
+        ],
+    },
+    NodeSchema {
+        name: "WindowClause",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node_vec("window_exprs"),
+        ],
+    },
+    NodeSchema {
+        name: "WindowExpr",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node("as"),
+            node("window"),
+            opt_node("comma"),
+        ],
+    },
+    NodeSchema {
+        name: "WindowFrameClause",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            opt_node("between"),
+            node_vec("start"),
+            opt_node("and"),
+            opt_node_vec("end"),
+        ],
+    },
+    NodeSchema {
+        name: "WindowSpecification",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            opt_node("name"),
+            opt_node("partitionby"),
+            opt_node("orderby"),
+            opt_node("frame"),
+            node("rparen"),
+        ],
+    },
+    NodeSchema {
+        name: "WithClause",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node_vec("queries"),
+        ],
+    },
+    NodeSchema {
+        name: "WithPartitionColumnsClause",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node_vec("partition_columns"),
+            opt_node("column_schema_group"),
+        ],
+    },
+    NodeSchema {
+        name: "WithQuery",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node("as"),
+            node("stmt"),
+            opt_node("comma"),
+        ],
+    },
+    NodeSchema {
+        name: "XXXByExprs",
+        doc: "",
+        extends: &["BaseNode"],
+        token: true,
+        children: &[
+            node("by"),
+            node_vec("exprs").ts_type("Expr"),
+        ],
+    },
+];
@@ -21,7 +21,7 @@ fn test_to_string() {
     let mut parent = Node::new(Token::from_str0("parent"), NodeType::Unknown);
     parent.push_node("son", son);
     parent.push_node("daughter", daughter);
-    let res = format!("{}", parent.to_string());
+    let res = parent.to_string();
 
     println!("{}", res);
     assert_eq!(
@@ -40,3 +40,189 @@ son:
         res
     );
 }
+
+#[test]
+fn test_get_children() {
+    let mut parent = Node::new(Token::from_str0("parent"), NodeType::Unknown);
+    parent.push_node("son", Node::new(Token::from_str0("son"), NodeType::Unknown));
+    parent.push_node_vec(
+        "daughters",
+        vec![Node::new(Token::from_str0("daughter"), NodeType::Unknown)],
+    );
+    assert_eq!(2, parent.children().len());
+    assert_eq!(
+        "son",
+        parent
+            .get_node("son")
+            .unwrap()
+            .token
+            .as_ref()
+            .unwrap()
+            .literal
+    );
+    assert_eq!(1, parent.get_node_vec("daughters").unwrap().len());
+    // the type of the child is not matched
+    assert_eq!(None, parent.get_node("daughters"));
+    assert_eq!(None, parent.get_node_vec("son"));
+    assert_eq!(None, parent.get_node("nobody"));
+}
+
+#[test]
+fn test_span() {
+    let stmts = crate::parser::Parser::new(
+        "\
+SELECT 1;
+SELECT
+  'a\nb', -- comment
+  2
+;"
+        .to_string(),
+    )
+    .unwrap()
+    .parse_code()
+    .unwrap();
+    let span = stmts[1].span().unwrap();
+    assert_eq!(
+        ((2, 1), (6, 2), (10, 42)),
+        (
+            (span.line, span.column),
+            (span.end_line, span.end_column),
+            (span.start, span.end)
+        )
+    );
+    // nodes without tokens
+    assert_eq!(None, Node::empty(NodeType::Unknown).span());
+}
+
+#[test]
+fn test_visitor() {
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+        functions: usize,
+    }
+    impl Visitor for Recorder {
+        fn visit_node(&mut self, node: &Node) {
+            let literal = node.token.as_ref().unwrap().literal.clone();
+            self.events.push(format!("visit {}", literal));
+        }
+        fn leave_node(&mut self, node: &Node) {
+            let literal = node.token.as_ref().unwrap().literal.clone();
+            self.events.push(format!("leave {}", literal));
+        }
+        fn visit_calling_function(&mut self, _node: &Node) {
+            self.functions += 1;
+        }
+    }
+    let stmts = crate::parse_str("SELECT f(a, 1) AS x;").unwrap();
+    let mut recorder = Recorder::default();
+    walk(&mut recorder, &stmts[0]);
+    // source order (not the order of keys)
+    assert_eq!(
+        vec![
+            "visit SELECT",
+            "visit (",
+            "visit f",
+            "leave f",
+            "visit a",
+            "visit ,",
+            "leave ,",
+            "leave a",
+            "visit 1",
+            "leave 1",
+            "visit )",
+            "leave )",
+            "visit AS",
+            "leave AS",
+            "visit x",
+            "leave x",
+            "leave (",
+            "visit ;",
+            "leave ;",
+            "leave SELECT",
+        ],
+        recorder.events
+    );
+    assert_eq!(1, recorder.functions);
+}
+
+#[test]
+fn test_visitor_mut() {
+    struct Redactor;
+    impl VisitorMut for Redactor {
+        fn visit_string_literal(&mut self, node: &mut Node) {
+            if let Some(token) = node.token.as_mut() {
+                token.literal = "'***'".to_string();
+            }
+        }
+    }
+    let mut stmts = crate::parse_str("SELECT 'secret', x FROM t WHERE y = 'password';").unwrap();
+    for stmt in &mut stmts {
+        walk_mut(&mut Redactor, stmt);
+    }
+    assert_eq!(
+        "SELECT '***', x FROM t WHERE y = '***';",
+        crate::unparse(&stmts)
+    );
+}
+
+#[test]
+fn test_children_order() {
+    let stmts = crate::parse_str("SELECT DISTINCT a, b FROM t WHERE x; -- c").unwrap();
+    // sorted by the position regardless of the order of `push_node()`
+    let keys: Vec<&String> = stmts[0].children().keys().collect();
+    assert_eq!(
+        vec!["distinct_or_all", "exprs", "from", "where", "semicolon"],
+        keys
+    );
+    let children: Vec<(&str, &str)> = stmts[0]
+        .children_in_source_order()
+        .map(|(k, n)| (k, n.token.as_ref().unwrap().literal.as_str()))
+        .collect();
+    assert_eq!(
+        vec![
+            ("distinct_or_all", "DISTINCT"),
+            ("exprs", "a"),
+            ("exprs", "b"),
+            ("from", "FROM"),
+            ("where", "WHERE"),
+            ("semicolon", ";"),
+        ],
+        children
+    );
+    // the replaced child keeps its position
+    let mut node = stmts[0].clone();
+    let semicolon = node.get_node("semicolon").unwrap().clone();
+    node.push_node("distinct_or_all", semicolon);
+    let keys: Vec<&String> = node.children().keys().collect();
+    assert_eq!(
+        vec!["distinct_or_all", "exprs", "from", "where", "semicolon"],
+        keys
+    );
+}
+
+#[test]
+fn test_schema() {
+    use schema::ChildKind;
+    assert!(NodeType::Unknown.schema().is_none());
+    let select = NodeType::SelectStatement.schema().unwrap();
+    assert_eq!(select.child("exprs").unwrap().kind, ChildKind::NodeVec);
+    assert!(select.child("from").unwrap().optional);
+    // inherited from XXXStatement and BaseNode
+    assert_eq!(select.child("semicolon").unwrap().kind, ChildKind::Node);
+    assert!(select.keys().contains(&"leading_comments"));
+    assert!(select.child("unknown_key").is_none());
+    // EOF cannot have trailing comments
+    let eof = NodeType::EOF.schema().unwrap();
+    assert_eq!(
+        eof.child("trailing_comments").unwrap().kind,
+        ChildKind::Never
+    );
+    assert!(!eof.keys().contains(&"trailing_comments"));
+    // every abstract type in `extends` should be declared
+    for node in schema::ABSTRACT_NODES.iter().chain(schema::NODES) {
+        for name in node.extends {
+            assert!(schema::NodeSchema::find(name).is_some(), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use super::{ContentType, Node, NodeType};
+
+// e.g.
+// struct TableCounter(usize);
+// impl Visitor for TableCounter {
+//     fn visit_node(&mut self, node: &Node) { ... }
+//     fn visit_select_statement(&mut self, node: &Node) { ... }
+// }
+// walk(&mut TableCounter(0), &stmt);
+macro_rules! define_visitor {
+    (
+        $visitor:ident,
+        $walk:ident,
+        $children:ident,
+        $node_ref:ty,
+        { $($node_type:ident => $hook:ident,)* }
+    ) => {
+        // hooks are called in the following order
+        // visit_node -> visit_xxx (depends on `node_type`) -> children -> leave_node
+        pub trait $visitor {
+            fn visit_node(&mut self, _node: $node_ref) {}
+            fn leave_node(&mut self, _node: $node_ref) {}
+            $(fn $hook(&mut self, _node: $node_ref) {})*
+        }
+
+        // children are visited in source order
+        pub fn $walk<V: $visitor + ?Sized>(visitor: &mut V, node: $node_ref) {
+            visitor.visit_node(node);
+            match node.node_type {
+                $(NodeType::$node_type => visitor.$hook(node),)*
+            }
+            for child in $children(node) {
+                $walk(visitor, child);
+            }
+            visitor.leave_node(node);
+        }
+    };
+}
+
+// the hooks are listed once for both `Visitor` and `VisitorMut`
+// (`walk()` does not compile if any `NodeType` is missing)
+macro_rules! define_visitors {
+    ({ $($node_type:ident => $hook:ident,)* }) => {
+        define_visitor!(Visitor, walk, children, &Node, { $($node_type => $hook,)* });
+        define_visitor!(VisitorMut, walk_mut, children_mut, &mut Node, { $($node_type => $hook,)* });
+    };
+}
+
+define_visitors!({
+    AddColumnClause => visit_add_column_clause,
+    AlterSchemaStatement => visit_alter_schema_statement,
+    AlterTableStatement => visit_alter_table_statement,
+    AlterColumnStatement => visit_alter_column_statement,
+    AlterViewStatement => visit_alter_view_statement,
+    ArrayAccessing => visit_array_accessing,
+    ArrayLiteral => visit_array_literal,
+    AssertStatement => visit_assert_statement,
+    Asterisk => visit_asterisk,
+    BinaryOperator => visit_binary_operator,
+    BeginStatement => visit_begin_statement,
+    BetweenOperator => visit_between_operator,
+    BooleanLiteral => visit_boolean_literal,
+    CallingArrayAccessingFunction => visit_calling_array_accessing_function,
+    CallingFunction => visit_calling_function,
+    CallingTableFunction => visit_calling_table_function,
+    CallingUnnest => visit_calling_unnest,
+    CallStatement => visit_call_statement,
+    CaseArm => visit_case_arm,
+    CaseExpr => visit_case_expr,
+    CastArgument => visit_cast_argument,
+    CreateFunctionStatement => visit_create_function_statement,
+    CreateProcedureStatement => visit_create_procedure_statement,
+    CreateSchemaStatement => visit_create_schema_statement,
+    CreateTableStatement => visit_create_table_statement,
+    CreateViewStatement => visit_create_view_statement,
+    Comment => visit_comment,
+    DeclareStatement => visit_declare_statement,
+    DeleteStatement => visit_delete_statement,
+    DotOperator => visit_dot_operator,
+    DropColumnClause => visit_drop_column_clause,
+    DropStatement => visit_drop_statement,
+    ElseIfClause => visit_else_if_clause,
+    EOF => visit_eof,
+    Error => visit_error,
+    ExecuteStatement => visit_execute_statement,
+    ExportStatement => visit_export_statement,
+    ExtractArgument => visit_extract_argument,
+    ForSystemTimeAsOfClause => visit_for_system_time_as_of_clause,
+    GrantStatement => visit_grant_statement,
+    GroupedExpr => visit_grouped_expr,
+    GroupedExprs => visit_grouped_exprs,
+    GroupedStatement => visit_grouped_statement,
+    GroupedType => visit_grouped_type,
+    GroupedTypeDeclarations => visit_grouped_type_declarations,
+    Keyword => visit_keyword,
+    KeywordWithExpr => visit_keyword_with_expr,
+    KeywordWithExprs => visit_keyword_with_exprs,
+    KeywordWithType => visit_keyword_with_type,
+    KeywordWithGroupedXXX => visit_keyword_with_grouped_xxx,
+    KeywordWithStatement => visit_keyword_with_statement,
+    KeywordWithStatements => visit_keyword_with_statements,
+    Identifier => visit_identifier,
+    IfStatement => visit_if_statement,
+    InsertStatement => visit_insert_statement,
+    IntervalLiteral => visit_interval_literal,
+    InOperator => visit_in_operator,
+    JoinOperator => visit_join_operator,
+    LanguageSpecifier => visit_language_specifier,
+    LimitClause => visit_limit_clause,
+    LoopStatement => visit_loop_statement,
+    MergeStatement => visit_merge_statement,
+    NumericLiteral => visit_numeric_literal,
+    NullLiteral => visit_null_literal,
+    OverClause => visit_over_clause,
+    Parameter => visit_parameter,
+    PivotConfig => visit_pivot_config,
+    PivotOperator => visit_pivot_operator,
+    RaiseStatement => visit_raise_statement,
+    RevokeStatement => visit_revoke_statement,
+    SelectStatement => visit_select_statement,
+    SetOperator => visit_set_operator,
+    SetStatement => visit_set_statement,
+    SingleTokenStatement => visit_single_token_statement,
+    StringLiteral => visit_string_literal,
+    StructLiteral => visit_struct_literal,
+    Symbol => visit_symbol,
+    TableSampleClause => visit_table_sample_clause,
+    TableSampleRatio => visit_table_sample_ratio,
+    TransactionStatement => visit_transaction_statement,
+    TruncateStatement => visit_truncate_statement,
+    Type => visit_type,
+    TypeDeclaration => visit_type_declaration,
+    UnaryOperator => visit_unary_operator,
+    Unknown => visit_unknown,
+    UnpivotOperator => visit_unpivot_operator,
+    UnpivotConfig => visit_unpivot_config,
+    UpdateStatement => visit_update_statement,
+    WhenClause => visit_when_clause,
+    WhileStatement => visit_while_statement,
+    WindowClause => visit_window_clause,
+    WindowExpr => visit_window_expr,
+    WindowFrameClause => visit_window_frame_clause,
+    WindowSpecification => visit_window_specification,
+    WithClause => visit_with_clause,
+    WithPartitionColumnsClause => visit_with_partition_columns_clause,
+    WithQuery => visit_with_query,
+    XXXByExprs => visit_xxx_by_exprs,
+});
+
+fn children(node: &Node) -> Vec<&Node> {
+    node.children_in_source_order().map(|(_, n)| n).collect()
+}
+
+fn children_mut(node: &mut Node) -> Vec<&mut Node> {
+    let mut children = Vec::new();
+    for child in node.children.values_mut() {
+        match child {
+            ContentType::Node(n) => children.push(n),
+            ContentType::NodeVec(ns) => children.extend(ns.iter_mut()),
+        }
+    }
+    // NOTE children without tokens are visited last
+    children.sort_by_key(|n| n.first_start().unwrap_or(usize::MAX));
+    children
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests;
+
+use crate::token::Token;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub token: Option<Box<Token>>, // None if the error was raised by the lexer
+    pub expected: Vec<String>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: String) -> ParseError {
+        ParseError {
+            line,
+            column,
+            token: None,
+            expected: Vec::new(),
+            message,
+        }
+    }
+    pub fn unexpected_token(token: &Token, expected: &[&str]) -> ParseError {
+        let got = if token.literal.is_empty() {
+            "EOF".to_string()
+        } else {
+            format!("token `{}`", token.literal)
+        };
+        let message = if expected.is_empty() {
+            format!("Unexpected {}", got)
+        } else {
+            format!("Expected {} but got: {}", join_expected(expected), got)
+        };
+        ParseError {
+            line: token.line,
+            column: token.column,
+            token: Some(Box::new(token.clone())),
+            expected: expected.iter().map(|s| s.to_string()).collect(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// ["A", "B", "C"] -> "`A`, `B` or `C`"
+fn join_expected(expected: &[&str]) -> String {
+    let quoted: Vec<String> = expected.iter().map(|s| format!("`{}`", s)).collect();
+    match quoted.split_last() {
+        Some((last, init)) if !init.is_empty() => format!("{} or {}", init.join(", "), last),
+        Some((last, _)) => last.to_string(),
+        None => "".to_string(),
+    }
+}
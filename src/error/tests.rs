@@ -0,0 +1,22 @@
+use super::*;
+
+#[test]
+fn test_unexpected_token() {
+    let error =
+        ParseError::unexpected_token(&Token::from_str(2, 3, "x"), &["IN", "LIKE", "BETWEEN"]);
+    assert_eq!(2, error.line);
+    assert_eq!(3, error.column);
+    assert_eq!(Some(Box::new(Token::from_str(2, 3, "x"))), error.token);
+    assert_eq!(vec!["IN", "LIKE", "BETWEEN"], error.expected);
+    assert_eq!(
+        "Expected `IN`, `LIKE` or `BETWEEN` but got: token `x`",
+        error.message
+    );
+    assert_eq!(
+        "2:3: Expected `IN`, `LIKE` or `BETWEEN` but got: token `x`",
+        error.to_string()
+    );
+
+    let error = ParseError::unexpected_token(&Token::eof(1, 1), &[]);
+    assert_eq!("Unexpected EOF", error.message);
+}
@@ -0,0 +1,736 @@
+#[cfg(test)]
+mod tests;
+
+mod doc;
+
+use crate::cst::{walk_mut, ContentType, Node, NodeType, VisitorMut};
+use crate::token::{CommentStyle, Token, TokenKind};
+use doc::{Doc, Printer};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum KeywordCase {
+    Upper,
+    Lower,
+    Preserve,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CommaStyle {
+    Trailing, // a,\n  b
+    Leading,  // a\n  , b
+}
+
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FormatOptions {
+    pub indent_width: usize,
+    pub keyword_case: KeywordCase,
+    pub max_width: usize,
+    pub comma_style: CommaStyle,
+    pub preserve_comments: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> FormatOptions {
+        FormatOptions {
+            indent_width: 2,
+            keyword_case: KeywordCase::Upper,
+            max_width: 80,
+            comma_style: CommaStyle::Trailing,
+            preserve_comments: true,
+        }
+    }
+}
+
+// `stmts` is expected to be the result of `Parser::parse_code()`
+pub fn format(stmts: &[Node], options: &FormatOptions) -> String {
+    let formatter = Formatter { options };
+    let doc = formatter.stmts(stmts);
+    let res = Printer::new(options.max_width, options.indent_width).print(&doc);
+    if res.is_empty() {
+        res
+    } else {
+        res + "\n"
+    }
+}
+
+// the node types whose alphabetic tokens are regarded as keywords
+const KEYWORD_NODE_TYPES: [NodeType; 28] = [
+    NodeType::BetweenOperator,
+    NodeType::BinaryOperator,
+    NodeType::BooleanLiteral,
+    NodeType::CaseArm,
+    NodeType::CaseExpr,
+    NodeType::CastArgument,
+    NodeType::ElseIfClause,
+    NodeType::ExtractArgument,
+    NodeType::ForSystemTimeAsOfClause,
+    NodeType::InOperator,
+    NodeType::IntervalLiteral,
+    NodeType::JoinOperator,
+    NodeType::Keyword,
+    NodeType::KeywordWithExpr,
+    NodeType::KeywordWithExprs,
+    NodeType::KeywordWithGroupedXXX,
+    NodeType::KeywordWithStatement,
+    NodeType::KeywordWithStatements,
+    NodeType::KeywordWithType,
+    NodeType::LanguageSpecifier,
+    NodeType::LimitClause,
+    NodeType::NullLiteral,
+    NodeType::OverClause,
+    NodeType::SetOperator,
+    NodeType::UnaryOperator,
+    NodeType::WhenClause,
+    NodeType::WithClause,
+    NodeType::XXXByExprs,
+];
+
+// the node types which start with a new line in statements
+const CLAUSE_NODE_TYPES: [NodeType; 9] = [
+    NodeType::KeywordWithExpr,
+    NodeType::KeywordWithExprs,
+    NodeType::KeywordWithStatement,
+    NodeType::KeywordWithStatements,
+    NodeType::LimitClause,
+    NodeType::WhenClause,
+    NodeType::WindowClause,
+    NodeType::WithClause,
+    NodeType::XXXByExprs,
+];
+
+// a token of the node itself, or a child (or children) of the node
+struct Item<'a> {
+    key: &'a str, // "self" means the token of the node
+    nodes: Vec<&'a Node>,
+    first: &'a Token,
+    last: &'a Token,
+}
+
+impl<'a> Item<'a> {
+    fn is_self(&self) -> bool {
+        self.key == "self"
+    }
+    fn is_comment(&self) -> bool {
+        self.key == "leading_comments" || self.key == "trailing_comments"
+    }
+    fn node_type(&self) -> Option<&NodeType> {
+        self.nodes.first().map(|n| &n.node_type)
+    }
+    fn is_clause(&self) -> bool {
+        self.node_type()
+            .is_some_and(|t| CLAUSE_NODE_TYPES.contains(t))
+    }
+    fn is_statement(&self) -> bool {
+        self.node_type().is_some_and(is_statement)
+    }
+    fn ends_with_line_comment(&self) -> bool {
+        is_line_comment(self.last)
+    }
+}
+
+struct Formatter<'a> {
+    options: &'a FormatOptions,
+}
+
+impl<'a> Formatter<'a> {
+    fn stmts(&self, stmts: &[Node]) -> Doc {
+        let mut docs = Vec::new();
+        for stmt in stmts {
+            // EOF has nothing to print but comments
+            if stmt.node_type == NodeType::EOF
+                && (!self.options.preserve_comments || stmt.children().is_empty())
+            {
+                continue;
+            }
+            if !docs.is_empty() {
+                docs.push(newlines(first_token(stmt)));
+            }
+            docs.push(self.node(stmt));
+        }
+        Doc::Concat(docs)
+    }
+    fn node(&self, node: &Node) -> Doc {
+        self.node_excluding(node, None)
+    }
+    fn node_excluding(&self, node: &Node, excluded_key: Option<&str>) -> Doc {
+        let items = self.items(node, excluded_key);
+        match &node.node_type {
+            NodeType::CaseExpr => self.case_expr(node, &items),
+            NodeType::KeywordWithStatements => self.keyword_with_statements(node, &items),
+            NodeType::SelectStatement => self.clause(node, &items, true),
+            NodeType::SetOperator => self.set_operator(node, &items),
+            NodeType::WhenClause => self.when_clause(node, &items),
+            t if CLAUSE_NODE_TYPES.contains(t) => self.clause(node, &items, false),
+            t if is_statement(t) => self.statement(node, &items),
+            _ => {
+                if is_bracketed(node) {
+                    self.bracketed(node, &items)
+                } else {
+                    self.seq(node, &items)
+                }
+            }
+        }
+    }
+    fn items<'b>(&self, node: &'b Node, excluded_key: Option<&str>) -> Vec<Item<'b>> {
+        let mut items = Vec::new();
+        if let Some(t) = &node.token {
+            items.push(Item {
+                key: "self",
+                nodes: Vec::new(),
+                first: t,
+                last: t,
+            });
+        }
+        for (key, child) in node.children() {
+            if Some(key.as_str()) == excluded_key {
+                continue;
+            }
+            if !self.options.preserve_comments
+                && (key == "leading_comments" || key == "trailing_comments")
+            {
+                continue;
+            }
+            let nodes: Vec<&Node> = match child {
+                ContentType::Node(n) => vec![n],
+                ContentType::NodeVec(ns) => ns.iter().collect(),
+            };
+            let mut tokens: Vec<&Token> = nodes.iter().flat_map(|n| n.tokens()).collect();
+            tokens.sort_by_key(|t| t.start);
+            if let (Some(first), Some(last)) = (tokens.first(), tokens.last()) {
+                items.push(Item {
+                    key,
+                    nodes,
+                    first,
+                    last,
+                });
+            }
+        }
+        items.sort_by_key(|i| i.first.start);
+        items
+    }
+    // ----- layouts -----
+    fn seq(&self, node: &Node, items: &[Item]) -> Doc {
+        let mut docs = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            if 0 < i {
+                docs.push(self.separator(node, &items[i - 1], item));
+            }
+            docs.push(self.item(node, item));
+        }
+        Doc::Concat(docs)
+    }
+    fn statement(&self, node: &Node, items: &[Item]) -> Doc {
+        let mut docs = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            if item.key == "stmts" {
+                docs.push(Doc::indent(Doc::Concat(vec![
+                    Doc::HardLine,
+                    self.item(node, item),
+                ])));
+                continue;
+            }
+            if 0 < i {
+                let prev = &items[i - 1];
+                let is_new_line = if item.key == "semicolon" || item.is_comment() {
+                    false
+                } else if let "then" | "do" | "default" = item.key {
+                    false // IF cond THEN | WHILE cond DO | DECLARE x DEFAULT 1
+                } else {
+                    item.is_clause()
+                        || item.is_statement()
+                        || prev.is_statement()
+                        || prev.key == "stmts"
+                        || prev.node_type() == Some(&NodeType::KeywordWithStatements)
+                        || item.node_type() == Some(&NodeType::ElseIfClause)
+                };
+                if is_new_line {
+                    docs.push(Doc::HardLine);
+                } else {
+                    docs.push(self.separator(node, prev, item));
+                }
+            }
+            docs.push(self.item(node, item));
+        }
+        Doc::Concat(docs)
+    }
+    // WHERE expr | GROUP BY exprs | SELECT exprs FROM ... | ...
+    fn clause(&self, node: &Node, items: &[Item], is_select: bool) -> Doc {
+        let mut docs = Vec::new();
+        let mut head = Vec::new();
+        let mut body = Vec::new();
+        let start = node.token.as_ref().map_or(0, |t| t.start);
+        let mut idx = 0;
+        // WITH clause or leading comments
+        while idx < items.len() && items[idx].first.start < start {
+            if 0 < idx {
+                docs.push(self.separator(node, &items[idx - 1], &items[idx]));
+            }
+            docs.push(self.item(node, &items[idx]));
+            idx += 1;
+        }
+        if 0 < idx {
+            if items[idx - 1].key == "with" {
+                docs.push(Doc::HardLine);
+            } else {
+                docs.push(self.separator(node, &items[idx - 1], &items[idx]));
+            }
+        }
+        // head
+        while idx < items.len() {
+            let item = &items[idx];
+            let is_head = item.is_self()
+                || item.is_comment()
+                || item.node_type() == Some(&NodeType::Keyword)
+                || item.key == "as_struct_or_value";
+            if !is_head {
+                break;
+            }
+            if !head.is_empty() {
+                head.push(self.separator(node, &items[idx - 1], item));
+            }
+            head.push(self.item(node, item));
+            idx += 1;
+        }
+        // body
+        while idx < items.len() {
+            let item = &items[idx];
+            if is_select && item.key != "exprs" {
+                break;
+            }
+            if !body.is_empty() {
+                body.push(self.separator(node, &items[idx - 1], item));
+            }
+            body.push(self.item(node, item));
+            idx += 1;
+        }
+        // NOTE `VALUES (1, 2), (3, 4)` is a list
+        let is_grouped = !is_select
+            && body.len() == 1
+            && items[idx - 1].nodes.len() == 1
+            && items[idx - 1].first.literal == "("
+            && !items[..idx - 1].iter().any(|i| i.ends_with_line_comment());
+        if body.is_empty() {
+            docs.push(Doc::Concat(head));
+        } else if is_grouped {
+            // AS (SELECT 1) | OPTIONS (...)
+            docs.push(Doc::Concat(head));
+            docs.push(Doc::Space);
+            docs.push(Doc::Concat(body));
+        } else {
+            docs.push(Doc::group(Doc::Concat(vec![
+                Doc::Concat(head),
+                Doc::indent(Doc::Concat(vec![Doc::Line, Doc::Concat(body)])),
+            ])));
+        }
+        // clauses of SELECT statement
+        while idx < items.len() {
+            let item = &items[idx];
+            if item.key == "semicolon" || item.is_comment() {
+                docs.push(self.separator(node, &items[idx - 1], item));
+            } else {
+                docs.push(Doc::HardLine);
+            }
+            docs.push(self.item(node, item));
+            idx += 1;
+        }
+        Doc::Concat(docs)
+    }
+    fn set_operator(&self, node: &Node, items: &[Item]) -> Doc {
+        let mut docs = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            if 0 < i {
+                if item.is_self() || item.key == "right" || items[i - 1].key == "with" {
+                    docs.push(Doc::HardLine);
+                } else {
+                    docs.push(self.separator(node, &items[i - 1], item));
+                }
+            }
+            docs.push(self.item(node, item));
+        }
+        Doc::Concat(docs)
+    }
+    fn case_expr(&self, node: &Node, items: &[Item]) -> Doc {
+        let mut docs = Vec::new();
+        let mut arms = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            match item.key {
+                "arms" | "else" => {
+                    for arm in &item.nodes {
+                        arms.push(Doc::Line);
+                        arms.push(self.node(arm));
+                    }
+                }
+                "end" => {
+                    docs.push(Doc::indent(Doc::Concat(arms)));
+                    arms = Vec::new();
+                    docs.push(Doc::Line);
+                    docs.push(self.item(node, item));
+                }
+                _ => {
+                    if 0 < i {
+                        docs.push(self.separator(node, &items[i - 1], item));
+                    }
+                    docs.push(self.item(node, item));
+                }
+            }
+        }
+        Doc::group(Doc::Concat(docs))
+    }
+    // THEN stmts | ELSE stmts | LOOP stmts
+    fn keyword_with_statements(&self, node: &Node, items: &[Item]) -> Doc {
+        let mut docs = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            if item.key == "stmts" {
+                docs.push(Doc::indent(Doc::Concat(vec![
+                    Doc::HardLine,
+                    self.item(node, item),
+                ])));
+            } else {
+                if 0 < i {
+                    docs.push(self.separator(node, &items[i - 1], item));
+                }
+                docs.push(self.item(node, item));
+            }
+        }
+        Doc::Concat(docs)
+    }
+    // WHEN [NOT] MATCHED [BY ...] [AND cond] THEN stmt
+    // the statement follows THEN on the same line if it fits
+    fn when_clause(&self, node: &Node, items: &[Item]) -> Doc {
+        let mut docs = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            if 0 < i {
+                docs.push(self.separator(node, &items[i - 1], item));
+            }
+            match item.key {
+                "then" => docs.push(self.then_statement(item.nodes[0])),
+                _ => docs.push(self.item(node, item)),
+            }
+        }
+        Doc::Concat(docs)
+    }
+    fn then_statement(&self, node: &Node) -> Doc {
+        let items = self.items(node, None);
+        let mut docs = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            if item.key == "stmt" {
+                docs.push(Doc::group(Doc::indent(Doc::Concat(vec![
+                    Doc::Line,
+                    self.item(node, item),
+                ]))));
+                continue;
+            }
+            if 0 < i {
+                docs.push(self.separator(node, &items[i - 1], item));
+            }
+            docs.push(self.item(node, item));
+        }
+        Doc::Concat(docs)
+    }
+    // f(x) | (1, 2) | [1, 2] | <INT64> | ...
+    fn bracketed(&self, node: &Node, items: &[Item]) -> Doc {
+        // unwrap is safe because `is_bracketed()` is checked
+        let lparen = node.token.as_ref().unwrap();
+        let rparen = node.get_node("rparen").unwrap().token.as_ref().unwrap();
+        let mut docs = Vec::new();
+        let mut inner = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            if lparen.start < item.first.start && item.first.start < rparen.start {
+                if !inner.is_empty() {
+                    inner.push(self.separator(node, &items[i - 1], item));
+                }
+                inner.push(self.item(node, item));
+                continue;
+            }
+            if 0 < i && item.key != "rparen" {
+                docs.push(self.separator(node, &items[i - 1], item));
+            }
+            if item.key == "rparen" && !inner.is_empty() {
+                let inner = std::mem::take(&mut inner);
+                let lparen = docs.pop().unwrap();
+                if lparen_literal(node) == "<" {
+                    // <INT64> is never broken
+                    docs.push(Doc::Concat(vec![
+                        lparen,
+                        Doc::Concat(inner),
+                        self.item(node, item),
+                    ]));
+                } else {
+                    docs.push(Doc::group(Doc::Concat(vec![
+                        lparen,
+                        Doc::indent(Doc::Concat(vec![Doc::SoftLine, Doc::Concat(inner)])),
+                        Doc::SoftLine,
+                        self.item(node, item),
+                    ])));
+                }
+                continue;
+            }
+            docs.push(self.item(node, item));
+        }
+        Doc::Concat(docs)
+    }
+    // ----- items -----
+    fn item(&self, node: &Node, item: &Item) -> Doc {
+        if item.is_self() {
+            return self.token(node, item.first);
+        }
+        if item.nodes.len() == 1 && !has_comma(item.nodes[0]) {
+            return self.node(item.nodes[0]);
+        }
+        self.node_vec(item)
+    }
+    fn node_vec(&self, item: &Item) -> Doc {
+        let mut docs = Vec::new();
+        let is_list = item.nodes.iter().any(|n| has_comma(n));
+        let mut prev_comma: Option<Doc> = None;
+        for (i, n) in item.nodes.iter().enumerate() {
+            if 0 < i {
+                if item.is_comment() {
+                    if n.token
+                        .as_ref()
+                        .is_some_and(|t| t.leading_whitespace.contains('\n'))
+                    {
+                        docs.push(newlines(first_token(n)));
+                    } else {
+                        docs.push(Doc::Space);
+                    }
+                } else if is_list {
+                    match self.options.comma_style {
+                        CommaStyle::Trailing => docs.push(Doc::Line),
+                        CommaStyle::Leading => docs.push(Doc::SoftLine),
+                    }
+                } else if is_statement(&n.node_type) || CLAUSE_NODE_TYPES.contains(&n.node_type) {
+                    docs.push(newlines(first_token(n)));
+                } else {
+                    docs.push(Doc::Space);
+                }
+            }
+            if let Some(c) = prev_comma.take() {
+                docs.push(c);
+                docs.push(Doc::Space);
+            }
+            if !is_list || !has_comma(n) {
+                docs.push(self.node(n));
+                continue;
+            }
+            // `x -- comment` + `,` is printed as `x, -- comment` (`x -- comment` + `, y` if leading)
+            let detached = if self.options.preserve_comments {
+                detach_line_comments(n)
+            } else {
+                None
+            };
+            let (n, comments) = match detached {
+                Some((n, comments)) => (Cow::Owned(n), comments),
+                None => (Cow::Borrowed(*n), Vec::new()),
+            };
+            let comma = n.get_node("comma").unwrap();
+            docs.push(self.node_excluding(&n, Some("comma")));
+            let is_last = i + 1 == item.nodes.len();
+            match self.options.comma_style {
+                CommaStyle::Leading if !is_last => prev_comma = Some(self.node(comma)),
+                _ => docs.push(self.node(comma)),
+            }
+            for (j, comment) in comments.iter().enumerate() {
+                if j == 0 {
+                    docs.push(Doc::Space);
+                }
+                docs.push(self.node(comment));
+            }
+        }
+        Doc::Concat(docs)
+    }
+    fn token(&self, node: &Node, token: &Token) -> Doc {
+        let literal = if is_keyword(node, token) {
+            match self.options.keyword_case {
+                KeywordCase::Upper => token.literal.to_uppercase(),
+                KeywordCase::Lower => token.literal.to_lowercase(),
+                KeywordCase::Preserve => token.literal.clone(),
+            }
+        } else {
+            token.literal.clone()
+        };
+        if is_line_comment(token) {
+            // nothing can follow line comments
+            Doc::Concat(vec![Doc::Text(literal), Doc::HardLine])
+        } else {
+            Doc::Text(literal)
+        }
+    }
+    fn separator(&self, node: &Node, prev: &Item, next: &Item) -> Doc {
+        let none = Doc::text("");
+        if prev.ends_with_line_comment() {
+            return newlines(next.first);
+        }
+        if next.is_comment() {
+            return Doc::Space;
+        }
+        let prev_literal = prev.last.literal.as_str();
+        let next_literal = next.first.literal.as_str();
+        // ----- no space -----
+        if let "," | ";" | "." = next_literal {
+            return none;
+        }
+        if prev_literal == "." || next.key == "rparen" {
+            return none;
+        }
+        if prev.is_self() && is_bracketed(node) {
+            return none;
+        }
+        if next.is_self() {
+            match node.node_type {
+                // `JOIN ... USING (a)` is parsed as a function call
+                NodeType::CallingFunction if prev.first.is("USING") => (),
+                NodeType::CallingFunction
+                | NodeType::CallingTableFunction
+                | NodeType::CallingUnnest
+                | NodeType::CallingArrayAccessingFunction
+                | NodeType::ArrayAccessing => return none,
+                NodeType::ArrayLiteral | NodeType::StructLiteral if prev.key == "type" => {
+                    return none
+                }
+                _ => (),
+            }
+        }
+        if next.key == "type_declaration" && next_literal == "<" {
+            return none;
+        }
+        if prev.is_self() && node.node_type == NodeType::UnaryOperator {
+            // NOTE `- -1` should not be `--1`
+            if let "-" | "+" | "~" = prev_literal {
+                if !next_literal.starts_with('-') && !next_literal.starts_with('+') {
+                    return none;
+                }
+            }
+        }
+        if node.node_type == NodeType::BinaryOperator
+            && node.token.as_ref().is_some_and(|t| t.is("-"))
+            && next.first.leading_whitespace.is_empty()
+            && !next_literal.starts_with('-')
+        {
+            // my-project.dataset.table
+            return none;
+        }
+        // ----- line -----
+        if next.is_self()
+            && node.node_type == NodeType::BinaryOperator
+            && next.first.in_(&vec!["AND", "OR"])
+        {
+            return Doc::Line;
+        }
+        if node.node_type == NodeType::JoinOperator {
+            let is_comma = node.token.as_ref().is_some_and(|t| t.is(","));
+            if (prev.key == "left" && !is_comma) || (prev.is_self() && is_comma) {
+                return Doc::Line;
+            }
+        }
+        Doc::Space
+    }
+}
+
+// a newline or a blank line (if there are blank lines in the original code)
+fn newlines(token: &Token) -> Doc {
+    if 2 <= token.leading_whitespace.matches('\n').count() {
+        Doc::BlankLine
+    } else {
+        Doc::HardLine
+    }
+}
+
+fn is_statement(node_type: &NodeType) -> bool {
+    *node_type != NodeType::GroupedStatement && format!("{:?}", node_type).ends_with("Statement")
+}
+
+fn is_bracketed(node: &Node) -> bool {
+    node.get_node("rparen").is_some() && ["(", "[", "<"].contains(&lparen_literal(node))
+}
+
+fn lparen_literal(node: &Node) -> &str {
+    node.token.as_ref().map_or("", |t| t.literal.as_str())
+}
+
+fn is_keyword(node: &Node, token: &Token) -> bool {
+    let mut chars = token.literal.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() => (),
+        _ => return false,
+    }
+    if !chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return false;
+    }
+    if token.is_reserved_keyword() {
+        return true;
+    }
+    if node.node_type == NodeType::Error {
+        return false;
+    }
+    KEYWORD_NODE_TYPES.contains(&node.node_type) || is_statement(&node.node_type)
+}
+
+fn is_line_comment(token: &Token) -> bool {
+    match token.kind {
+        TokenKind::Comment { style } => style != CommentStyle::Block,
+        _ => false,
+    }
+}
+
+fn has_comma(node: &Node) -> bool {
+    node.get_node("comma").is_some()
+}
+
+fn first_token(node: &Node) -> &Token {
+    // NOTE every node in CST has at least one token
+    node.tokens()[0]
+}
+
+// the comments after the last token of the list item (its comma aside)
+// None if there is no line comment, which would swallow the comma
+fn detach_line_comments(node: &Node) -> Option<(Node, Vec<Node>)> {
+    let comma = node.get_node("comma")?.token.as_ref()?.start;
+    let last = node
+        .tokens()
+        .into_iter()
+        .filter(|t| !t.is_comment() && t.start != comma)
+        .map(|t| t.start)
+        .max()?;
+    let mut node = node.clone();
+    let mut detacher = CommentDetacher {
+        after: last,
+        comments: Vec::new(),
+    };
+    walk_mut(&mut detacher, &mut node);
+    if !detacher
+        .comments
+        .iter()
+        .any(|c| is_line_comment(first_token(c)))
+    {
+        return None;
+    }
+    Some((node, detacher.comments))
+}
+
+struct CommentDetacher {
+    after: usize,
+    comments: Vec<Node>,
+}
+
+impl VisitorMut for CommentDetacher {
+    fn visit_node(&mut self, node: &mut Node) {
+        let comments = match node.get_node_vec("trailing_comments") {
+            Some(comments) if comments.iter().all(|c| self.after < first_token(c).start) => {
+                comments.clone()
+            }
+            _ => return,
+        };
+        self.comments.extend(comments);
+        node.push_node_vec("trailing_comments", Vec::new());
+    }
+}
@@ -0,0 +1,179 @@
+// a simplified version of the algorithm described in
+// "A prettier printer" (Philip Wadler)
+
+#[derive(Clone, Debug)]
+pub enum Doc {
+    Text(String),
+    Space,     // " " unless at the beginning of the line
+    Line,      // " " or newline
+    SoftLine,  // "" or newline
+    HardLine,  // always newline
+    BlankLine, // empty line (consecutive blank lines are merged)
+    Indent(Box<Doc>),
+    Group(Box<Doc>), // contents are printed in one line if they fit
+    Concat(Vec<Doc>),
+}
+
+impl Doc {
+    pub fn text(s: &str) -> Doc {
+        Doc::Text(s.to_string())
+    }
+    pub fn indent(doc: Doc) -> Doc {
+        Doc::Indent(Box::new(doc))
+    }
+    pub fn group(doc: Doc) -> Doc {
+        Doc::Group(Box::new(doc))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+pub struct Printer {
+    max_width: usize,
+    indent_width: usize,
+    res: String,
+    column: usize,
+    is_line_empty: bool,
+}
+
+impl Printer {
+    pub fn new(max_width: usize, indent_width: usize) -> Printer {
+        Printer {
+            max_width,
+            indent_width,
+            res: String::new(),
+            column: 0,
+            is_line_empty: true,
+        }
+    }
+    pub fn print(mut self, doc: &Doc) -> String {
+        let mut stack: Vec<(usize, Mode, &Doc)> = vec![(0, Mode::Break, doc)];
+        while let Some((indent, mode, doc)) = stack.pop() {
+            match doc {
+                Doc::Text(s) => self.push_text(indent, s),
+                Doc::Space => {
+                    if !self.is_line_empty {
+                        self.push_text(indent, " ");
+                    }
+                }
+                Doc::Line => match mode {
+                    Mode::Flat => {
+                        if !self.is_line_empty {
+                            self.push_text(indent, " ");
+                        }
+                    }
+                    Mode::Break => self.newline(),
+                },
+                Doc::SoftLine => {
+                    if mode == Mode::Break {
+                        self.newline();
+                    }
+                }
+                Doc::HardLine => self.newline(),
+                Doc::BlankLine => {
+                    self.newline();
+                    if !self.res.is_empty() && !self.res.ends_with("\n\n") {
+                        self.res.push('\n');
+                    }
+                }
+                Doc::Indent(d) => stack.push((indent + self.indent_width, mode, d)),
+                Doc::Group(d) => {
+                    let mode = if mode == Mode::Flat || self.fits(indent, d, &stack) {
+                        Mode::Flat
+                    } else {
+                        Mode::Break
+                    };
+                    stack.push((indent, mode, d));
+                }
+                Doc::Concat(ds) => {
+                    for d in ds.iter().rev() {
+                        stack.push((indent, mode, d));
+                    }
+                }
+            }
+        }
+        self.res.truncate(self.res.trim_end().len());
+        self.res
+    }
+    fn push_text(&mut self, indent: usize, s: &str) {
+        if s.is_empty() {
+            return;
+        }
+        if self.is_line_empty {
+            self.res.push_str(&" ".repeat(indent));
+            self.column = indent;
+            self.is_line_empty = false;
+        }
+        self.res.push_str(s);
+        self.column = match s.rfind('\n') {
+            Some(i) => s[i + 1..].chars().count(),
+            None => self.column + s.chars().count(),
+        };
+    }
+    fn newline(&mut self) {
+        // NOTE indentation is inserted by the next `push_text()`
+        if !self.is_line_empty {
+            self.res.truncate(self.res.trim_end_matches(' ').len());
+            self.res.push('\n');
+            self.column = 0;
+            self.is_line_empty = true;
+        }
+    }
+    fn fits(&self, indent: usize, doc: &Doc, rest: &[(usize, Mode, &Doc)]) -> bool {
+        let mut width = self.max_width as isize
+            - if self.is_line_empty {
+                indent
+            } else {
+                self.column
+            } as isize;
+        let mut stack: Vec<(Mode, &Doc)> = vec![(Mode::Flat, doc)];
+        let mut rest_idx = rest.len();
+        let mut in_rest = false;
+        loop {
+            if width < 0 {
+                return false;
+            }
+            let (mode, doc) = match stack.pop() {
+                Some(x) => x,
+                None => {
+                    // the following contents should also be fit until the next newline
+                    if rest_idx == 0 {
+                        return true;
+                    }
+                    rest_idx -= 1;
+                    in_rest = true;
+                    (rest[rest_idx].1, rest[rest_idx].2)
+                }
+            };
+            match doc {
+                Doc::Text(s) => {
+                    if s.contains('\n') {
+                        return in_rest;
+                    }
+                    width -= s.chars().count() as isize;
+                }
+                Doc::Space => width -= 1,
+                Doc::Line => match mode {
+                    Mode::Flat => width -= 1,
+                    Mode::Break => return true,
+                },
+                Doc::SoftLine => {
+                    if mode == Mode::Break {
+                        return true;
+                    }
+                }
+                Doc::HardLine | Doc::BlankLine => return in_rest,
+                Doc::Indent(d) | Doc::Group(d) => stack.push((mode, d)),
+                Doc::Concat(ds) => {
+                    for d in ds.iter().rev() {
+                        stack.push((mode, d));
+                    }
+                }
+            }
+        }
+    }
+}
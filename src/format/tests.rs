@@ -0,0 +1,336 @@
+use super::*;
+use crate::parser::Parser;
+
+struct TestCase {
+    code: String,
+    options: FormatOptions,
+    expected_output: String,
+}
+
+impl TestCase {
+    pub fn new(code: &str, expected_output: &str) -> TestCase {
+        TestCase::with_options(code, FormatOptions::default(), expected_output)
+    }
+    pub fn with_options(code: &str, options: FormatOptions, expected_output: &str) -> TestCase {
+        TestCase {
+            code: code.to_string(),
+            options,
+            expected_output: expected_output.to_string(),
+        }
+    }
+    pub fn test(&self) {
+        let stmts = Parser::new(self.code.clone())
+            .unwrap()
+            .parse_code()
+            .unwrap();
+        let result = format(&stmts, &self.options);
+        assert_eq!(self.expected_output, result);
+        // formatted code should not be changed anymore
+        let stmts = Parser::new(result.clone()).unwrap().parse_code().unwrap();
+        assert_eq!(result, format(&stmts, &self.options));
+    }
+}
+
+#[test]
+fn test_format_select() {
+    let test_cases = vec![
+        TestCase::new("", ""),
+        TestCase::new("select 1", "SELECT 1\n"),
+        TestCase::new(
+            "select a, b from t where a = 1 and b = 2 order by a desc limit 10;",
+            "\
+SELECT a, b
+FROM t
+WHERE a = 1 AND b = 2
+ORDER BY a DESC
+LIMIT 10;
+",
+        ),
+        TestCase::new(
+            "with x as (select 1 one) select distinct * from x union all select 2;",
+            "\
+WITH x AS (SELECT 1 one)
+SELECT DISTINCT *
+FROM x
+UNION ALL
+SELECT 2;
+",
+        ),
+        TestCase::new(
+            "select f(x), arr[offset(1)], struct<a int64>(1), - -1, -x, [1,2] from `my-project.ds.t`;",
+            "\
+SELECT f(x), arr[offset(1)], STRUCT<a int64>(1), - -1, -x, [1, 2]
+FROM `my-project.ds.t`;
+",
+        ),
+        TestCase::new(
+            "select case when a then 1 else 2 end, cast(x as string) from t1 left join t2 on t1.id = t2.id",
+            "\
+SELECT CASE WHEN a THEN 1 ELSE 2 END, CAST(x AS string)
+FROM t1 LEFT JOIN t2 ON t1.id = t2.id
+",
+        ),
+        TestCase::new(
+            "select * from t1 join t2 using(id)",
+            "\
+SELECT *
+FROM t1 JOIN t2 USING (id)
+",
+        ),
+    ];
+    for t in test_cases {
+        t.test();
+    }
+}
+
+#[test]
+fn test_format_dml() {
+    let test_cases = vec![
+        TestCase::new(
+            "merge t using s on t.id = s.id when matched then update set a = 1 when not matched by source and true then delete when not matched then insert (id, a) values (s.id, s.a);",
+            "\
+MERGE t
+USING s
+ON t.id = s.id
+WHEN MATCHED THEN
+  UPDATE
+  SET a = 1
+WHEN NOT MATCHED BY SOURCE AND TRUE THEN DELETE
+WHEN NOT MATCHED THEN
+  INSERT (id, a)
+  VALUES (s.id, s.a);
+",
+        ),
+        TestCase::new(
+            "merge t using s on false when not matched then insert row",
+            "\
+MERGE t
+USING s
+ON FALSE
+WHEN NOT MATCHED THEN INSERT ROW
+",
+        ),
+        TestCase::new(
+            "insert into t (a, b) values (1, 2), (3, 4)",
+            "\
+INSERT INTO t (a, b)
+VALUES (1, 2), (3, 4)
+",
+        ),
+        // the rows are indented like other lists
+        TestCase::with_options(
+            "insert into t (a, b) values (1111, 2222), (3333, 4444), (5555, 6666)",
+            FormatOptions {
+                max_width: 30,
+                ..Default::default()
+            },
+            "\
+INSERT INTO t (a, b)
+VALUES
+  (1111, 2222),
+  (3333, 4444),
+  (5555, 6666)
+",
+        ),
+    ];
+    for t in test_cases {
+        t.test();
+    }
+}
+
+#[test]
+fn test_format_script() {
+    let test_cases = vec![
+        TestCase::new(
+            "declare x int64 default 1; while x > 0 do set x = x - 1; end while;",
+            "\
+DECLARE x int64 DEFAULT 1;
+WHILE x > 0 DO
+  SET x = x - 1;
+END WHILE;
+",
+        ),
+        TestCase::new(
+            "if true then select 1; else select 2; end if;",
+            "\
+IF TRUE THEN
+  SELECT 1;
+ELSE
+  SELECT 2;
+END IF;
+",
+        ),
+        TestCase::new(
+            "begin select 1; exception when error then select 2; end;",
+            "\
+BEGIN
+  SELECT 1;
+EXCEPTION WHEN ERROR THEN
+  SELECT 2;
+END;
+",
+        ),
+    ];
+    for t in test_cases {
+        t.test();
+    }
+}
+
+#[test]
+fn test_format_comments() {
+    let test_cases = vec![
+        TestCase::new(
+            "\
+-- leading
+
+
+select
+  a, -- a
+  /* b */ b
+from t; -- trailing
+
+
+
+select 2;
+-- EOF
+",
+            "\
+-- leading
+
+SELECT
+  a, -- a
+  /* b */ b
+FROM t; -- trailing
+
+SELECT 2;
+-- EOF
+",
+        ),
+        TestCase::with_options(
+            "-- leading\nselect a, -- a\nb from t; -- trailing\n-- EOF",
+            FormatOptions {
+                preserve_comments: false,
+                ..Default::default()
+            },
+            "\
+SELECT a, b
+FROM t;
+",
+        ),
+        // the comma is placed before line comments
+        TestCase::new(
+            "select\n  a -- a\n  , f(b) -- b\n  , c\nfrom t",
+            "\
+SELECT
+  a, -- a
+  f(b), -- b
+  c
+FROM t
+",
+        ),
+        TestCase::with_options(
+            "select a, -- a\n  b, -- b\n  c\nfrom t",
+            FormatOptions {
+                comma_style: CommaStyle::Leading,
+                ..Default::default()
+            },
+            "\
+SELECT
+  a -- a
+  , b -- b
+  , c
+FROM t
+",
+        ),
+    ];
+    for t in test_cases {
+        t.test();
+    }
+}
+
+#[test]
+fn test_format_options() {
+    let code = "Select aaaa, bbbb, f(cccc, dddd) From t";
+    let test_cases = vec![
+        TestCase::with_options(
+            code,
+            FormatOptions {
+                keyword_case: KeywordCase::Lower,
+                ..Default::default()
+            },
+            "\
+select aaaa, bbbb, f(cccc, dddd)
+from t
+",
+        ),
+        TestCase::with_options(
+            code,
+            FormatOptions {
+                keyword_case: KeywordCase::Preserve,
+                indent_width: 4,
+                max_width: 30,
+                ..Default::default()
+            },
+            "\
+Select
+    aaaa,
+    bbbb,
+    f(cccc, dddd)
+From t
+",
+        ),
+        TestCase::with_options(
+            code,
+            FormatOptions {
+                comma_style: CommaStyle::Leading,
+                max_width: 30,
+                ..Default::default()
+            },
+            "\
+SELECT
+  aaaa
+  , bbbb
+  , f(cccc, dddd)
+FROM t
+",
+        ),
+        TestCase::with_options(
+            code,
+            FormatOptions {
+                max_width: 10,
+                ..Default::default()
+            },
+            "\
+SELECT
+  aaaa,
+  bbbb,
+  f(
+    cccc,
+    dddd
+  )
+FROM t
+",
+        ),
+        TestCase::with_options(
+            "select case when aaaa then 1 else 2 end from t where aaaa = 1 and bbbb = 2",
+            FormatOptions {
+                max_width: 20,
+                ..Default::default()
+            },
+            "\
+SELECT
+  CASE
+    WHEN aaaa THEN 1
+    ELSE 2
+  END
+FROM t
+WHERE
+  aaaa = 1
+  AND bbbb = 2
+",
+        ),
+    ];
+    for t in test_cases {
+        t.test();
+    }
+}
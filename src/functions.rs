@@ -0,0 +1,1156 @@
+// built-in functions of BigQuery
+// the catalog is used to validate function calls and to infer their return types
+#[cfg(test)]
+mod tests;
+
+use crate::catalog::{Type, ValidationError};
+use crate::cst::{Node, NodeType};
+use crate::infer;
+use crate::names;
+use crate::token::Span;
+use std::fmt;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Kind {
+    Scalar,
+    Aggregate,
+    Window, // navigation and numbering functions (`OVER (...)` is required)
+}
+
+// `ty` is informational only (`validate()` does not check the types of arguments)
+// `ANY` is any type and `NUMBER` is any numeric type
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Argument {
+    pub ty: &'static str, // `STRING|BYTES` accepts either of them
+    pub optional: bool,
+    pub repeated: bool, // only the last argument is repeated
+}
+
+const fn req(ty: &'static str) -> Argument {
+    Argument {
+        ty,
+        optional: false,
+        repeated: false,
+    }
+}
+
+const fn opt(ty: &'static str) -> Argument {
+    Argument {
+        ty,
+        optional: true,
+        repeated: false,
+    }
+}
+
+// zero or more times
+const fn rep(ty: &'static str) -> Argument {
+    Argument {
+        ty,
+        optional: true,
+        repeated: true,
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(crate) enum Returns {
+    Scalar(&'static str),  // a scalar type
+    ArrayOf(&'static str), // an array of a scalar type
+    Arg(usize),            // the type of the argument
+    ArrayOfArg(usize),     // an array of the type of the argument
+    ElementOf(usize),      // the element type of the array argument
+    Common(usize),         // the common type of the arguments after the index
+    Fractional(usize),     // the type of the argument (FLOAT64 if it is INT64)
+    Extract,               // EXTRACT(part FROM x)
+    Other,                 // not modeled (e.g. STRUCT)
+}
+
+use Returns::*;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Function {
+    pub name: &'static str, // upper case (e.g. `NET.HOST`)
+    pub kind: Kind,
+    pub args: &'static [Argument],
+    pub analytic: bool,     // `OVER (...)` is allowed
+    pub safe: bool,         // `SAFE.` prefix is allowed
+    pub distinct: bool,     // `f(DISTINCT x)`
+    pub ignore_nulls: bool, // `IGNORE NULLS` and `RESPECT NULLS`
+    pub order_by: bool,     // `f(x ORDER BY y)`
+    pub limit: bool,        // `f(x LIMIT 1)`
+    pub(crate) returns: Returns,
+}
+
+const fn function(name: &'static str, args: &'static [Argument], returns: Returns) -> Function {
+    Function {
+        name,
+        kind: Kind::Scalar,
+        args,
+        analytic: false,
+        safe: true,
+        distinct: false,
+        ignore_nulls: false,
+        order_by: false,
+        limit: false,
+        returns,
+    }
+}
+
+impl Function {
+    const fn aggregate(self) -> Function {
+        Function {
+            kind: Kind::Aggregate,
+            analytic: true,
+            safe: false,
+            ..self
+        }
+    }
+    const fn window(self) -> Function {
+        Function {
+            kind: Kind::Window,
+            analytic: true,
+            safe: false,
+            ..self
+        }
+    }
+    // aggregate functions which cannot be used as analytic functions
+    const fn not_analytic(self) -> Function {
+        Function {
+            analytic: false,
+            ..self
+        }
+    }
+    // conditional expressions and functions which take subqueries
+    const fn unsafe_(self) -> Function {
+        Function {
+            safe: false,
+            ..self
+        }
+    }
+    const fn distinct(self) -> Function {
+        Function {
+            distinct: true,
+            ..self
+        }
+    }
+    const fn ignore_nulls(self) -> Function {
+        Function {
+            ignore_nulls: true,
+            ..self
+        }
+    }
+    const fn ordered(self) -> Function {
+        Function {
+            order_by: true,
+            limit: true,
+            ..self
+        }
+    }
+    pub fn min_args(&self) -> usize {
+        self.args.iter().filter(|a| !a.optional).count()
+    }
+    // None if the number of arguments is not limited
+    pub fn max_args(&self) -> Option<usize> {
+        match self.args.iter().any(|a| a.repeated) {
+            true => None,
+            false => Some(self.args.len()),
+        }
+    }
+}
+
+// `SUBSTR(STRING|BYTES, INT64, [INT64])`
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|a| match (a.optional, a.repeated) {
+                (_, true) => format!("[{}, ...]", a.ty),
+                (true, false) => format!("[{}]", a.ty),
+                (false, false) => a.ty.to_string(),
+            })
+            .collect();
+        write!(f, "{}({})", self.name, args.join(", "))
+    }
+}
+
+const ANY: &str = "ANY";
+const NUMBER: &str = "NUMBER";
+const INT64: &str = "INT64";
+const FLOAT64: &str = "FLOAT64";
+const NUMERIC: &str = "NUMERIC";
+const BIGNUMERIC: &str = "BIGNUMERIC";
+const BOOL: &str = "BOOL";
+const STRING: &str = "STRING";
+const BYTES: &str = "BYTES";
+const STRINGS: &str = "STRING|BYTES";
+const DATE: &str = "DATE";
+const DATETIME: &str = "DATETIME";
+const TIME: &str = "TIME";
+const TIMESTAMP: &str = "TIMESTAMP";
+const INTERVAL: &str = "INTERVAL";
+const PART: &str = "DATE_PART";
+const JSON: &str = "JSON";
+const JSONS: &str = "JSON|STRING";
+const ARRAY: &str = "ARRAY";
+const GEOGRAPHY: &str = "GEOGRAPHY";
+const RANGE: &str = "RANGE";
+
+const FUNCTIONS: &[Function] = &[
+    // aggregate functions
+    function("ANY_VALUE", &[req(ANY)], Arg(0)).aggregate(),
+    function("APPROX_COUNT_DISTINCT", &[req(ANY)], Scalar(INT64))
+        .aggregate()
+        .not_analytic(),
+    function("APPROX_QUANTILES", &[req(ANY), req(INT64)], ArrayOfArg(0))
+        .aggregate()
+        .not_analytic()
+        .distinct()
+        .ignore_nulls(),
+    function("APPROX_TOP_COUNT", &[req(ANY), req(INT64)], Other)
+        .aggregate()
+        .not_analytic(),
+    function(
+        "APPROX_TOP_SUM",
+        &[req(ANY), req(NUMBER), req(INT64)],
+        Other,
+    )
+    .aggregate()
+    .not_analytic(),
+    function("ARRAY_AGG", &[req(ANY)], ArrayOfArg(0))
+        .aggregate()
+        .distinct()
+        .ignore_nulls()
+        .ordered(),
+    function("ARRAY_CONCAT_AGG", &[req(ARRAY)], Arg(0))
+        .aggregate()
+        .not_analytic()
+        .ordered(),
+    function("AVG", &[req(NUMBER)], Fractional(0))
+        .aggregate()
+        .distinct(),
+    function("BIT_AND", &[req(INT64)], Arg(0))
+        .aggregate()
+        .not_analytic()
+        .distinct(),
+    function("BIT_OR", &[req(INT64)], Arg(0))
+        .aggregate()
+        .not_analytic()
+        .distinct(),
+    function("BIT_XOR", &[req(INT64)], Arg(0))
+        .aggregate()
+        .not_analytic()
+        .distinct(),
+    function("CORR", &[req(NUMBER), req(NUMBER)], Scalar(FLOAT64)).aggregate(),
+    function("COUNT", &[req(ANY)], Scalar(INT64))
+        .aggregate()
+        .distinct(),
+    function("COUNTIF", &[req(BOOL)], Scalar(INT64))
+        .aggregate()
+        .distinct(),
+    function("COVAR_POP", &[req(NUMBER), req(NUMBER)], Scalar(FLOAT64)).aggregate(),
+    function("COVAR_SAMP", &[req(NUMBER), req(NUMBER)], Scalar(FLOAT64)).aggregate(),
+    function("GROUPING", &[req(ANY)], Scalar(INT64))
+        .aggregate()
+        .not_analytic(),
+    function("LOGICAL_AND", &[req(BOOL)], Scalar(BOOL)).aggregate(),
+    function("LOGICAL_OR", &[req(BOOL)], Scalar(BOOL)).aggregate(),
+    function("MAX", &[req(ANY)], Arg(0)).aggregate().distinct(),
+    function("MAX_BY", &[req(ANY), req(ANY)], Arg(0))
+        .aggregate()
+        .not_analytic(),
+    function("MIN", &[req(ANY)], Arg(0)).aggregate().distinct(),
+    function("MIN_BY", &[req(ANY), req(ANY)], Arg(0))
+        .aggregate()
+        .not_analytic(),
+    function("STDDEV", &[req(NUMBER)], Scalar(FLOAT64))
+        .aggregate()
+        .distinct(),
+    function("STDDEV_POP", &[req(NUMBER)], Scalar(FLOAT64))
+        .aggregate()
+        .distinct(),
+    function("STDDEV_SAMP", &[req(NUMBER)], Scalar(FLOAT64))
+        .aggregate()
+        .distinct(),
+    function("STRING_AGG", &[req(STRINGS), opt(STRINGS)], Arg(0))
+        .aggregate()
+        .distinct()
+        .ordered(),
+    function("SUM", &[req(NUMBER)], Arg(0))
+        .aggregate()
+        .distinct(),
+    function("VARIANCE", &[req(NUMBER)], Scalar(FLOAT64))
+        .aggregate()
+        .distinct(),
+    function("VAR_POP", &[req(NUMBER)], Scalar(FLOAT64))
+        .aggregate()
+        .distinct(),
+    function("VAR_SAMP", &[req(NUMBER)], Scalar(FLOAT64))
+        .aggregate()
+        .distinct(),
+    function("HLL_COUNT.EXTRACT", &[req(BYTES)], Scalar(INT64)),
+    function("HLL_COUNT.INIT", &[req(ANY), opt(INT64)], Scalar(BYTES))
+        .aggregate()
+        .not_analytic(),
+    function("HLL_COUNT.MERGE", &[req(BYTES)], Scalar(INT64))
+        .aggregate()
+        .not_analytic(),
+    function("HLL_COUNT.MERGE_PARTIAL", &[req(BYTES)], Scalar(BYTES))
+        .aggregate()
+        .not_analytic(),
+    // window functions
+    function("CUME_DIST", &[], Scalar(FLOAT64)).window(),
+    function("DENSE_RANK", &[], Scalar(INT64)).window(),
+    function("FIRST_VALUE", &[req(ANY)], Arg(0))
+        .window()
+        .ignore_nulls(),
+    function("LAG", &[req(ANY), opt(INT64), opt(ANY)], Arg(0)).window(),
+    function("LAST_VALUE", &[req(ANY)], Arg(0))
+        .window()
+        .ignore_nulls(),
+    function("LEAD", &[req(ANY), opt(INT64), opt(ANY)], Arg(0)).window(),
+    function("NTH_VALUE", &[req(ANY), req(INT64)], Arg(0))
+        .window()
+        .ignore_nulls(),
+    function("NTILE", &[req(INT64)], Scalar(INT64)).window(),
+    function(
+        "PERCENTILE_CONT",
+        &[req(NUMBER), req(NUMBER)],
+        Fractional(0),
+    )
+    .window()
+    .ignore_nulls(),
+    function("PERCENTILE_DISC", &[req(ANY), req(NUMBER)], Arg(0))
+        .window()
+        .ignore_nulls(),
+    function("PERCENT_RANK", &[], Scalar(FLOAT64)).window(),
+    function("RANK", &[], Scalar(INT64)).window(),
+    function("ROW_NUMBER", &[], Scalar(INT64)).window(),
+    function(
+        "ST_CLUSTERDBSCAN",
+        &[req(GEOGRAPHY), req(NUMBER), req(INT64)],
+        Scalar(INT64),
+    )
+    .window(),
+    // conditional expressions
+    function("COALESCE", &[req(ANY), rep(ANY)], Common(0)).unsafe_(),
+    function("IF", &[req(BOOL), req(ANY), req(ANY)], Common(1)).unsafe_(),
+    function("IFNULL", &[req(ANY), req(ANY)], Common(0)).unsafe_(),
+    function("NULLIF", &[req(ANY), req(ANY)], Arg(0)).unsafe_(),
+    function("NULLIFZERO", &[req(NUMBER)], Arg(0)),
+    function("ZEROIFNULL", &[req(NUMBER)], Arg(0)),
+    // conversion
+    function("CAST", &[req(ANY)], Arg(0)).unsafe_(),
+    function("SAFE_CAST", &[req(ANY)], Arg(0)).unsafe_(),
+    function("BOOL", &[req(JSON)], Scalar(BOOL)),
+    function("FLOAT64", &[req(JSON), opt(STRING)], Scalar(FLOAT64)),
+    function("INT64", &[req(JSON)], Scalar(INT64)),
+    function("LAX_BOOL", &[req(JSON)], Scalar(BOOL)),
+    function("LAX_FLOAT64", &[req(JSON)], Scalar(FLOAT64)),
+    function("LAX_INT64", &[req(JSON)], Scalar(INT64)),
+    function("LAX_STRING", &[req(JSON)], Scalar(STRING)),
+    function("PARSE_BIGNUMERIC", &[req(STRING)], Scalar(BIGNUMERIC)),
+    function("PARSE_NUMERIC", &[req(STRING)], Scalar(NUMERIC)),
+    function("STRING", &[req(ANY), opt(STRING)], Scalar(STRING)),
+    // mathematical functions
+    function("ABS", &[req(NUMBER)], Arg(0)),
+    function("ACOS", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("ACOSH", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("ASIN", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("ASINH", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("ATAN", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("ATAN2", &[req(NUMBER), req(NUMBER)], Scalar(FLOAT64)),
+    function("ATANH", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("BIT_COUNT", &[req("INT64|BYTES")], Scalar(INT64)),
+    function("CBRT", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("CEIL", &[req(NUMBER)], Fractional(0)),
+    function("CEILING", &[req(NUMBER)], Fractional(0)),
+    function("COS", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("COSH", &[req(NUMBER)], Scalar(FLOAT64)),
+    function(
+        "COSINE_DISTANCE",
+        &[req(ARRAY), req(ARRAY)],
+        Scalar(FLOAT64),
+    ),
+    function("COT", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("COTH", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("CSC", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("CSCH", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("DIV", &[req(NUMBER), req(NUMBER)], Common(0)),
+    function(
+        "EUCLIDEAN_DISTANCE",
+        &[req(ARRAY), req(ARRAY)],
+        Scalar(FLOAT64),
+    ),
+    function("EXP", &[req(NUMBER)], Fractional(0)),
+    function("FLOOR", &[req(NUMBER)], Fractional(0)),
+    function("GREATEST", &[req(ANY), rep(ANY)], Common(0)),
+    function("IEEE_DIVIDE", &[req(NUMBER), req(NUMBER)], Scalar(FLOAT64)),
+    function("IS_INF", &[req(NUMBER)], Scalar(BOOL)),
+    function("IS_NAN", &[req(NUMBER)], Scalar(BOOL)),
+    function("LEAST", &[req(ANY), rep(ANY)], Common(0)),
+    function("LN", &[req(NUMBER)], Fractional(0)),
+    function("LOG", &[req(NUMBER), opt(NUMBER)], Fractional(0)),
+    function("LOG10", &[req(NUMBER)], Fractional(0)),
+    function("MOD", &[req(NUMBER), req(NUMBER)], Common(0)),
+    function("POW", &[req(NUMBER), req(NUMBER)], Fractional(0)),
+    function("POWER", &[req(NUMBER), req(NUMBER)], Fractional(0)),
+    function("RAND", &[], Scalar(FLOAT64)),
+    function("RANGE_BUCKET", &[req(ANY), req(ARRAY)], Scalar(INT64)),
+    function(
+        "ROUND",
+        &[req(NUMBER), opt(INT64), opt(STRING)],
+        Fractional(0),
+    ),
+    function("SAFE_ADD", &[req(NUMBER), req(NUMBER)], Common(0)),
+    function("SAFE_DIVIDE", &[req(NUMBER), req(NUMBER)], Fractional(0)),
+    function("SAFE_MULTIPLY", &[req(NUMBER), req(NUMBER)], Common(0)),
+    function("SAFE_NEGATE", &[req(NUMBER)], Arg(0)),
+    function("SAFE_SUBTRACT", &[req(NUMBER), req(NUMBER)], Common(0)),
+    function("SEC", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("SECH", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("SIGN", &[req(NUMBER)], Arg(0)),
+    function("SIN", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("SINH", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("SQRT", &[req(NUMBER)], Fractional(0)),
+    function("TAN", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("TANH", &[req(NUMBER)], Scalar(FLOAT64)),
+    function("TRUNC", &[req(NUMBER), opt(INT64)], Fractional(0)),
+    // string functions
+    function("ASCII", &[req(STRINGS)], Scalar(INT64)),
+    function("BYTE_LENGTH", &[req(STRINGS)], Scalar(INT64)),
+    function("CHARACTER_LENGTH", &[req(STRING)], Scalar(INT64)),
+    function("CHAR_LENGTH", &[req(STRING)], Scalar(INT64)),
+    function("CHR", &[req(INT64)], Scalar(STRING)),
+    function("CODE_POINTS_TO_BYTES", &[req(ARRAY)], Scalar(BYTES)),
+    function("CODE_POINTS_TO_STRING", &[req(ARRAY)], Scalar(STRING)),
+    function("COLLATE", &[req(STRING), req(STRING)], Scalar(STRING)),
+    function("CONCAT", &[req(STRINGS), rep(STRINGS)], Arg(0)),
+    function(
+        "CONTAINS_SUBSTR",
+        &[req(ANY), req(STRING), opt(STRING)],
+        Scalar(BOOL),
+    ),
+    function(
+        "EDIT_DISTANCE",
+        &[req(STRINGS), req(STRINGS), opt(INT64)],
+        Scalar(INT64),
+    ),
+    function("ENDS_WITH", &[req(STRINGS), req(STRINGS)], Scalar(BOOL)),
+    function("FORMAT", &[req(STRING), rep(ANY)], Scalar(STRING)),
+    function("FROM_BASE32", &[req(STRING)], Scalar(BYTES)),
+    function("FROM_BASE64", &[req(STRING)], Scalar(BYTES)),
+    function("FROM_HEX", &[req(STRING)], Scalar(BYTES)),
+    function("INITCAP", &[req(STRING), opt(STRING)], Scalar(STRING)),
+    function(
+        "INSTR",
+        &[req(STRINGS), req(STRINGS), opt(INT64), opt(INT64)],
+        Scalar(INT64),
+    ),
+    function("LEFT", &[req(STRINGS), req(INT64)], Arg(0)),
+    function("LENGTH", &[req(STRINGS)], Scalar(INT64)),
+    function("LOWER", &[req(STRINGS)], Arg(0)),
+    function("LPAD", &[req(STRINGS), req(INT64), opt(STRINGS)], Arg(0)),
+    function("LTRIM", &[req(STRINGS), opt(STRINGS)], Arg(0)),
+    function("NORMALIZE", &[req(STRING), opt(ANY)], Scalar(STRING)),
+    function(
+        "NORMALIZE_AND_CASEFOLD",
+        &[req(STRING), opt(ANY)],
+        Scalar(STRING),
+    ),
+    function("OCTET_LENGTH", &[req(STRINGS)], Scalar(INT64)),
+    function(
+        "REGEXP_CONTAINS",
+        &[req(STRINGS), req(STRINGS)],
+        Scalar(BOOL),
+    ),
+    function(
+        "REGEXP_EXTRACT",
+        &[req(STRINGS), req(STRINGS), opt(INT64), opt(INT64)],
+        Arg(0),
+    ),
+    function(
+        "REGEXP_EXTRACT_ALL",
+        &[req(STRINGS), req(STRINGS)],
+        ArrayOfArg(0),
+    ),
+    function(
+        "REGEXP_INSTR",
+        &[
+            req(STRINGS),
+            req(STRINGS),
+            opt(INT64),
+            opt(INT64),
+            opt(INT64),
+        ],
+        Scalar(INT64),
+    ),
+    function(
+        "REGEXP_REPLACE",
+        &[req(STRINGS), req(STRINGS), req(STRINGS)],
+        Arg(0),
+    ),
+    function(
+        "REGEXP_SUBSTR",
+        &[req(STRINGS), req(STRINGS), opt(INT64), opt(INT64)],
+        Arg(0),
+    ),
+    function("REPEAT", &[req(STRINGS), req(INT64)], Arg(0)),
+    function(
+        "REPLACE",
+        &[req(STRINGS), req(STRINGS), req(STRINGS)],
+        Arg(0),
+    ),
+    function("REVERSE", &[req(STRINGS)], Arg(0)),
+    function("RIGHT", &[req(STRINGS), req(INT64)], Arg(0)),
+    function("RPAD", &[req(STRINGS), req(INT64), opt(STRINGS)], Arg(0)),
+    function("RTRIM", &[req(STRINGS), opt(STRINGS)], Arg(0)),
+    function(
+        "SAFE_CONVERT_BYTES_TO_STRING",
+        &[req(BYTES)],
+        Scalar(STRING),
+    ),
+    function("SOUNDEX", &[req(STRING)], Scalar(STRING)),
+    function("SPLIT", &[req(STRINGS), opt(STRINGS)], ArrayOfArg(0)),
+    function("STARTS_WITH", &[req(STRINGS), req(STRINGS)], Scalar(BOOL)),
+    function("STRPOS", &[req(STRINGS), req(STRINGS)], Scalar(INT64)),
+    function("SUBSTR", &[req(STRINGS), req(INT64), opt(INT64)], Arg(0)),
+    function("SUBSTRING", &[req(STRINGS), req(INT64), opt(INT64)], Arg(0)),
+    function("TO_BASE32", &[req(BYTES)], Scalar(STRING)),
+    function("TO_BASE64", &[req(BYTES)], Scalar(STRING)),
+    function("TO_CODE_POINTS", &[req(STRINGS)], ArrayOf(INT64)),
+    function("TO_HEX", &[req(BYTES)], Scalar(STRING)),
+    function(
+        "TRANSLATE",
+        &[req(STRINGS), req(STRINGS), req(STRINGS)],
+        Arg(0),
+    ),
+    function("TRIM", &[req(STRINGS), opt(STRINGS)], Arg(0)),
+    function("UNICODE", &[req(STRING)], Scalar(INT64)),
+    function("UPPER", &[req(STRINGS)], Arg(0)),
+    // hash functions
+    function("FARM_FINGERPRINT", &[req(STRINGS)], Scalar(INT64)),
+    function("MD5", &[req(STRINGS)], Scalar(BYTES)),
+    function("SHA1", &[req(STRINGS)], Scalar(BYTES)),
+    function("SHA256", &[req(STRINGS)], Scalar(BYTES)),
+    function("SHA512", &[req(STRINGS)], Scalar(BYTES)),
+    // JSON functions (some of them take named arguments, e.g. `wide_number_mode => 'round'`)
+    function("JSON_ARRAY", &[rep(ANY)], Scalar(JSON)),
+    function(
+        "JSON_ARRAY_APPEND",
+        &[req(JSON), req(STRING), req(ANY), rep(ANY)],
+        Scalar(JSON),
+    ),
+    function(
+        "JSON_ARRAY_INSERT",
+        &[req(JSON), req(STRING), req(ANY), rep(ANY)],
+        Scalar(JSON),
+    ),
+    function("JSON_EXTRACT", &[req(JSONS), opt(STRING)], Arg(0)),
+    function(
+        "JSON_EXTRACT_ARRAY",
+        &[req(JSONS), opt(STRING)],
+        ArrayOfArg(0),
+    ),
+    function(
+        "JSON_EXTRACT_SCALAR",
+        &[req(JSONS), opt(STRING)],
+        Scalar(STRING),
+    ),
+    function(
+        "JSON_EXTRACT_STRING_ARRAY",
+        &[req(JSONS), opt(STRING)],
+        ArrayOf(STRING),
+    ),
+    function(
+        "JSON_KEYS",
+        &[req(JSON), opt(INT64), opt(STRING)],
+        ArrayOf(STRING),
+    ),
+    function("JSON_OBJECT", &[rep(ANY)], Scalar(JSON)),
+    function("JSON_QUERY", &[req(JSONS), req(STRING)], Arg(0)),
+    function(
+        "JSON_QUERY_ARRAY",
+        &[req(JSONS), opt(STRING)],
+        ArrayOfArg(0),
+    ),
+    function(
+        "JSON_REMOVE",
+        &[req(JSON), req(STRING), rep(STRING)],
+        Scalar(JSON),
+    ),
+    function(
+        "JSON_SET",
+        &[req(JSON), req(STRING), req(ANY), rep(ANY)],
+        Scalar(JSON),
+    ),
+    function("JSON_STRIP_NULLS", &[req(JSON), rep(ANY)], Scalar(JSON)),
+    function("JSON_TYPE", &[req(JSON)], Scalar(STRING)),
+    function("JSON_VALUE", &[req(JSONS), opt(STRING)], Scalar(STRING)),
+    function(
+        "JSON_VALUE_ARRAY",
+        &[req(JSONS), opt(STRING)],
+        ArrayOf(STRING),
+    ),
+    function("PARSE_JSON", &[req(STRING), opt(STRING)], Scalar(JSON)),
+    function("TO_JSON", &[req(ANY), opt(BOOL)], Scalar(JSON)),
+    function("TO_JSON_STRING", &[req(ANY), opt(BOOL)], Scalar(STRING)),
+    // array functions
+    function("ARRAY", &[req(ANY)], ArrayOfArg(0)).unsafe_(),
+    function("ARRAY_CONCAT", &[req(ARRAY), rep(ARRAY)], Common(0)),
+    function("ARRAY_FIRST", &[req(ARRAY)], ElementOf(0)),
+    function("ARRAY_INCLUDES", &[req(ARRAY), req(ANY)], Scalar(BOOL)),
+    function(
+        "ARRAY_INCLUDES_ALL",
+        &[req(ARRAY), req(ARRAY)],
+        Scalar(BOOL),
+    ),
+    function(
+        "ARRAY_INCLUDES_ANY",
+        &[req(ARRAY), req(ARRAY)],
+        Scalar(BOOL),
+    ),
+    function("ARRAY_LAST", &[req(ARRAY)], ElementOf(0)),
+    function("ARRAY_LENGTH", &[req(ARRAY)], Scalar(INT64)),
+    function("ARRAY_MAX", &[req(ARRAY)], ElementOf(0)),
+    function("ARRAY_MIN", &[req(ARRAY)], ElementOf(0)),
+    function("ARRAY_REVERSE", &[req(ARRAY)], Arg(0)),
+    function("ARRAY_SLICE", &[req(ARRAY), req(INT64), req(INT64)], Arg(0)),
+    function(
+        "ARRAY_TO_STRING",
+        &[req(ARRAY), req(STRINGS), opt(STRINGS)],
+        Scalar(STRING),
+    ),
+    function(
+        "GENERATE_ARRAY",
+        &[req(NUMBER), req(NUMBER), opt(NUMBER)],
+        ArrayOfArg(0),
+    ),
+    function(
+        "GENERATE_DATE_ARRAY",
+        &[req(DATE), req(DATE), opt(INTERVAL)],
+        ArrayOf(DATE),
+    ),
+    function(
+        "GENERATE_TIMESTAMP_ARRAY",
+        &[req(TIMESTAMP), req(TIMESTAMP), req(INTERVAL)],
+        ArrayOf(TIMESTAMP),
+    ),
+    // range functions (RANGE<T> is not modeled)
+    function(
+        "GENERATE_RANGE_ARRAY",
+        &[req(RANGE), req(INTERVAL), opt(BOOL)],
+        Other,
+    ),
+    function("RANGE", &[req(ANY), req(ANY)], Other),
+    function("RANGE_CONTAINS", &[req(RANGE), req(ANY)], Scalar(BOOL)),
+    function("RANGE_END", &[req(RANGE)], Other),
+    function("RANGE_INTERSECT", &[req(RANGE), req(RANGE)], Other),
+    function("RANGE_OVERLAPS", &[req(RANGE), req(RANGE)], Scalar(BOOL)),
+    function("RANGE_START", &[req(RANGE)], Other),
+    // date and time functions
+    function("CURRENT_DATE", &[opt(STRING)], Scalar(DATE)),
+    function("CURRENT_DATETIME", &[opt(STRING)], Scalar(DATETIME)),
+    function("CURRENT_TIME", &[opt(STRING)], Scalar(TIME)),
+    function("CURRENT_TIMESTAMP", &[], Scalar(TIMESTAMP)),
+    // DATE(timestamp, [time_zone]), DATE(year, month, day), ...
+    function("DATE", &[req(ANY), opt(ANY), opt(ANY)], Scalar(DATE)),
+    function(
+        "DATETIME",
+        &[
+            req(ANY),
+            opt(ANY),
+            opt(INT64),
+            opt(INT64),
+            opt(INT64),
+            opt(INT64),
+        ],
+        Scalar(DATETIME),
+    ),
+    function("DATETIME_ADD", &[req(DATETIME), req(INTERVAL)], Arg(0)),
+    function(
+        "DATETIME_BUCKET",
+        &[req(DATETIME), req(INTERVAL), opt(DATETIME)],
+        Arg(0),
+    ),
+    function(
+        "DATETIME_DIFF",
+        &[req(DATETIME), req(DATETIME), req(PART)],
+        Scalar(INT64),
+    ),
+    function("DATETIME_SUB", &[req(DATETIME), req(INTERVAL)], Arg(0)),
+    function("DATETIME_TRUNC", &[req(DATETIME), req(PART)], Arg(0)),
+    function("DATE_ADD", &[req(DATE), req(INTERVAL)], Arg(0)),
+    function(
+        "DATE_BUCKET",
+        &[req(DATE), req(INTERVAL), opt(DATE)],
+        Arg(0),
+    ),
+    function(
+        "DATE_DIFF",
+        &[req(DATE), req(DATE), req(PART)],
+        Scalar(INT64),
+    ),
+    function("DATE_FROM_UNIX_DATE", &[req(INT64)], Scalar(DATE)),
+    function("DATE_SUB", &[req(DATE), req(INTERVAL)], Arg(0)),
+    function("DATE_TRUNC", &[req(DATE), req(PART), opt(STRING)], Arg(0)),
+    function("EXTRACT", &[req(ANY)], Extract),
+    function("FORMAT_DATE", &[req(STRING), req(DATE)], Scalar(STRING)),
+    function(
+        "FORMAT_DATETIME",
+        &[req(STRING), req(DATETIME)],
+        Scalar(STRING),
+    ),
+    function("FORMAT_TIME", &[req(STRING), req(TIME)], Scalar(STRING)),
+    function(
+        "FORMAT_TIMESTAMP",
+        &[req(STRING), req(TIMESTAMP), opt(STRING)],
+        Scalar(STRING),
+    ),
+    function("JUSTIFY_DAYS", &[req(INTERVAL)], Scalar(INTERVAL)),
+    function("JUSTIFY_HOURS", &[req(INTERVAL)], Scalar(INTERVAL)),
+    function("JUSTIFY_INTERVAL", &[req(INTERVAL)], Scalar(INTERVAL)),
+    function("LAST_DAY", &[req(DATE), opt(PART)], Scalar(DATE)),
+    // MAKE_INTERVAL(year => 1, ...)
+    function(
+        "MAKE_INTERVAL",
+        &[
+            opt(INT64),
+            opt(INT64),
+            opt(INT64),
+            opt(INT64),
+            opt(INT64),
+            opt(INT64),
+        ],
+        Scalar(INTERVAL),
+    ),
+    function("PARSE_DATE", &[req(STRING), req(STRING)], Scalar(DATE)),
+    function(
+        "PARSE_DATETIME",
+        &[req(STRING), req(STRING)],
+        Scalar(DATETIME),
+    ),
+    function("PARSE_TIME", &[req(STRING), req(STRING)], Scalar(TIME)),
+    function(
+        "PARSE_TIMESTAMP",
+        &[req(STRING), req(STRING), opt(STRING)],
+        Scalar(TIMESTAMP),
+    ),
+    function("TIME", &[req(ANY), opt(ANY), opt(INT64)], Scalar(TIME)),
+    function("TIMESTAMP", &[req(ANY), opt(STRING)], Scalar(TIMESTAMP)),
+    function("TIMESTAMP_ADD", &[req(TIMESTAMP), req(INTERVAL)], Arg(0)),
+    function(
+        "TIMESTAMP_BUCKET",
+        &[req(TIMESTAMP), req(INTERVAL), opt(TIMESTAMP)],
+        Arg(0),
+    ),
+    function(
+        "TIMESTAMP_DIFF",
+        &[req(TIMESTAMP), req(TIMESTAMP), req(PART)],
+        Scalar(INT64),
+    ),
+    function("TIMESTAMP_MICROS", &[req(INT64)], Scalar(TIMESTAMP)),
+    function("TIMESTAMP_MILLIS", &[req(INT64)], Scalar(TIMESTAMP)),
+    function("TIMESTAMP_SECONDS", &[req(INT64)], Scalar(TIMESTAMP)),
+    function("TIMESTAMP_SUB", &[req(TIMESTAMP), req(INTERVAL)], Arg(0)),
+    function(
+        "TIMESTAMP_TRUNC",
+        &[req(TIMESTAMP), req(PART), opt(STRING)],
+        Arg(0),
+    ),
+    function("TIME_ADD", &[req(TIME), req(INTERVAL)], Arg(0)),
+    function(
+        "TIME_DIFF",
+        &[req(TIME), req(TIME), req(PART)],
+        Scalar(INT64),
+    ),
+    function("TIME_SUB", &[req(TIME), req(INTERVAL)], Arg(0)),
+    function("TIME_TRUNC", &[req(TIME), req(PART)], Arg(0)),
+    function("UNIX_DATE", &[req(DATE)], Scalar(INT64)),
+    function("UNIX_MICROS", &[req(TIMESTAMP)], Scalar(INT64)),
+    function("UNIX_MILLIS", &[req(TIMESTAMP)], Scalar(INT64)),
+    function("UNIX_SECONDS", &[req(TIMESTAMP)], Scalar(INT64)),
+    // geography functions
+    function(
+        "ST_ANGLE",
+        &[req(GEOGRAPHY), req(GEOGRAPHY), req(GEOGRAPHY)],
+        Scalar(FLOAT64),
+    ),
+    function("ST_AREA", &[req(GEOGRAPHY), opt(BOOL)], Scalar(FLOAT64)),
+    function("ST_ASBINARY", &[req(GEOGRAPHY)], Scalar(BYTES)),
+    function("ST_ASGEOJSON", &[req(GEOGRAPHY)], Scalar(STRING)),
+    function("ST_ASTEXT", &[req(GEOGRAPHY)], Scalar(STRING)),
+    function(
+        "ST_AZIMUTH",
+        &[req(GEOGRAPHY), req(GEOGRAPHY)],
+        Scalar(FLOAT64),
+    ),
+    function("ST_BOUNDARY", &[req(GEOGRAPHY)], Scalar(GEOGRAPHY)),
+    function("ST_BOUNDINGBOX", &[req(GEOGRAPHY)], Other),
+    function(
+        "ST_BUFFER",
+        &[req(GEOGRAPHY), req(NUMBER), rep(ANY)],
+        Scalar(GEOGRAPHY),
+    ),
+    function("ST_CENTROID", &[req(GEOGRAPHY)], Scalar(GEOGRAPHY)),
+    function("ST_CENTROID_AGG", &[req(GEOGRAPHY)], Scalar(GEOGRAPHY))
+        .aggregate()
+        .not_analytic(),
+    function(
+        "ST_CLOSESTPOINT",
+        &[req(GEOGRAPHY), req(GEOGRAPHY), opt(BOOL)],
+        Scalar(GEOGRAPHY),
+    ),
+    function(
+        "ST_CONTAINS",
+        &[req(GEOGRAPHY), req(GEOGRAPHY)],
+        Scalar(BOOL),
+    ),
+    function("ST_CONVEXHULL", &[req(GEOGRAPHY)], Scalar(GEOGRAPHY)),
+    function(
+        "ST_COVEREDBY",
+        &[req(GEOGRAPHY), req(GEOGRAPHY)],
+        Scalar(BOOL),
+    ),
+    function("ST_COVERS", &[req(GEOGRAPHY), req(GEOGRAPHY)], Scalar(BOOL)),
+    function(
+        "ST_DIFFERENCE",
+        &[req(GEOGRAPHY), req(GEOGRAPHY)],
+        Scalar(GEOGRAPHY),
+    ),
+    function("ST_DIMENSION", &[req(GEOGRAPHY)], Scalar(INT64)),
+    function(
+        "ST_DISJOINT",
+        &[req(GEOGRAPHY), req(GEOGRAPHY)],
+        Scalar(BOOL),
+    ),
+    function(
+        "ST_DISTANCE",
+        &[req(GEOGRAPHY), req(GEOGRAPHY), opt(BOOL)],
+        Scalar(FLOAT64),
+    ),
+    function("ST_DUMP", &[req(GEOGRAPHY), opt(INT64)], ArrayOf(GEOGRAPHY)),
+    function(
+        "ST_DWITHIN",
+        &[req(GEOGRAPHY), req(GEOGRAPHY), req(NUMBER), opt(BOOL)],
+        Scalar(BOOL),
+    ),
+    function("ST_ENDPOINT", &[req(GEOGRAPHY)], Scalar(GEOGRAPHY)),
+    function("ST_EQUALS", &[req(GEOGRAPHY), req(GEOGRAPHY)], Scalar(BOOL)),
+    function("ST_EXTENT", &[req(GEOGRAPHY)], Other)
+        .aggregate()
+        .not_analytic(),
+    function("ST_EXTERIORRING", &[req(GEOGRAPHY)], Scalar(GEOGRAPHY)),
+    function("ST_GEOGFROM", &[req(ANY)], Scalar(GEOGRAPHY)),
+    function(
+        "ST_GEOGFROMGEOJSON",
+        &[req(STRING), rep(ANY)],
+        Scalar(GEOGRAPHY),
+    ),
+    function(
+        "ST_GEOGFROMTEXT",
+        &[req(STRING), rep(ANY)],
+        Scalar(GEOGRAPHY),
+    ),
+    function(
+        "ST_GEOGFROMWKB",
+        &[req(STRINGS), rep(ANY)],
+        Scalar(GEOGRAPHY),
+    ),
+    function(
+        "ST_GEOGPOINT",
+        &[req(NUMBER), req(NUMBER)],
+        Scalar(GEOGRAPHY),
+    ),
+    function("ST_GEOGPOINTFROMGEOHASH", &[req(STRING)], Scalar(GEOGRAPHY)),
+    function("ST_GEOHASH", &[req(GEOGRAPHY), opt(INT64)], Scalar(STRING)),
+    function(
+        "ST_INTERSECTION",
+        &[req(GEOGRAPHY), req(GEOGRAPHY)],
+        Scalar(GEOGRAPHY),
+    ),
+    function(
+        "ST_INTERSECTS",
+        &[req(GEOGRAPHY), req(GEOGRAPHY)],
+        Scalar(BOOL),
+    ),
+    function(
+        "ST_INTERSECTSBOX",
+        &[
+            req(GEOGRAPHY),
+            req(NUMBER),
+            req(NUMBER),
+            req(NUMBER),
+            req(NUMBER),
+        ],
+        Scalar(BOOL),
+    ),
+    function("ST_ISCLOSED", &[req(GEOGRAPHY)], Scalar(BOOL)),
+    function("ST_ISCOLLECTION", &[req(GEOGRAPHY)], Scalar(BOOL)),
+    function("ST_ISEMPTY", &[req(GEOGRAPHY)], Scalar(BOOL)),
+    function("ST_ISRING", &[req(GEOGRAPHY)], Scalar(BOOL)),
+    function("ST_LENGTH", &[req(GEOGRAPHY), opt(BOOL)], Scalar(FLOAT64)),
+    function(
+        "ST_MAKELINE",
+        &[req(ANY), opt(GEOGRAPHY)],
+        Scalar(GEOGRAPHY),
+    ),
+    function(
+        "ST_MAKEPOLYGON",
+        &[req(GEOGRAPHY), opt(ARRAY)],
+        Scalar(GEOGRAPHY),
+    ),
+    function(
+        "ST_MAXDISTANCE",
+        &[req(GEOGRAPHY), req(GEOGRAPHY), opt(BOOL)],
+        Scalar(FLOAT64),
+    ),
+    function("ST_NPOINTS", &[req(GEOGRAPHY)], Scalar(INT64)),
+    function("ST_NUMGEOMETRIES", &[req(GEOGRAPHY)], Scalar(INT64)),
+    function("ST_NUMPOINTS", &[req(GEOGRAPHY)], Scalar(INT64)),
+    function(
+        "ST_PERIMETER",
+        &[req(GEOGRAPHY), opt(BOOL)],
+        Scalar(FLOAT64),
+    ),
+    function(
+        "ST_POINTN",
+        &[req(GEOGRAPHY), req(INT64)],
+        Scalar(GEOGRAPHY),
+    ),
+    function(
+        "ST_SIMPLIFY",
+        &[req(GEOGRAPHY), req(NUMBER)],
+        Scalar(GEOGRAPHY),
+    ),
+    function(
+        "ST_SNAPTOGRID",
+        &[req(GEOGRAPHY), req(NUMBER)],
+        Scalar(GEOGRAPHY),
+    ),
+    function("ST_STARTPOINT", &[req(GEOGRAPHY)], Scalar(GEOGRAPHY)),
+    function(
+        "ST_TOUCHES",
+        &[req(GEOGRAPHY), req(GEOGRAPHY)],
+        Scalar(BOOL),
+    ),
+    function("ST_UNION", &[req(ANY), opt(GEOGRAPHY)], Scalar(GEOGRAPHY)),
+    function("ST_UNION_AGG", &[req(GEOGRAPHY)], Scalar(GEOGRAPHY))
+        .aggregate()
+        .not_analytic(),
+    function("ST_WITHIN", &[req(GEOGRAPHY), req(GEOGRAPHY)], Scalar(BOOL)),
+    function("ST_X", &[req(GEOGRAPHY)], Scalar(FLOAT64)),
+    function("ST_Y", &[req(GEOGRAPHY)], Scalar(FLOAT64)),
+    // net functions
+    function("NET.HOST", &[req(STRING)], Scalar(STRING)),
+    function("NET.IPV4_FROM_INT64", &[req(INT64)], Scalar(BYTES)),
+    function("NET.IPV4_TO_INT64", &[req(BYTES)], Scalar(INT64)),
+    function("NET.IP_FROM_STRING", &[req(STRING)], Scalar(BYTES)),
+    function("NET.IP_NET_MASK", &[req(INT64), req(INT64)], Scalar(BYTES)),
+    function("NET.IP_TO_STRING", &[req(BYTES)], Scalar(STRING)),
+    function("NET.IP_TRUNC", &[req(BYTES), req(INT64)], Scalar(BYTES)),
+    function("NET.PUBLIC_SUFFIX", &[req(STRING)], Scalar(STRING)),
+    function("NET.REG_DOMAIN", &[req(STRING)], Scalar(STRING)),
+    function("NET.SAFE_IP_FROM_STRING", &[req(STRING)], Scalar(BYTES)),
+    // others
+    function("ERROR", &[req(STRING)], Other),
+    function("EXISTS", &[req(ANY)], Scalar(BOOL)).unsafe_(),
+    function("GENERATE_UUID", &[], Scalar(STRING)),
+    function("SESSION_USER", &[], Scalar(STRING)),
+];
+
+pub fn builtin() -> &'static [Function] {
+    FUNCTIONS
+}
+
+// case insensitive, `name` does not have `SAFE.` prefix
+pub fn find(name: &str) -> Option<&'static Function> {
+    FUNCTIONS.iter().find(|f| f.name.eq_ignore_ascii_case(name))
+}
+
+fn scalar(name: &str) -> Type {
+    Type::from_name(name).unwrap_or(Type::Unknown)
+}
+
+// Unknown if the function is not built-in
+pub(crate) fn return_type(name: &str, args: &[(&Node, Type)]) -> Type {
+    let function = match find(name) {
+        Some(function) => function,
+        None => return Type::Unknown,
+    };
+    let arg = |i: usize| args.get(i).map_or(Type::Unknown, |(_, ty)| ty.clone());
+    match function.returns {
+        Scalar(ty) => scalar(ty),
+        ArrayOf(ty) => Type::Array(Box::new(scalar(ty))),
+        Arg(i) => arg(i),
+        ArrayOfArg(i) => Type::Array(Box::new(arg(i))),
+        ElementOf(i) => match arg(i) {
+            Type::Array(element) => *element,
+            _ => Type::Unknown,
+        },
+        Common(i) => infer::common_type(args.get(i..).unwrap_or_default()),
+        Fractional(i) => match arg(i) {
+            Type::Int64 => Type::Float64,
+            ty => ty,
+        },
+        // EXTRACT(DATE FROM ts) returns DATE
+        Extract => {
+            let part = args
+                .first()
+                .and_then(|(node, _)| node.get_node("extract_datepart"))
+                .and_then(|part| part.token.as_ref());
+            match part.map(|t| t.literal.to_uppercase()).as_deref() {
+                Some("DATE") => Type::Date,
+                Some("DATETIME") => Type::Datetime,
+                Some("TIME") => Type::Time,
+                _ => Type::Int64,
+            }
+        }
+        Other => Type::Unknown,
+    }
+}
+
+// names of user-defined functions (upper case)
+fn udfs(node: &Node, names: &mut Vec<String>) {
+    if node.node_type == NodeType::CreateFunctionStatement {
+        if let Some(name) = node.get_node("ident").and_then(names::table_name) {
+            names.push(name.to_uppercase());
+        }
+    }
+    for (_, child) in node.children_in_source_order() {
+        udfs(child, names);
+    }
+}
+
+// `NET.HOST` is built-in while `dataset.f` is a user-defined function
+fn is_builtin_namespace(name: &str) -> bool {
+    match name.rsplit_once('.') {
+        Some((namespace, _)) => FUNCTIONS
+            .iter()
+            .any(|f| f.name.rsplit_once('.').is_some_and(|(n, _)| n == namespace)),
+        None => true,
+    }
+}
+
+// unknown functions, the number of arguments, OVER clauses, DISTINCT, IGNORE NULLS, ...
+// the types of arguments are not checked and user-defined functions are not validated
+pub fn validate(stmts: &[Node]) -> Vec<ValidationError> {
+    let mut names = Vec::new();
+    for stmt in stmts {
+        udfs(stmt, &mut names);
+    }
+    let mut errors = Vec::new();
+    for stmt in stmts {
+        validate_node(stmt, &names, &mut errors);
+    }
+    errors
+}
+
+fn validate_node(node: &Node, udfs: &[String], errors: &mut Vec<ValidationError>) {
+    if node.node_type == NodeType::CallingFunction {
+        validate_call(node, udfs, errors);
+    }
+    for (key, child) in node.children_in_source_order() {
+        // `JOIN ... USING (col)` is parsed as a function call
+        if node.node_type == NodeType::JoinOperator && key == "using" {
+            continue;
+        }
+        validate_node(child, udfs, errors);
+    }
+}
+
+fn validate_call(node: &Node, udfs: &[String], errors: &mut Vec<ValidationError>) {
+    let func = match node.get_node("func") {
+        Some(func) => func,
+        None => return,
+    };
+    let (name, span) = match (names::table_name(func), func.span()) {
+        (Some(name), Some(span)) => (name, span),
+        _ => return,
+    };
+    let (safe, name) = match name.split_once('.') {
+        Some((prefix, rest)) if prefix.eq_ignore_ascii_case("SAFE") => (true, rest.to_string()),
+        _ => (false, name),
+    };
+    // WEEK(MONDAY) is a date part
+    if name.eq_ignore_ascii_case("WEEK") || udfs.contains(&name.to_uppercase()) {
+        return;
+    }
+    let mut error = |message: String, span: Span| errors.push(ValidationError { message, span });
+    let function = match find(&name) {
+        Some(function) => function,
+        None => {
+            if is_builtin_namespace(&name.to_uppercase()) {
+                error(format!("Function not found: {}", name), span);
+            }
+            return;
+        }
+    };
+    let name = function.name;
+    if safe && !function.safe {
+        error(
+            format!("Function {} does not support SAFE error mode", name),
+            span.clone(),
+        );
+    }
+    let count = node.get_node_vec("args").map_or(0, |args| args.len());
+    if count < function.min_args() || function.max_args().is_some_and(|max| max < count) {
+        let message = format!(
+            "Number of arguments does not match for function {}. Supported signature: {}",
+            name, function
+        );
+        error(message, span.clone());
+    }
+    // the keywords which are not supported
+    if let Some(distinct) = node.get_node("distinct") {
+        if !function.distinct {
+            error(
+                format!("Function {} does not support DISTINCT", name),
+                names::span_between(distinct, distinct),
+            );
+        }
+    }
+    if let Some(nulls) = node.get_node_vec("ignore_nulls") {
+        if !function.ignore_nulls && !nulls.is_empty() {
+            let (first, last) = (&nulls[0], &nulls[nulls.len() - 1]);
+            let keywords: Vec<String> = nulls.iter().map(keyword).collect();
+            let message = format!("Function {} does not support {}", name, keywords.join(" "));
+            error(message, names::span_between(first, last));
+        }
+    }
+    if let Some(orderby) = node.get_node("orderby") {
+        if !function.order_by {
+            error(
+                format!("Function {} does not support ORDER BY", name),
+                names::span_between(orderby, orderby.get_node("by").unwrap_or(orderby)),
+            );
+        }
+    }
+    if let Some(limit) = node.get_node("limit") {
+        if !function.limit {
+            error(
+                format!("Function {} does not support LIMIT", name),
+                names::span_between(limit, limit),
+            );
+        }
+    }
+    match node.get_node("over") {
+        Some(over) if !function.analytic => error(
+            format!("Function {} does not support an OVER clause", name),
+            names::span_between(over, over),
+        ),
+        None if function.kind == Kind::Window => error(
+            format!(
+                "Analytic function {} cannot be called without an OVER clause",
+                name
+            ),
+            span,
+        ),
+        _ => (),
+    }
+}
+
+fn keyword(node: &Node) -> String {
+    node.token
+        .as_ref()
+        .map_or(String::new(), |t| t.literal.to_uppercase())
+}
@@ -0,0 +1,112 @@
+use super::*;
+use crate::parser::Parser;
+
+// one line per error: `line:column message`
+fn test_validate(code: &str, expected: &[&str]) {
+    let stmts = Parser::new(code.to_string()).unwrap().parse_code().unwrap();
+    let result: Vec<String> = validate(&stmts)
+        .iter()
+        .map(|e| format!("{}:{} {}", e.span.line, e.span.column, e.message))
+        .collect();
+    assert_eq!(expected, result, "{}", code);
+}
+
+#[test]
+fn test_find() {
+    let substr = find("substr").unwrap();
+    assert_eq!("SUBSTR", substr.name);
+    assert_eq!(Kind::Scalar, substr.kind);
+    assert_eq!((2, Some(3)), (substr.min_args(), substr.max_args()));
+    assert_eq!("SUBSTR(STRING|BYTES, INT64, [INT64])", substr.to_string());
+    let concat = find("CONCAT").unwrap();
+    assert_eq!((1, None), (concat.min_args(), concat.max_args()));
+    assert_eq!(
+        "CONCAT(STRING|BYTES, [STRING|BYTES, ...])",
+        concat.to_string()
+    );
+    let array_agg = find("ARRAY_AGG").unwrap();
+    assert_eq!(Kind::Aggregate, array_agg.kind);
+    assert!(array_agg.analytic && array_agg.distinct && array_agg.order_by && !array_agg.safe);
+    assert_eq!(Kind::Window, find("row_number").unwrap().kind);
+    assert!(find("net.host").is_some());
+    assert!(find("UNKNOWN_FUNCTION").is_none());
+    // every name is unique and upper case
+    for (i, f) in builtin().iter().enumerate() {
+        assert_eq!(f.name.to_uppercase(), f.name);
+        assert!(
+            builtin()[..i].iter().all(|g| g.name != f.name),
+            "{}",
+            f.name
+        );
+    }
+}
+
+#[test]
+fn test_validate_names() {
+    test_validate(
+        "\
+CREATE TEMP FUNCTION f(x INT64) AS (x);
+SELECT f(1), ds.g(1), F(2), foo(1), NET.HOSTS(s), NET.HOST(s), DATE_TRUNC(d, WEEK(MONDAY))
+FROM t JOIN u USING(col)",
+        &[
+            "2:29 Function not found: foo",
+            "2:37 Function not found: NET.HOSTS",
+        ],
+    );
+    // special syntax
+    test_validate(
+        "SELECT COUNT(*), IF(a, 1, 2), EXTRACT(DAY FROM d), CAST(a AS INT64), ARRAY(SELECT 1), EXISTS(SELECT 1), arr[OFFSET(0)]",
+        &[],
+    );
+    test_validate(
+        "SELECT ARRAY_INCLUDES(arr, 1), ARRAY_INCLUDES_ANY(arr, [1, 2]), RANGE(DATE '2020-01-01', NULL), RANGE_START(r)",
+        &[],
+    );
+}
+
+#[test]
+fn test_validate_arguments() {
+    test_validate(
+        "SELECT SUBSTR(s), SUBSTR(s, 1), COUNT(), CONCAT(a, b, c), CURRENT_DATE(), RAND(1)",
+        &[
+            "1:8 Number of arguments does not match for function SUBSTR. Supported signature: SUBSTR(STRING|BYTES, INT64, [INT64])",
+            "1:33 Number of arguments does not match for function COUNT. Supported signature: COUNT(ANY)",
+            "1:75 Number of arguments does not match for function RAND. Supported signature: RAND()",
+        ],
+    );
+    // the types are informational only
+    test_validate("SELECT SUBSTR(1, 'a'), ARRAY_LENGTH(1)", &[]);
+}
+
+#[test]
+fn test_validate_modifiers() {
+    test_validate(
+        "\
+SELECT
+  SAFE.SUBSTR(s, 1),
+  SAFE.SUM(x),
+  SUM(DISTINCT x),
+  ANY_VALUE(DISTINCT x),
+  STRING_AGG(x, ',' ORDER BY x LIMIT 1),
+  SUM(x ORDER BY x LIMIT 1),
+  FIRST_VALUE(x RESPECT NULLS) OVER (),
+  LAG(x IGNORE NULLS) OVER ()
+FROM t",
+        &[
+            "3:3 Function SUM does not support SAFE error mode",
+            "5:13 Function ANY_VALUE does not support DISTINCT",
+            "7:9 Function SUM does not support ORDER BY",
+            "7:20 Function SUM does not support LIMIT",
+            "9:9 Function LAG does not support IGNORE NULLS",
+        ],
+    );
+    // OVER clause
+    test_validate(
+        "SELECT ROW_NUMBER(), RANK() OVER (), SUM(x) OVER (), UPPER(s) OVER (), APPROX_COUNT_DISTINCT(x) OVER (w) FROM t",
+        &[
+            "1:8 Analytic function ROW_NUMBER cannot be called without an OVER clause",
+            "1:63 Function UPPER does not support an OVER clause",
+            "1:97 Function APPROX_COUNT_DISTINCT does not support an OVER clause",
+        ],
+    );
+}
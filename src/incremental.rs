@@ -0,0 +1,241 @@
+// incremental reparsing
+// top-level statements are parsed independently, so only the statements around the edit
+// are relexed and reparsed, and the others are reused (their positions are shifted)
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{walk_mut, Node, NodeType, VisitorMut};
+use crate::error::{ParseError, ParseResult};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::token::{Token, TokenKind};
+
+// `start..end` (byte offsets) of the old code is replaced with `text`
+#[derive(PartialEq, Debug, Clone)]
+pub struct TextEdit {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn new(start: usize, end: usize, text: &str) -> TextEdit {
+        TextEdit {
+            start,
+            end,
+            text: text.to_string(),
+        }
+    }
+    // panics if `start..end` is not a valid range of `code`
+    pub fn apply(&self, code: &str) -> String {
+        let mut res = String::with_capacity(code.len() + self.text.len());
+        res.push_str(&code[..self.start]);
+        res.push_str(&self.text);
+        res.push_str(&code[self.end..]);
+        res
+    }
+}
+
+// `code` and `stmts` are the old ones, the result is the same as `Parser::parse_code()`
+pub fn reparse(code: &str, stmts: Vec<Node>, edit: &TextEdit) -> ParseResult<Vec<Node>> {
+    Ok(Reparser::new(code, stmts, edit, false).reparse()?.0)
+}
+
+// the result is the same as `Parser::parse_code_with_recovery()`
+// NOTE `Error` nodes are always reparsed to collect their errors again
+pub fn reparse_with_recovery(
+    code: &str,
+    stmts: Vec<Node>,
+    edit: &TextEdit,
+) -> ParseResult<(Vec<Node>, Vec<ParseError>)> {
+    Reparser::new(code, stmts, edit, true).reparse()
+}
+
+struct Reparser {
+    code: String, // the new code
+    // a reused statement (None if it should be reparsed) and its end in the new code
+    segments: Vec<(Option<Node>, usize)>,
+    recover: bool,
+}
+
+impl Reparser {
+    fn new(code: &str, stmts: Vec<Node>, edit: &TextEdit, recover: bool) -> Reparser {
+        let new_code = edit.apply(code);
+        let mut shift = Shift::new(code, edit);
+        // comments inserted after `;` become the trailing comments of the statement
+        let line_start = code[..edit.start].rfind('\n').map_or(0, |i| i + 1);
+        let mut segments = Vec::new();
+        let mut start = 0;
+        for mut stmt in stmts {
+            // statements own the whitespace before them and EOF owns the rest of the code
+            let end = match (&stmt.node_type, stmt.span()) {
+                (NodeType::EOF, _) | (_, None) => code.len(),
+                (_, Some(span)) => span.end,
+            };
+            let touched = line_start <= end && start <= edit.end;
+            let new_end = if end < edit.start {
+                end
+            } else {
+                // NOTE the end of a touched statement except the last one is meaningless
+                (end.max(edit.end) as isize + shift.bytes) as usize
+            };
+            if touched || stmt.node_type == NodeType::Error {
+                segments.push((None, new_end));
+            } else {
+                if edit.end < start {
+                    walk_mut(&mut shift, &mut stmt);
+                }
+                segments.push((Some(stmt), new_end));
+            }
+            start = end;
+        }
+        Reparser {
+            code: new_code,
+            segments,
+            recover,
+        }
+    }
+    fn reparse(mut self) -> ParseResult<(Vec<Node>, Vec<ParseError>)> {
+        let mut stmts: Vec<Node> = Vec::new();
+        let mut errors = Vec::new();
+        let mut i = 0;
+        while i < self.segments.len() {
+            if let Some(stmt) = self.segments[i].0.take() {
+                stmts.push(stmt);
+                i += 1;
+                continue;
+            }
+            // a statement without `;` may be continued by the following one
+            while stmts
+                .last()
+                .is_some_and(|s| s.get_node("semicolon").is_none())
+            {
+                stmts.pop();
+            }
+            let mut j = i; // the last segment to be reparsed
+            loop {
+                while j + 1 < self.segments.len() && self.segments[j + 1].0.is_none() {
+                    j += 1;
+                }
+                let is_last = j + 1 == self.segments.len();
+                match self.parse_region(stmts.last(), self.segments[j].1) {
+                    Ok((mut new_stmts, new_errors)) => {
+                        let eof = new_stmts
+                            .pop()
+                            .unwrap_or_else(|| Node::empty(NodeType::EOF));
+                        // the last statement may be continued and comments may be trailing ones
+                        let is_complete = new_stmts.last().is_none_or(|s| {
+                            s.node_type != NodeType::Error && s.get_node("semicolon").is_some()
+                        }) && eof.get_node_vec("leading_comments").is_none();
+                        // the following statements may fix the error
+                        let reached_eof = new_errors
+                            .iter()
+                            .any(|e| e.token.as_ref().is_some_and(|t| t.kind == TokenKind::EOF));
+                        if is_last || (is_complete && !reached_eof) {
+                            if is_last {
+                                new_stmts.push(eof);
+                            }
+                            stmts.extend(new_stmts);
+                            errors.extend(new_errors);
+                            break;
+                        }
+                        j = if reached_eof {
+                            self.segments.len() - 1
+                        } else {
+                            j + 1
+                        };
+                    }
+                    Err(error) if is_last => return Err(error),
+                    // the edit may affect the rest of the code (e.g. unterminated string)
+                    Err(_) => j = self.segments.len() - 1,
+                }
+                for segment in &mut self.segments[i..=j] {
+                    segment.0 = None;
+                }
+            }
+            i = j + 1;
+        }
+        Ok((stmts, errors))
+    }
+    // parse the code between `prev` and `end`
+    fn parse_region(
+        &self,
+        prev: Option<&Node>,
+        end: usize,
+    ) -> ParseResult<(Vec<Node>, Vec<ParseError>)> {
+        let (line, column, start, start_utf16) = match prev.and_then(|s| s.span()) {
+            Some(span) => (span.end_line, span.end_column, span.end, span.end_utf16),
+            None => (1, 1, 0, 0),
+        };
+        let input = self.code[start..end].to_string();
+        let mut lexer = Lexer::with_position(input, line, column, start, start_utf16);
+        lexer.tokenize_code()?;
+        let mut parser = Parser::from_tokens(lexer.tokens);
+        if self.recover {
+            Ok(parser.parse_code_with_recovery())
+        } else {
+            Ok((parser.parse_code()?, Vec::new()))
+        }
+    }
+}
+
+// moves tokens after the edit
+struct Shift {
+    line: usize, // the line of the end of the edit (in the old code)
+    lines: isize,
+    columns: isize, // only for tokens on `line`
+    bytes: isize,
+    utf16: isize,
+}
+
+impl Shift {
+    fn new(code: &str, edit: &TextEdit) -> Shift {
+        let removed = &code[edit.start..edit.end];
+        let before = &code[..edit.end];
+        let old_column = before[before.rfind('\n').map_or(0, |i| i + 1)..]
+            .chars()
+            .count();
+        let new_column = match edit.text.rfind('\n') {
+            Some(i) => edit.text[i + 1..].chars().count(),
+            None => {
+                let before = &code[..edit.start];
+                before[before.rfind('\n').map_or(0, |i| i + 1)..]
+                    .chars()
+                    .count()
+                    + edit.text.chars().count()
+            }
+        };
+        Shift {
+            line: before.matches('\n').count() + 1,
+            lines: edit.text.matches('\n').count() as isize
+                - removed.matches('\n').count() as isize,
+            columns: new_column as isize - old_column as isize,
+            bytes: edit.text.len() as isize - removed.len() as isize,
+            utf16: edit.text.encode_utf16().count() as isize
+                - removed.encode_utf16().count() as isize,
+        }
+    }
+    fn token(&self, token: &mut Token) {
+        let add = |n: usize, d: isize| (n as isize + d) as usize;
+        if token.line == self.line {
+            token.column = add(token.column, self.columns);
+        }
+        if token.end_line == self.line {
+            token.end_column = add(token.end_column, self.columns);
+        }
+        token.line = add(token.line, self.lines);
+        token.end_line = add(token.end_line, self.lines);
+        token.start = add(token.start, self.bytes);
+        token.end = add(token.end, self.bytes);
+        token.start_utf16 = add(token.start_utf16, self.utf16);
+        token.end_utf16 = add(token.end_utf16, self.utf16);
+    }
+}
+
+impl VisitorMut for Shift {
+    fn visit_node(&mut self, node: &mut Node) {
+        if let Some(token) = &mut node.token {
+            self.token(token);
+        }
+    }
+}
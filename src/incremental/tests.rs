@@ -0,0 +1,99 @@
+use super::*;
+
+fn parse(code: &str) -> ParseResult<Vec<Node>> {
+    Parser::new(code.to_string())?.parse_code()
+}
+
+fn parse_with_recovery(code: &str) -> ParseResult<(Vec<Node>, Vec<ParseError>)> {
+    Ok(Parser::new(code.to_string())?.parse_code_with_recovery())
+}
+
+// the result should be the same as parsing the new code from scratch
+fn test_reparse(code: &str, edit: &TextEdit) {
+    let new_code = edit.apply(code);
+    let message = format!("{:?} -> {:?}", code, new_code);
+    if let Ok(stmts) = parse(code) {
+        assert_eq!(parse(&new_code), reparse(code, stmts, edit), "{}", message);
+    }
+    if let Ok((stmts, _)) = parse_with_recovery(code) {
+        assert_eq!(
+            parse_with_recovery(&new_code),
+            reparse_with_recovery(code, stmts, edit),
+            "{}",
+            message
+        );
+    }
+}
+
+const SCRIPT: &str = "\
+-- header
+DECLARE x INT64 DEFAULT 1; -- trailing
+SELECT 'あ😀' AS a, x FROM t WHERE b < 1;
+/* leading */ SELECT ARRAY<INT64>[1, 2]; SELECT 3
+UNION ALL SELECT 4;
+BEGIN
+  SELECT 5;
+END;
+SELECT 6
+-- EOF
+";
+
+#[test]
+fn test_reparse_every_position() {
+    let insertions = [
+        "",
+        " ",
+        "\n",
+        ";",
+        "x",
+        "'",
+        "\"",
+        "`",
+        "(",
+        "-- c\n",
+        "/*",
+        "*/",
+        "#",
+        "😀",
+        "SELECT 9;",
+    ];
+    let boundaries: Vec<usize> = (0..=SCRIPT.len())
+        .filter(|i| SCRIPT.is_char_boundary(*i))
+        .collect();
+    for (n, start) in boundaries.iter().enumerate() {
+        for text in insertions {
+            test_reparse(SCRIPT, &TextEdit::new(*start, *start, text));
+        }
+        // deletion and replacement
+        for end in boundaries.iter().skip(n + 1).take(3) {
+            test_reparse(SCRIPT, &TextEdit::new(*start, *end, ""));
+            test_reparse(SCRIPT, &TextEdit::new(*start, *end, "\n;"));
+        }
+    }
+}
+
+#[test]
+fn test_reparse_with_errors() {
+    let code = "SELECT 1; SELECT FROM; SELECT 2 SELECT 3; SELECT (; SELECT 4;";
+    for start in 0..=code.len() {
+        for text in ["", " ", ";", "x", ")", "'"] {
+            test_reparse(code, &TextEdit::new(start, start, text));
+        }
+    }
+    test_reparse(code, &TextEdit::new(0, code.len(), ""));
+    test_reparse("", &TextEdit::new(0, 0, "SELECT 1;"));
+}
+
+#[test]
+fn test_reparse_reuse() {
+    let code = "SELECT a FROM t;\nSELECT b FROM u;\nSELECT c FROM v;";
+    let stmts = parse(code).unwrap();
+    let exprs = |stmts: &[Node], i: usize| stmts[i].get_node_vec("exprs").unwrap().as_ptr();
+    let (first, last) = (exprs(&stmts, 0), exprs(&stmts, 2));
+    // `SELECT b FROM u` -> `SELECT bb, b2 FROM u`
+    let edit = TextEdit::new(24, 25, "bb,\n  b2");
+    let stmts = reparse(code, stmts, &edit).unwrap();
+    assert_eq!(parse(&edit.apply(code)).unwrap(), stmts);
+    assert_eq!(first, exprs(&stmts, 0));
+    assert_eq!(last, exprs(&stmts, 2));
+}
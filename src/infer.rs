@@ -0,0 +1,258 @@
+// type inference
+// expressions are annotated with their BigQuery types while names are resolved,
+// so the types of columns come from the catalog (`Type::Unknown` if they are not known)
+// (the traversal is shared with `resolve()`, which asks `Inference` for the types)
+#[cfg(test)]
+mod tests;
+
+use crate::catalog::{Catalog, Field, Type};
+use crate::cst::{Node, NodeType};
+use crate::names::implicit_names;
+use crate::resolve::{self, Analysis, Typing};
+use std::collections::HashMap;
+
+// keyed by the start of the token of the node
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Types {
+    pub(crate) exprs: HashMap<usize, Type>,
+    pub(crate) queries: HashMap<usize, Vec<Field>>, // anonymous columns have empty names
+}
+
+impl Types {
+    // None if `node` is not an expression (or NULL which can be any type)
+    pub fn type_of(&self, node: &Node) -> Option<&Type> {
+        self.exprs.get(&node.token.as_ref()?.start)
+    }
+    pub(crate) fn query(&self, node: &Node) -> Option<&Vec<Field>> {
+        match node.node_type {
+            NodeType::GroupedStatement => self.query(node.get_node("stmt")?),
+            _ => self.queries.get(&node.token.as_ref()?.start),
+        }
+    }
+    // the columns `SelectStatement`, `SetOperator` or `GroupedStatement` returns,
+    // or the columns of the table `CREATE TABLE` or `CREATE VIEW` creates
+    // None if the columns are unknown (e.g. `SELECT *` from unknown tables)
+    pub fn schema_of(&self, query: &Node) -> Option<Vec<Field>> {
+        Some(implicit_names(self.query(query)?))
+    }
+    // false if the expressions cannot be compared (e.g. INT64 and STRING)
+    // unknown types are comparable with any type
+    pub fn comparable(&self, left: &Node, right: &Node) -> bool {
+        let (l, r) = match (self.type_of(left), self.type_of(right)) {
+            (Some(l), Some(r)) => (l, r),
+            _ => return true,
+        };
+        // '2020-01-01' is coerced to DATE
+        let is_string = |n: &Node| n.node_type == NodeType::StringLiteral;
+        if is_string(left) && is_date_or_time(r) || is_string(right) && is_date_or_time(l) {
+            return true;
+        }
+        supertype(l, r).is_some()
+    }
+}
+
+// the types of the expressions in the statements
+pub fn infer(stmts: &[Node], catalog: &Catalog) -> Types {
+    let analysis = analyze(stmts, Some(catalog));
+    Types {
+        exprs: analysis.exprs,
+        queries: analysis.queries,
+    }
+}
+
+// names are resolved and types are inferred at the same time
+// because the types of columns depend on their bindings
+pub(crate) fn analyze(stmts: &[Node], catalog: Option<&Catalog>) -> Analysis {
+    resolve::analyze(stmts, catalog, &Inference)
+}
+
+struct Inference;
+
+impl Typing for Inference {
+    fn literal(&self, node: &Node) -> Type {
+        literal(node)
+    }
+    fn unary(&self, operator: &str, operand: Type) -> Type {
+        unary(operator, operand)
+    }
+    fn binary(&self, operator: &str, left: &Type, right: &Type) -> Type {
+        binary(operator, left, right)
+    }
+    fn common_type(&self, exprs: &[(&Node, Type)]) -> Type {
+        common_type(exprs)
+    }
+    fn function(&self, name: &str, args: &[(&Node, Type)]) -> Type {
+        function(name, args)
+    }
+    fn supertype(&self, a: &Type, b: &Type) -> Option<Type> {
+        supertype(a, b)
+    }
+}
+
+fn numeric_rank(ty: &Type) -> Option<usize> {
+    match ty {
+        Type::Int64 => Some(0),
+        Type::Numeric => Some(1),
+        Type::BigNumeric => Some(2),
+        Type::Float64 => Some(3),
+        _ => None,
+    }
+}
+
+fn is_date_or_time(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Date | Type::Datetime | Type::Time | Type::Timestamp
+    )
+}
+
+// the type which both types are coerced to (None if there is no such type)
+pub(crate) fn supertype(a: &Type, b: &Type) -> Option<Type> {
+    if a == b {
+        return Some(a.clone());
+    }
+    match (a, b) {
+        (Type::Unknown, _) | (_, Type::Unknown) => Some(Type::Unknown),
+        (Type::Date, Type::Datetime) | (Type::Datetime, Type::Date) => Some(Type::Datetime),
+        (Type::Array(a), Type::Array(b)) => Some(Type::Array(Box::new(supertype(a, b)?))),
+        // the field names of the first one are used
+        (Type::Struct(a), Type::Struct(b)) if a.len() == b.len() => {
+            let mut fields = Vec::new();
+            for (a, b) in a.iter().zip(b) {
+                let mut field = a.clone();
+                field.data_type = supertype(&a.data_type, &b.data_type)?;
+                fields.push(field);
+            }
+            Some(Type::Struct(fields))
+        }
+        _ => match (numeric_rank(a), numeric_rank(b)) {
+            (Some(x), Some(y)) => Some(if x < y { b.clone() } else { a.clone() }),
+            _ => None,
+        },
+    }
+}
+
+// the result type of CASE, IF, COALESCE, [a, b, ...], ...
+// NULL is coerced to any type and string literals are coerced to date and time types
+pub(crate) fn common_type(exprs: &[(&Node, Type)]) -> Type {
+    let mut res: Option<Type> = None;
+    let mut string_literal = false;
+    for (node, ty) in exprs {
+        match node.node_type {
+            NodeType::NullLiteral => continue,
+            NodeType::StringLiteral if *ty == Type::String => {
+                string_literal = true;
+                continue;
+            }
+            _ => (),
+        }
+        res = match res {
+            None => Some(ty.clone()),
+            Some(r) => match supertype(&r, ty) {
+                Some(ty) => Some(ty),
+                None => return Type::Unknown,
+            },
+        };
+    }
+    match res {
+        None if string_literal => Type::String,
+        None => Type::Unknown,
+        Some(ty) if string_literal && !is_date_or_time(&ty) => {
+            supertype(&ty, &Type::String).unwrap_or(Type::Unknown)
+        }
+        Some(ty) => ty,
+    }
+}
+
+// NumericLiteral, StringLiteral, BooleanLiteral and IntervalLiteral
+pub(crate) fn literal(node: &Node) -> Type {
+    let literal = match &node.token {
+        Some(token) => token.literal.as_str(),
+        None => return Type::Unknown,
+    };
+    match node.node_type {
+        NodeType::NumericLiteral => {
+            let lower = literal.to_lowercase();
+            if !lower.starts_with("0x") && (lower.contains('.') || lower.contains('e')) {
+                Type::Float64
+            } else {
+                Type::Int64
+            }
+        }
+        // b'...', rb'...', br'...'
+        NodeType::StringLiteral => {
+            let prefix = &literal[..literal.find(['\'', '"']).unwrap_or(0)];
+            if prefix.contains(['b', 'B']) {
+                Type::Bytes
+            } else {
+                Type::String
+            }
+        }
+        NodeType::BooleanLiteral => Type::Bool,
+        NodeType::IntervalLiteral => Type::Interval,
+        _ => Type::Unknown,
+    }
+}
+
+// -x, NOT x, DATE '2020-01-01', NUMERIC '1.5', ...
+pub(crate) fn unary(operator: &str, operand: Type) -> Type {
+    match operator {
+        "-" | "+" | "~" => operand,
+        _ if operator.eq_ignore_ascii_case("NOT") => Type::Bool,
+        // typed literals
+        _ => Type::from_name(operator).unwrap_or(Type::Unknown),
+    }
+}
+
+fn arithmetic(left: &Type, right: &Type) -> Type {
+    match (numeric_rank(left), numeric_rank(right)) {
+        (Some(_), Some(_)) => supertype(left, right).unwrap_or(Type::Unknown),
+        _ => Type::Unknown,
+    }
+}
+
+pub(crate) fn binary(operator: &str, left: &Type, right: &Type) -> Type {
+    use Type::*;
+    match operator.to_uppercase().as_str() {
+        "=" | "!=" | "<>" | "<" | ">" | "<=" | ">=" | "LIKE" | "IS" | "AND" | "OR" => Bool,
+        "+" => match (left, right) {
+            (Date, Int64) | (Int64, Date) => Date,
+            (Date | Datetime, Interval) | (Interval, Date | Datetime) => Datetime,
+            (Timestamp, Interval) | (Interval, Timestamp) => Timestamp,
+            (Interval, Interval) => Interval,
+            _ => arithmetic(left, right),
+        },
+        "-" => match (left, right) {
+            (Date, Int64) => Date,
+            (Date | Datetime, Interval) => Datetime,
+            (Timestamp, Interval) => Timestamp,
+            (Interval, Interval) => Interval,
+            (Date, Date) | (Datetime, Datetime) | (Timestamp, Timestamp) => Interval,
+            _ => arithmetic(left, right),
+        },
+        "*" => match (left, right) {
+            (Interval, Int64) | (Int64, Interval) => Interval,
+            _ => arithmetic(left, right),
+        },
+        "/" => match (left, right) {
+            (Interval, Int64) => Interval,
+            _ => match arithmetic(left, right) {
+                Int64 => Float64,
+                ty => ty,
+            },
+        },
+        "||" => match (left, right) {
+            (Array(_), _) | (_, Array(_)) => supertype(left, right).unwrap_or(Unknown),
+            (Bytes, _) | (_, Bytes) => Bytes,
+            (String, _) | (_, String) => String,
+            _ => Unknown,
+        },
+        "&" | "|" | "^" | "<<" | ">>" => left.clone(),
+        _ => Unknown,
+    }
+}
+
+// `name` is upper case without `SAFE.` (e.g. `SUBSTR`, `NET.HOST`)
+pub(crate) fn function(name: &str, args: &[(&Node, Type)]) -> Type {
+    crate::functions::return_type(name, args)
+}
@@ -0,0 +1,352 @@
+use super::*;
+use crate::catalog::Field;
+use crate::parser::Parser;
+
+// the types of the selected expressions of the last statement
+fn test_infer(code: &str, expected: &[&str]) {
+    let mut catalog = Catalog::new();
+    catalog.add_table(
+        "ds.t",
+        vec![
+            Field::new("i", Type::Int64),
+            Field::new("n", Type::Numeric),
+            Field::new("s", Type::String),
+            Field::new("d", Type::Date),
+            Field::new("ts", Type::Timestamp),
+            Field::new("arr", Type::Array(Box::new(Type::String))),
+            Field::new(
+                "st",
+                Type::Struct(vec![
+                    Field::new("x", Type::Float64),
+                    Field::new("y", Type::Bool),
+                ]),
+            ),
+        ],
+    );
+    let stmts = Parser::new(code.to_string()).unwrap().parse_code().unwrap();
+    let types = infer(&stmts, &catalog);
+    let select = stmts
+        .iter()
+        .rev()
+        .find(|s| s.node_type == NodeType::SelectStatement)
+        .unwrap();
+    let result: Vec<String> = select
+        .get_node_vec("exprs")
+        .unwrap()
+        .iter()
+        .map(|e| match types.type_of(e) {
+            Some(ty) => ty.to_string(),
+            None => "-".to_string(),
+        })
+        .collect();
+    assert_eq!(expected, result, "{}", code);
+}
+
+#[test]
+fn test_infer_literals() {
+    test_infer(
+        "SELECT 1, 1.5, 1e3, 0xFF, 'a', b'a', RB'a', TRUE, NULL, INTERVAL 1 DAY, @p",
+        &[
+            "INT64", "FLOAT64", "FLOAT64", "INT64", "STRING", "BYTES", "BYTES", "BOOL", "-",
+            "INTERVAL", "UNKNOWN",
+        ],
+    );
+    test_infer(
+        "SELECT DATE '2020-01-01', TIMESTAMP '2020-01-01', NUMERIC '1.5', DATETIME '2020-01-01'",
+        &["DATE", "TIMESTAMP", "NUMERIC", "DATETIME"],
+    );
+    test_infer(
+        "SELECT STRUCT(1 AS a, 'b'), (i, s), STRUCT<x INT64>(1), [1, 2.5], ARRAY<STRING>['a'], [NULL] FROM ds.t",
+        &[
+            "STRUCT<a INT64, STRING>",
+            "STRUCT<i INT64, s STRING>",
+            "STRUCT<x INT64>",
+            "ARRAY<FLOAT64>",
+            "ARRAY<STRING>",
+            "ARRAY<UNKNOWN>",
+        ],
+    );
+}
+
+#[test]
+fn test_infer_operators() {
+    test_infer(
+        "SELECT i + 1, i + n, i / 2, -n, NOT TRUE, i = 1, s LIKE 'a%', i BETWEEN 1 AND 2, i IN (1), s || 'a', i & 1 FROM ds.t",
+        &[
+            "INT64", "NUMERIC", "FLOAT64", "NUMERIC", "BOOL", "BOOL", "BOOL", "BOOL", "BOOL",
+            "STRING", "INT64",
+        ],
+    );
+    test_infer(
+        "SELECT d + 1, d + INTERVAL 1 DAY, ts - ts, ts - INTERVAL 1 HOUR, arr || ['a'], (i + 1) * 2 FROM ds.t",
+        &[
+            "DATE",
+            "DATETIME",
+            "INTERVAL",
+            "TIMESTAMP",
+            "ARRAY<STRING>",
+            "INT64",
+        ],
+    );
+    // CASE
+    test_infer(
+        "\
+SELECT
+  CASE WHEN i = 1 THEN 1 WHEN i = 2 THEN n ELSE NULL END,
+  CASE i WHEN 1 THEN d ELSE '2020-01-01' END,
+  CASE WHEN TRUE THEN 1 ELSE 'a' END
+FROM ds.t",
+        &["NUMERIC", "DATE", "UNKNOWN"],
+    );
+}
+
+#[test]
+fn test_infer_functions() {
+    test_infer(
+        "\
+SELECT
+  CAST(s AS INT64),
+  CAST(i AS STRING),
+  COUNT(*),
+  SUM(i),
+  AVG(i),
+  MAX(d),
+  ARRAY_AGG(st),
+  SAFE.SUBSTR(s, 1),
+  IF(TRUE, i, n),
+  COALESCE(NULL, s),
+  DATE_TRUNC(d, MONTH),
+  EXTRACT(YEAR FROM d),
+  EXTRACT(DATE FROM ts),
+  CURRENT_DATE,
+  CURRENT_TIMESTAMP(),
+  ROW_NUMBER() OVER (ORDER BY i),
+  NET.HOST(s),
+  ARRAY_INCLUDES(arr, s),
+  ARRAY_MAX(arr),
+  UNKNOWN_FUNCTION(i)
+FROM ds.t",
+        &[
+            "INT64",
+            "STRING",
+            "INT64",
+            "INT64",
+            "FLOAT64",
+            "DATE",
+            "ARRAY<STRUCT<x FLOAT64, y BOOL>>",
+            "STRING",
+            "NUMERIC",
+            "STRING",
+            "DATE",
+            "INT64",
+            "DATE",
+            "DATE",
+            "TIMESTAMP",
+            "INT64",
+            "STRING",
+            "BOOL",
+            "STRING",
+            "UNKNOWN",
+        ],
+    );
+}
+
+#[test]
+fn test_infer_columns() {
+    test_infer(
+        "\
+WITH c AS (SELECT i AS j FROM ds.t)
+SELECT
+  i, t.s, st.x, st, arr[OFFSET(0)], e, o, j, u.col, (SELECT 1)
+FROM ds.t, UNNEST(arr) AS e WITH OFFSET AS o, c, ds.unknown AS u",
+        &[
+            "INT64",
+            "STRING",
+            "FLOAT64",
+            "STRUCT<x FLOAT64, y BOOL>",
+            "STRING",
+            "STRING",
+            "INT64",
+            "INT64",
+            "UNKNOWN",
+            "INT64",
+        ],
+    );
+    // variables
+    test_infer(
+        "DECLARE v ARRAY<INT64>; SELECT v, v[SAFE_OFFSET(1)] + 0.5;",
+        &["ARRAY<INT64>", "FLOAT64"],
+    );
+}
+
+#[test]
+fn test_comparable() {
+    let stmts = Parser::new(
+        "SELECT 1 = 1.5, 1 = '1', DATE '2020-01-01' = '2020-01-01', 'a' = 'b', [1] = [2.5], x = 1, NULL = 1"
+            .to_string(),
+    )
+    .unwrap()
+    .parse_code()
+    .unwrap();
+    let types = infer(&stmts, &Catalog::new());
+    let result: Vec<bool> = stmts[0]
+        .get_node_vec("exprs")
+        .unwrap()
+        .iter()
+        .map(|e| types.comparable(e.get_node("left").unwrap(), e.get_node("right").unwrap()))
+        .collect();
+    assert_eq!(vec![true, false, true, true, true, true, true], result);
+}
+
+// `name TYPE` of each column of the last query
+fn test_schema(code: &str, expected: &[&str]) {
+    let mut catalog = Catalog::new();
+    catalog.add_ddl(
+        &Parser::new(
+            "\
+CREATE TABLE ds.users (id INT64, name STRING, address STRUCT<city STRING, zip STRING>);
+CREATE TABLE ds.orders (id INT64, user_id INT64, amount NUMERIC);"
+                .to_string(),
+        )
+        .unwrap()
+        .parse_code()
+        .unwrap(),
+    );
+    let stmts = Parser::new(code.to_string()).unwrap().parse_code().unwrap();
+    let types = infer(&stmts, &catalog);
+    let query = stmts
+        .iter()
+        .rev()
+        .find(|s| s.node_type != NodeType::EOF)
+        .unwrap();
+    let result: Vec<String> = match types.schema_of(query) {
+        Some(columns) => columns
+            .iter()
+            .map(|c| format!("{} {}", c.name, c.data_type))
+            .collect(),
+        None => vec!["unknown".to_string()],
+    };
+    assert_eq!(expected, result, "{}", code);
+}
+
+#[test]
+fn test_schema_select() {
+    test_schema(
+        "SELECT id, u.name AS user_name, address.city, 1, UPPER(name), 2.5 AS x, NULL FROM ds.users AS u",
+        &[
+            "id INT64",
+            "user_name STRING",
+            "city STRING",
+            "f0_ INT64",
+            "f1_ STRING",
+            "x FLOAT64",
+            "f2_ UNKNOWN",
+        ],
+    );
+    // asterisks
+    test_schema(
+        "SELECT * FROM ds.users JOIN ds.orders USING (id)",
+        &[
+            "id INT64",
+            "name STRING",
+            "address STRUCT<city STRING, zip STRING>",
+            "user_id INT64",
+            "amount NUMERIC",
+        ],
+    );
+    test_schema(
+        "SELECT u.* EXCEPT (address), o.* REPLACE (CAST(amount AS STRING) AS amount), address.* FROM ds.users AS u, ds.orders AS o",
+        &[
+            "id INT64",
+            "name STRING",
+            "id INT64",
+            "user_id INT64",
+            "amount STRING",
+            "city STRING",
+            "zip STRING",
+        ],
+    );
+    test_schema(
+        "SELECT * FROM UNNEST([1, 2]) AS x WITH OFFSET AS o",
+        &["x INT64", "o INT64"],
+    );
+    test_schema("SELECT * FROM ds.unknown", &["unknown"]);
+    test_schema(
+        "SELECT id, t.* FROM ds.users, ds.unknown AS t",
+        &["unknown"],
+    );
+    // CTEs and subqueries
+    test_schema(
+        "\
+WITH c AS (SELECT * FROM ds.orders)
+SELECT s.*, (SELECT MAX(amount) FROM c) AS max_amount FROM (SELECT user_id, SUM(amount) FROM c GROUP BY 1) AS s",
+        &["user_id INT64", "f0_ NUMERIC", "max_amount NUMERIC"],
+    );
+}
+
+#[test]
+fn test_schema_set_operator() {
+    test_schema(
+        "SELECT 1 AS a, 'x' AS b UNION ALL SELECT 2.5, NULL UNION ALL (SELECT amount, 'y' FROM ds.orders)",
+        &["a FLOAT64", "b STRING"],
+    );
+    test_schema(
+        "SELECT id FROM ds.users EXCEPT DISTINCT SELECT * FROM ds.unknown",
+        &["id INT64"],
+    );
+}
+
+#[test]
+fn test_schema_as_struct_or_value() {
+    test_schema(
+        "SELECT AS STRUCT id, name FROM ds.users",
+        &["id INT64", "name STRING"],
+    );
+    test_schema(
+        "SELECT AS VALUE STRUCT(id AS x, 1 AS y) FROM ds.users",
+        &["x INT64", "y INT64"],
+    );
+    test_schema("SELECT AS VALUE name FROM ds.users", &["f0_ STRING"]);
+    test_schema(
+        "\
+SELECT
+  ARRAY(SELECT AS STRUCT id, amount FROM ds.orders) AS a,
+  ARRAY(SELECT id FROM ds.orders) AS b,
+  (SELECT AS VALUE name FROM ds.users LIMIT 1) AS c,
+  EXISTS(SELECT 1) AS d",
+        &[
+            "a ARRAY<STRUCT<id INT64, amount NUMERIC>>",
+            "b ARRAY<INT64>",
+            "c STRING",
+            "d BOOL",
+        ],
+    );
+}
+
+#[test]
+fn test_schema_ddl() {
+    let mut catalog = Catalog::new();
+    catalog.add_ddl(
+        &Parser::new(
+            "\
+CREATE TABLE ds.a (id INT64, ts TIMESTAMP);
+CREATE TABLE ds.b AS SELECT id, DATE(ts) AS dt, COUNT(*) FROM ds.a GROUP BY 1, 2;
+CREATE VIEW ds.c (x, y) AS SELECT dt, id + 0.5 FROM ds.b;
+CREATE VIEW ds.d (x) AS SELECT * FROM ds.b;"
+                .to_string(),
+        )
+        .unwrap()
+        .parse_code()
+        .unwrap(),
+    );
+    let schema = |name: &str| -> Vec<String> {
+        let columns = catalog.table(name).unwrap().columns.iter().flatten();
+        columns
+            .map(|c| format!("{} {}", c.name, c.data_type))
+            .collect()
+    };
+    assert_eq!(vec!["id INT64", "dt DATE", "f0_ INT64"], schema("ds.b"));
+    assert_eq!(vec!["x DATE", "y FLOAT64"], schema("ds.c"));
+    // the number of columns does not match
+    assert_eq!(vec!["x UNKNOWN"], schema("ds.d"));
+}
@@ -1,14 +1,18 @@
 #[cfg(test)]
 mod tests;
 
-use crate::token::Token;
+use crate::error::{ParseError, ParseResult};
+use crate::token::{is_reserved_keyword, CommentStyle, Token, TokenKind};
 
 pub struct Lexer {
     input: Vec<char>,
     position: usize,
+    offset: usize,       // byte offset of `position`
+    offset_utf16: usize, // UTF-16 code unit offset of `position`
     line: usize,
     column: usize,
     type_declaration_depth: usize,
+    whitespace: String, // the leading whitespace of the next token
     pub tokens: Vec<Token>,
 }
 
@@ -18,117 +22,172 @@ impl Lexer {
         Lexer {
             input: chars,
             position: 0,
+            offset: 0,
+            offset_utf16: 0,
             line: 1,
             column: 1,
             type_declaration_depth: 0,
+            whitespace: String::new(),
             tokens: Vec::new(),
         }
     }
-    pub fn tokenize_code(&mut self) -> &Vec<Token> {
-        let mut token = self.next_token();
-        while !token.is_none() {
-            self.next_token();
-            token = self.next_token();
+    // `input` is a part of the code starting at the given position (used by incremental reparsing)
+    pub(crate) fn with_position(
+        input: String,
+        line: usize,
+        column: usize,
+        offset: usize,
+        offset_utf16: usize,
+    ) -> Lexer {
+        Lexer {
+            offset,
+            offset_utf16,
+            line,
+            column,
+            ..Lexer::new(input)
         }
-        self.tokens.push(Token::eof());
-        &self.tokens
+    }
+    pub fn tokenize_code(&mut self) -> ParseResult<&Vec<Token>> {
+        while self.next_token()?.is_some() {}
+        self.construct_token(self.line, self.column, "".to_string(), TokenKind::EOF);
+        Ok(&self.tokens)
     }
     fn get_char(&self, offset: usize) -> Option<char> {
         if self.position + offset < self.input.len() {
-            return Some(self.input[self.position + offset]);
+            Some(self.input[self.position + offset])
         } else {
-            return None; // EOF
+            None // EOF
         }
     }
-    fn read_char(&mut self) {
+    fn read_char(&mut self) -> ParseResult<()> {
         if self.position < self.input.len() {
-            if self.input[self.position] == '\n' {
+            let ch = self.input[self.position];
+            if ch == '\n' {
                 self.column = 1;
                 self.line += 1;
             } else {
                 self.column += 1;
             }
             self.position += 1;
+            self.offset += ch.len_utf8();
+            self.offset_utf16 += ch.len_utf16();
+            Ok(())
         } else {
-            panic!("`read_char()` is called at EOF");
+            Err(ParseError::new(
+                self.line,
+                self.column,
+                "Unexpected EOF".to_string(),
+            ))
         }
     }
-    fn skip_escaped_char(&mut self) {
-        self.read_char(); // '\\' -> ?
+    fn skip_escaped_char(&mut self) -> ParseResult<()> {
+        self.read_char()?; // '\\' -> ?
         match self.get_char(0) {
             // https://cloud.google.com/bigquery/docs/reference/standard-sql/lexical#literals
             Some('x') => {
                 for _ in 0..2 {
-                    self.read_char();
+                    self.read_char()?;
                 }
             }
             Some('u') => {
                 for _ in 0..4 {
-                    self.read_char();
+                    self.read_char()?;
                 }
             }
             Some('U') => {
                 for _ in 0..8 {
-                    self.read_char();
+                    self.read_char()?;
                 }
             }
             Some('0') => {
                 for _ in 0..3 {
-                    self.read_char();
+                    self.read_char()?;
                 }
             }
             Some('1'..='7') => {
                 for _ in 0..3 {
-                    self.read_char();
+                    self.read_char()?;
                 }
             }
             Some(_) => (), // \n, \t, ...
-            None => panic!("reached EOF in `skip_escaped_char()`"),
+            None => {
+                return Err(ParseError::new(
+                    self.line,
+                    self.column,
+                    "Unexpected EOF after `\\`".to_string(),
+                ))
+            }
         }
-        self.read_char();
+        self.read_char()
     }
-    fn read_identifier(&mut self) -> String {
+    fn read_identifier(&mut self) -> ParseResult<String> {
         let first_position = self.position;
         let first_char = self.get_char(0);
         if !is_valid_1st_char_of_ident(&first_char) {
-            panic!(
-                "Calling `read_identifier()` is not allowed here: {:?}",
-                first_char
-            );
+            let got = match first_char {
+                Some(ch) => format!("`{}`", ch),
+                None => "EOF".to_string(),
+            };
+            return Err(ParseError::new(
+                self.line,
+                self.column,
+                format!("Expected identifier but got: {}", got),
+            ));
         }
-        self.read_char();
+        self.read_char()?;
         while is_valid_char_of_ident(&self.get_char(0)) {
-            self.read_char();
+            self.read_char()?;
         }
-        self.input[first_position..self.position]
-            .into_iter()
-            .collect()
+        Ok(self.input[first_position..self.position].iter().collect())
     }
-    fn read_parameter(&mut self) -> String {
+    fn read_parameter(&mut self) -> ParseResult<String> {
         let first_position = self.position;
         while self.get_char(0) == Some('@') {
-            self.read_char();
+            self.read_char()?;
         }
         if self.get_char(0) == Some('`') {
-            self.read_quoted();
+            self.read_quoted()?;
         } else {
-            self.read_identifier();
+            self.read_identifier()?;
         }
-        self.input[first_position..self.position]
-            .into_iter()
-            .collect()
+        Ok(self.input[first_position..self.position].iter().collect())
     }
-    fn construct_token(&mut self, line: usize, column: usize, literal: String) -> &Token {
-        let token = Token::new(line, column, literal);
+    fn construct_token(
+        &mut self,
+        line: usize,
+        column: usize,
+        literal: String,
+        kind: TokenKind,
+    ) -> &Token {
+        let mut token = Token::with_kind(line, column, literal, kind);
+        // the current position is the end of the token
+        token.start = self.offset - token.literal.len();
+        token.end = self.offset;
+        token.start_utf16 = self.offset_utf16 - token.literal.encode_utf16().count();
+        token.end_utf16 = self.offset_utf16;
+        token.leading_whitespace = std::mem::take(&mut self.whitespace);
         self.tokens.push(token);
-        &self.tokens.last().unwrap()
+        self.tokens.last().unwrap()
+    }
+    // read `n` chars as an operator or a punctuation
+    fn construct_symbol(&mut self, line: usize, column: usize, n: usize) -> ParseResult<&Token> {
+        let first_position = self.position;
+        for _ in 0..n {
+            self.read_char()?;
+        }
+        let literal: String = self.input[first_position..self.position].iter().collect();
+        let kind = match literal.as_str() {
+            "(" | ")" | "[" | "]" | "," | ";" | "." | ":" => TokenKind::Punctuation,
+            _ => TokenKind::Operator,
+        };
+        Ok(self.construct_token(line, column, literal, kind))
     }
-    fn next_token(&mut self) -> Option<&Token> {
-        self.skip_whitespace();
+    fn next_token(&mut self) -> ParseResult<Option<&Token>> {
+        self.whitespace = self.read_whitespace()?;
         let ch = match self.get_char(0) {
             Some(ch) => ch,
             None => {
-                return None; // EOF
+                return Ok(None); // EOF
             }
         };
         let line = self.line;
@@ -136,250 +195,251 @@ impl Lexer {
         let token = match ch {
             '.' => match self.get_char(1) {
                 Some('0'..='9') => {
-                    let literal = self.read_number();
-                    self.construct_token(line, column, literal)
-                }
-                _ => {
-                    self.read_char();
-                    self.construct_token(line, column, ch.to_string())
+                    let literal = self.read_number()?;
+                    self.construct_token(line, column, literal, TokenKind::Number)
                 }
+                _ => self.construct_symbol(line, column, 1)?,
             },
             '#' => {
-                let literal = self.read_comment();
-                self.construct_token(line, column, literal)
+                let literal = self.read_comment()?;
+                let kind = TokenKind::Comment {
+                    style: CommentStyle::Hash,
+                };
+                self.construct_token(line, column, literal, kind)
             }
             // quotation
             '`' => {
-                let literal = self.read_quoted();
-                self.construct_token(line, column, literal)
+                let literal = self.read_quoted()?;
+                self.construct_token(line, column, literal, TokenKind::QuotedIdent)
             }
-            '"' => {
-                if self.get_char(1) == Some('"') && self.get_char(2) == Some('"') {
-                    let literal = self.read_multiline_string();
-                    self.construct_token(line, column, literal)
-                } else {
-                    let literal = self.read_quoted();
-                    self.construct_token(line, column, literal)
-                }
+            '"' | '\'' => {
+                let (literal, kind) = self.read_string("")?;
+                self.construct_token(line, column, literal, kind)
             }
-            '\'' => {
-                if self.get_char(1) == Some('\'') && self.get_char(2) == Some('\'') {
-                    let literal = self.read_multiline_string();
-                    self.construct_token(line, column, literal)
-                } else {
-                    let literal = self.read_quoted();
-                    self.construct_token(line, column, literal)
-                }
+            '-' if self.get_char(1) == Some('-') => {
+                let literal = self.read_comment()?;
+                let kind = TokenKind::Comment {
+                    style: CommentStyle::Line,
+                };
+                self.construct_token(line, column, literal, kind)
             }
-            '-' => {
-                if self.get_char(1) == Some('-') {
-                    let literal = self.read_comment();
-                    self.construct_token(line, column, literal)
-                } else {
-                    self.read_char();
-                    self.construct_token(line, column, ch.to_string())
-                }
+            '/' if self.get_char(1) == Some('*') => {
+                let literal = self.read_multiline_comment()?;
+                let kind = TokenKind::Comment {
+                    style: CommentStyle::Block,
+                };
+                self.construct_token(line, column, literal, kind)
             }
-            '/' => {
-                if self.get_char(1) == Some('*') {
-                    let literal = self.read_multiline_comment();
-                    self.construct_token(line, column, literal)
-                } else {
-                    self.read_char();
-                    self.construct_token(line, column, ch.to_string())
-                }
-            }
-            '|' => {
-                if self.get_char(1) == Some('|') {
-                    self.read_char();
-                    self.read_char();
-                    self.construct_token(line, column, "||".to_string())
-                } else {
-                    self.read_char();
-                    self.construct_token(line, column, ch.to_string())
-                }
-            }
-            '<' => {
-                if self.get_char(1) == Some('<') {
-                    self.read_char();
-                    self.read_char();
-                    self.construct_token(line, column, "<<".to_string())
-                } else if self.get_char(1) == Some('=') {
-                    self.read_char();
-                    self.read_char();
-                    self.construct_token(line, column, "<=".to_string())
-                } else if self.get_char(1) == Some('>') {
-                    self.read_char();
-                    self.read_char();
-                    self.construct_token(line, column, "<>".to_string())
-                } else {
-                    if self.tokens.last().unwrap().literal.to_uppercase() == "ARRAY"
-                        || self.tokens.last().unwrap().literal.to_uppercase() == "STRUCT"
-                    {
-                        self.type_declaration_depth += 1;
+            '|' if self.get_char(1) == Some('|') => self.construct_symbol(line, column, 2)?,
+            '<' => match self.get_char(1) {
+                Some('<') | Some('=') | Some('>') => self.construct_symbol(line, column, 2)?,
+                _ => {
+                    if let Some(last_token) = self.tokens.last() {
+                        if last_token.in_(&vec!["ARRAY", "STRUCT"]) {
+                            self.type_declaration_depth += 1;
+                        }
                     }
-                    self.read_char();
-                    self.construct_token(line, column, ch.to_string())
+                    self.construct_symbol(line, column, 1)?
                 }
-            }
+            },
             '>' => {
                 if 0 < self.type_declaration_depth {
                     self.type_declaration_depth -= 1;
-                    self.read_char();
-                    self.construct_token(line, column, ch.to_string())
-                } else if self.get_char(1) == Some('>') {
-                    self.read_char();
-                    self.read_char();
-                    self.construct_token(line, column, ">>".to_string())
-                } else if self.get_char(1) == Some('=') {
-                    self.read_char();
-                    self.read_char();
-                    self.construct_token(line, column, ">=".to_string())
-                } else {
-                    self.read_char();
-                    self.construct_token(line, column, ch.to_string())
-                }
-            }
-            '=' => {
-                if self.get_char(1) == Some('>') {
-                    self.read_char();
-                    self.read_char();
-                    self.construct_token(line, column, "=>".to_string())
+                    self.construct_symbol(line, column, 1)?
+                } else if let Some('>') | Some('=') = self.get_char(1) {
+                    self.construct_symbol(line, column, 2)?
                 } else {
-                    self.read_char();
-                    self.construct_token(line, column, ch.to_string())
-                }
-            }
-            '!' => {
-                if self.get_char(1) == Some('=') {
-                    self.read_char();
-                    self.read_char();
-                    self.construct_token(line, column, "!=".to_string())
-                } else {
-                    self.read_char();
-                    self.construct_token(line, column, ch.to_string())
+                    self.construct_symbol(line, column, 1)?
                 }
             }
+            '=' if self.get_char(1) == Some('>') => self.construct_symbol(line, column, 2)?,
+            '!' if self.get_char(1) == Some('=') => self.construct_symbol(line, column, 2)?,
             // parameter
             '@' => {
-                let literal = self.read_parameter();
-                self.construct_token(line, column, literal)
+                let literal = self.read_parameter()?;
+                self.construct_token(line, column, literal, TokenKind::Param)
+            }
+            '?' => {
+                self.read_char()?;
+                self.construct_token(line, column, ch.to_string(), TokenKind::Param)
             }
             // int64 or float64 literal
             '0'..='9' => {
-                let literal = self.read_number();
-                self.construct_token(line, column, literal)
+                let literal = self.read_number()?;
+                self.construct_token(line, column, literal, TokenKind::Number)
             }
             // other
             _ => {
                 if is_valid_1st_char_of_ident(&Some(ch)) {
-                    let literal = self.read_identifier();
-                    self.construct_token(line, column, literal)
+                    let literal = self.read_identifier()?;
+                    let is_string_prefix = matches!(
+                        literal.to_ascii_uppercase().as_str(),
+                        "R" | "B" | "RB" | "BR"
+                    );
+                    if is_string_prefix && matches!(self.get_char(0), Some('"') | Some('\'')) {
+                        // r'...' | b"..." | ...
+                        let (literal, kind) = self.read_string(&literal)?;
+                        self.construct_token(line, column, literal, kind)
+                    } else if is_reserved_keyword(&literal) {
+                        self.construct_token(line, column, literal, TokenKind::Keyword)
+                    } else {
+                        self.construct_token(line, column, literal, TokenKind::Ident)
+                    }
                 } else {
-                    self.read_char();
-                    self.construct_token(line, column, ch.to_string())
+                    self.construct_symbol(line, column, 1)?
                 }
             }
         };
-        Some(token)
+        Ok(Some(token))
     }
-    fn read_multiline_string(&mut self) -> String {
+    // `prefix` has already been read
+    fn read_string(&mut self, prefix: &str) -> ParseResult<(String, TokenKind)> {
+        let ch = self.get_char(0);
+        let triple = self.get_char(1) == ch && self.get_char(2) == ch;
+        let body = if triple {
+            self.read_multiline_string()?
+        } else {
+            self.read_quoted()?
+        };
+        let prefix_upper = prefix.to_ascii_uppercase();
+        let kind = TokenKind::String {
+            raw: prefix_upper.contains('R'),
+            bytes: prefix_upper.contains('B'),
+            triple,
+        };
+        Ok((format!("{}{}", prefix, body), kind))
+    }
+    fn read_multiline_string(&mut self) -> ParseResult<String> {
+        let (line, column) = (self.line, self.column);
         let first_position = self.position;
         let ch = self.get_char(0);
-        self.read_char(); // first ' -> second '
+        self.read_char()?; // first ' -> second '
         while !(self.get_char(0) == ch && self.get_char(1) == ch && self.get_char(2) == ch) {
-            if self.get_char(0) == Some('\\') {
-                self.skip_escaped_char();
+            let res = if self.get_char(0) == Some('\\') {
+                self.skip_escaped_char()
             } else {
-                self.read_char();
+                self.read_char()
+            };
+            if res.is_err() {
+                return Err(unclosed_error(
+                    line,
+                    column,
+                    "string literal",
+                    ch.map(String::from),
+                ));
             }
         }
-        self.read_char(); // first ' -> secont '
-        self.read_char(); // second ' -> third '
-        self.read_char(); // third ' ->  next_ch
-        self.input[first_position..self.position]
-            .into_iter()
-            .collect()
+        self.read_char()?; // first ' -> secont '
+        self.read_char()?; // second ' -> third '
+        self.read_char()?; // third ' ->  next_ch
+        Ok(self.input[first_position..self.position].iter().collect())
     }
-    fn read_quoted(&mut self) -> String {
+    fn read_quoted(&mut self) -> ParseResult<String> {
+        let (line, column) = (self.line, self.column);
         let quote = self.get_char(0);
         let first_position = self.position;
-        self.read_char();
+        self.read_char()?;
         while self.get_char(0) != quote {
-            if self.get_char(0) == Some('\\') {
-                self.skip_escaped_char();
+            let res = if self.get_char(0) == Some('\\') {
+                self.skip_escaped_char()
             } else {
-                self.read_char();
+                self.read_char()
+            };
+            if res.is_err() {
+                let what = if quote == Some('`') {
+                    "quoted identifier"
+                } else {
+                    "string literal"
+                };
+                return Err(unclosed_error(line, column, what, quote.map(String::from)));
             }
         }
-        self.read_char(); // ' -> next_ch
-        self.input[first_position..self.position]
-            .into_iter()
-            .collect()
+        self.read_char()?; // ' -> next_ch
+        Ok(self.input[first_position..self.position].iter().collect())
     }
-    fn skip_whitespace(&mut self) {
+    fn read_whitespace(&mut self) -> ParseResult<String> {
+        let first_position = self.position;
         while is_whitespace(&self.get_char(0)) {
-            self.read_char();
+            self.read_char()?;
         }
+        Ok(self.input[first_position..self.position].iter().collect())
     }
-    fn read_number(&mut self) -> String {
+    fn read_number(&mut self) -> ParseResult<String> {
         let first_position = self.position;
         while is_digit(&self.get_char(0)) {
-            self.read_char();
+            self.read_char()?;
         } // 9 -> .
         if self.get_char(0) == Some('.') {
-            self.read_char();
+            self.read_char()?;
             while is_digit(&self.get_char(0)) {
-                self.read_char();
+                self.read_char()?;
             }
         }
         if let Some('E') | Some('e') = self.get_char(0) {
-            self.read_char(); // e -> 9, +, -
+            self.read_char()?; // e -> 9, +, -
             if let Some('+') | Some('-') = self.get_char(0) {
-                self.read_char(); // +, - -> 9
+                self.read_char()?; // +, - -> 9
             }
             while is_digit(&self.get_char(0)) {
-                self.read_char();
+                self.read_char()?;
             }
         }
-        self.input[first_position..self.position]
-            .into_iter()
-            .collect()
+        Ok(self.input[first_position..self.position].iter().collect())
     }
-    fn read_comment(&mut self) -> String {
+    fn read_comment(&mut self) -> ParseResult<String> {
         let first_position = self.position;
         while !is_end_of_line(&self.get_char(0)) {
-            self.read_char();
+            self.read_char()?;
         }
-        self.input[first_position..self.position]
-            .into_iter()
-            .collect::<String>()
-            .trim_end()
-            .to_string()
+        // trailing whitespace is left to the next token
+        // NOTE it does not contain `\n`, so only `column` has to be moved back
+        while self.position > first_position && is_whitespace(&Some(self.input[self.position - 1]))
+        {
+            let ch = self.input[self.position - 1];
+            self.position -= 1;
+            self.column -= 1;
+            self.offset -= ch.len_utf8();
+            self.offset_utf16 -= ch.len_utf16();
+        }
+        Ok(self.input[first_position..self.position].iter().collect())
     }
-    fn read_multiline_comment(&mut self) -> String {
+    fn read_multiline_comment(&mut self) -> ParseResult<String> {
+        let (line, column) = (self.line, self.column);
         let first_position = self.position;
         while !(self.get_char(0) == Some('*') && self.get_char(1) == Some('/')) {
-            self.read_char();
+            if self.read_char().is_err() {
+                return Err(unclosed_error(
+                    line,
+                    column,
+                    "comment",
+                    Some("*/".to_string()),
+                ));
+            }
         }
-        self.read_char(); // * -> /
-        self.read_char(); // / -> next_char
-        self.input[first_position..self.position]
-            .into_iter()
-            .collect()
+        self.read_char()?; // * -> /
+        self.read_char()?; // / -> next_char
+        Ok(self.input[first_position..self.position].iter().collect())
+    }
+}
+
+fn unclosed_error(line: usize, column: usize, what: &str, closing: Option<String>) -> ParseError {
+    let mut error = ParseError::new(line, column, format!("This {} is not closed", what));
+    if let Some(closing) = closing {
+        error.expected = vec![closing];
     }
+    error
 }
 
 fn is_digit(ch: &Option<char>) -> bool {
     match ch {
-        Some(ch) => ch.is_digit(10),
+        Some(ch) => ch.is_ascii_digit(),
         None => false,
     }
 }
 
 fn is_valid_char_of_ident(ch: &Option<char>) -> bool {
     match ch {
-        Some(ch) => ch.is_alphabetic() || ch.is_digit(10) || ch == &'_',
+        Some(ch) => ch.is_alphabetic() || ch.is_ascii_digit() || ch == &'_',
         None => false,
     }
 }
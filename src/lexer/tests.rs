@@ -1,4 +1,5 @@
 use super::*;
+use crate::token::{CommentStyle, TokenKind};
 
 struct TestCase {
     code: String,
@@ -10,10 +11,16 @@ impl TestCase {
     fn new(code: &str, expected_tokens_without_eof: Vec<Token>) -> TestCase {
         let code = code.to_string();
         let mut l = Lexer::new(code.clone());
-        l.tokenize_code();
+        l.tokenize_code().unwrap();
         let result_tokens = l.tokens;
         let mut expected_tokens = expected_tokens_without_eof;
-        expected_tokens.push(Token::eof());
+        // EOF is located at the end of the code
+        let eof_line = code.matches('\n').count() + 1;
+        let eof_column = match code.rfind('\n') {
+            Some(i) => code[i + 1..].chars().count() + 1,
+            None => code.chars().count() + 1,
+        };
+        expected_tokens.push(Token::eof(eof_line, eof_column));
         TestCase {
             code,
             expected_tokens,
@@ -27,8 +34,27 @@ impl TestCase {
         );
         assert_eq!(self.expected_tokens.len(), self.result_tokens.len());
         for i in 0..self.expected_tokens.len() {
-            assert_eq!(self.expected_tokens[i], self.result_tokens[i]);
+            let expected = &self.expected_tokens[i];
+            let result = &self.result_tokens[i];
+            // `leading_whitespace` is tested below
+            // NOTE `kind` of expected tokens is guessed from the literal
+            assert_eq!(
+                (
+                    expected.line,
+                    expected.column,
+                    &expected.literal,
+                    expected.kind
+                ),
+                (result.line, result.column, &result.literal, result.kind)
+            );
         }
+        // no character is lost
+        let restored: String = self
+            .result_tokens
+            .iter()
+            .map(|t| format!("{}{}", t.leading_whitespace, t.literal))
+            .collect();
+        assert_eq!(self.code, restored);
     }
 }
 
@@ -88,8 +114,7 @@ xxx
                 Token::from_str(1, 1, "SELECT"),
                 Token::from_str(2, 3, "'xxx'"),
                 Token::from_str(2, 8, ","),
-                Token::from_str(3, 3, "r"),
-                Token::from_str(3, 4, "'xxx'"),
+                Token::from_str(3, 3, "r'xxx'"),
                 Token::from_str(3, 9, ","),
                 Token::from_str(4, 3, "\"xxx\""),
                 Token::from_str(4, 8, ","),
@@ -246,3 +271,147 @@ SELECT f(a1, a2)",
         t.test();
     }
 }
+
+#[test]
+fn test_tokenize_code_whitespace() {
+    let code = "\
+SELECT
+\t1, -- comment\t\r
+\r
+  2 /* c */
+
+";
+    let mut l = Lexer::new(code.to_string());
+    l.tokenize_code().unwrap();
+    let result: Vec<(&str, &str)> = l
+        .tokens
+        .iter()
+        .map(|t| (t.leading_whitespace.as_str(), t.literal.as_str()))
+        .collect();
+    assert_eq!(
+        vec![
+            ("", "SELECT"),
+            ("\n\t", "1"),
+            ("", ","),
+            (" ", "-- comment"),
+            ("\t\r\n\r\n  ", "2"),
+            (" ", "/* c */"),
+            ("\n\n", ""), // EOF
+        ],
+        result
+    );
+    // the column of the next token is not affected by trailing whitespace of comments
+    assert_eq!((4, 3), (l.tokens[4].line, l.tokens[4].column));
+}
+
+#[test]
+fn test_tokenize_code_offset() {
+    // `é` is 2 bytes, `😀` is 4 bytes (2 UTF-16 code units)
+    let code = "\
+SELECT 'é😀',
+  x -- 😀
+";
+    let mut l = Lexer::new(code.to_string());
+    l.tokenize_code().unwrap();
+    let result: Vec<_> = l
+        .tokens
+        .iter()
+        .map(|t| {
+            (
+                t.literal.as_str(),
+                (t.end_line, t.end_column),
+                (t.start, t.end),
+                (t.start_utf16, t.end_utf16),
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("SELECT", (1, 7), (0, 6), (0, 6)),
+            ("'é😀'", (1, 12), (7, 15), (7, 12)),
+            (",", (1, 13), (15, 16), (12, 13)),
+            ("x", (2, 4), (19, 20), (16, 17)),
+            ("-- 😀", (2, 9), (21, 28), (18, 23)),
+            ("", (3, 1), (29, 29), (24, 24)), // EOF
+        ],
+        result
+    );
+    for t in &l.tokens {
+        assert_eq!(t.literal, code[t.start..t.end]);
+    }
+}
+
+#[test]
+fn test_tokenize_code_kind() {
+    let code = "\
+SELECT x, `y`, TABLE, 'a', r\"b\", RB'''c''', 1e3, @p, @@v, ? -- x
+/* y */ # z
+FROM t WHERE x <= (1);";
+    let mut l = Lexer::new(code.to_string());
+    let kinds: Vec<(&str, TokenKind)> = l
+        .tokenize_code()
+        .unwrap()
+        .iter()
+        .filter(|t| t.literal != ",")
+        .map(|t| (t.literal.as_str(), t.kind))
+        .collect();
+    let string = |raw, bytes, triple| TokenKind::String { raw, bytes, triple };
+    let comment = |style| TokenKind::Comment { style };
+    assert_eq!(
+        vec![
+            ("SELECT", TokenKind::Keyword),
+            ("x", TokenKind::Ident),
+            ("`y`", TokenKind::QuotedIdent),
+            ("TABLE", TokenKind::Ident),
+            ("'a'", string(false, false, false)),
+            ("r\"b\"", string(true, false, false)),
+            ("RB'''c'''", string(true, true, true)),
+            ("1e3", TokenKind::Number),
+            ("@p", TokenKind::Param),
+            ("@@v", TokenKind::Param),
+            ("?", TokenKind::Param),
+            ("-- x", comment(CommentStyle::Line)),
+            ("/* y */", comment(CommentStyle::Block)),
+            ("# z", comment(CommentStyle::Hash)),
+            ("FROM", TokenKind::Keyword),
+            ("t", TokenKind::Ident),
+            ("WHERE", TokenKind::Keyword),
+            ("x", TokenKind::Ident),
+            ("<=", TokenKind::Operator),
+            ("(", TokenKind::Punctuation),
+            ("1", TokenKind::Number),
+            (")", TokenKind::Punctuation),
+            (";", TokenKind::Punctuation),
+            ("", TokenKind::EOF),
+        ],
+        kinds
+    );
+}
+
+#[test]
+fn test_tokenize_code_error() {
+    let test_cases = vec![
+        ("SELECT 'abc", (1, 8), "This string literal is not closed"),
+        (
+            "SELECT '''abc''",
+            (1, 8),
+            "This string literal is not closed",
+        ),
+        (
+            "SELECT `abc",
+            (1, 8),
+            "This quoted identifier is not closed",
+        ),
+        ("SELECT 1 /* abc", (1, 10), "This comment is not closed"),
+        ("SELECT 'abc\\", (1, 8), "This string literal is not closed"),
+        ("SELECT @", (1, 9), "Expected identifier but got: EOF"),
+    ];
+    for (code, (line, column), message) in test_cases {
+        let mut l = Lexer::new(code.to_string());
+        let error = l.tokenize_code().unwrap_err();
+        assert_eq!(
+            (line, column, message),
+            (error.line, error.column, error.message.as_str())
+        );
+    }
+}
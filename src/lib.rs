@@ -1,21 +1,161 @@
+pub mod ast;
+pub mod catalog;
 mod constants;
-mod cst;
-mod lexer;
-mod parser;
-mod token;
+pub mod cst;
+pub mod error;
+pub mod format;
+pub mod functions;
+pub mod incremental;
+pub mod infer;
+pub mod lexer;
+pub mod lineage;
+pub mod lint;
+mod names;
+pub mod parser;
+pub mod resolve;
+pub mod token;
+#[cfg(feature = "wasm")]
 mod types;
+pub mod unparser;
+#[cfg(feature = "wasm")]
 mod utils;
 
+pub use cst::{ContentType, Node, NodeType};
+pub use error::ParseError as Error;
+pub use format::FormatOptions;
+pub use incremental::TextEdit;
+pub use lint::{LintOptions, Linter};
+pub use token::{Token, TokenCategory};
+pub use unparser::unparse;
+
+#[cfg(feature = "wasm")]
+use serde::Serialize;
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
+// the last node is always EOF
+pub fn parse_str(code: &str) -> Result<Vec<Node>, Error> {
+    parser::Parser::new(code.to_string())?.parse_code()
+}
+
+// lexical errors are returned as `Err`, syntax errors are returned with partial CST
+pub fn parse_str_with_recovery(code: &str) -> Result<(Vec<Node>, Vec<Error>), Error> {
+    Ok(parser::Parser::new(code.to_string())?.parse_code_with_recovery())
+}
+
+// `code` and `stmts` are the old ones, only the statements around the edit are reparsed
+pub fn reparse_str(code: &str, stmts: Vec<Node>, edit: &TextEdit) -> Result<Vec<Node>, Error> {
+    incremental::reparse(code, stmts, edit)
+}
+
+pub fn reparse_str_with_recovery(
+    code: &str,
+    stmts: Vec<Node>,
+    edit: &TextEdit,
+) -> Result<(Vec<Node>, Vec<Error>), Error> {
+    incremental::reparse_with_recovery(code, stmts, edit)
+}
+
+// the last token is always EOF, use `token::categorize()` to classify them
+pub fn tokenize_str(code: &str) -> Result<Vec<Token>, Error> {
+    let mut lexer = lexer::Lexer::new(code.to_string());
+    lexer.tokenize_code()?;
+    Ok(lexer.tokens)
+}
+
+pub fn format_str(code: &str, options: &FormatOptions) -> Result<String, Error> {
+    Ok(format::format(&parse_str(code)?, options))
+}
+
+// diagnostics are sorted by position, syntax errors are returned as `Err`
+pub fn lint_str(code: &str, linter: &Linter) -> Result<Vec<lint::Diagnostic>, Error> {
+    Ok(linter.lint(&parse_str(code)?))
+}
+
+#[cfg(feature = "wasm")]
 #[wasm_bindgen(skip_typescript)]
-pub fn parse(code: String) -> JsValue {
+pub fn parse(code: String) -> Result<JsValue, JsValue> {
     utils::set_panic_hook();
-    let mut p = parser::Parser::new(code);
-    let stmts = p.parse_code();
-    match JsValue::from_serde(&stmts) {
-        Ok(json) => json,
-        Err(error) => panic!("Probrem converting struct to json: {:?}", error),
-    }
+    let stmts = parse_str(&code).map_err(|e| utils::to_js_error(&e))?;
+    utils::to_js_value(&stmts)
+}
+
+#[cfg(feature = "wasm")]
+#[derive(Serialize)]
+struct RecoveredCode {
+    stmts: Vec<Node>,
+    errors: Vec<Error>,
 }
 
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(skip_typescript)]
+pub fn parse_with_recovery(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let (stmts, errors) = parse_str_with_recovery(&code).map_err(|e| utils::to_js_error(&e))?;
+    utils::to_js_value(&RecoveredCode { stmts, errors })
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(skip_typescript)]
+pub fn format(code: String, options: JsValue) -> Result<String, JsValue> {
+    utils::set_panic_hook();
+    // every option can be omitted
+    let options: FormatOptions = if options.is_undefined() || options.is_null() {
+        FormatOptions::default()
+    } else {
+        #[allow(deprecated)]
+        match options.into_serde() {
+            Ok(options) => options,
+            Err(error) => {
+                let message = format!("Invalid format options: {}", error);
+                return Err(js_sys::Error::new(&message).into());
+            }
+        }
+    };
+    format_str(&code, &options).map_err(|e| utils::to_js_error(&e))
+}
+
+#[cfg(feature = "wasm")]
+#[derive(Serialize)]
+struct CategorizedToken<'a> {
+    #[serde(flatten)]
+    token: &'a Token,
+    category: TokenCategory,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(skip_typescript)]
+pub fn tokenize(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let tokens = tokenize_str(&code).map_err(|e| utils::to_js_error(&e))?;
+    let categorized: Vec<CategorizedToken> = tokens
+        .iter()
+        .zip(token::categorize(&tokens))
+        .map(|(token, category)| CategorizedToken { token, category })
+        .collect();
+    utils::to_js_value(&categorized)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(skip_typescript)]
+pub fn lint(code: String, options: JsValue) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let mut linter = Linter::default();
+    // every rule can be omitted
+    if !options.is_undefined() && !options.is_null() {
+        #[allow(deprecated)]
+        let options: LintOptions = match options.into_serde() {
+            Ok(options) => options,
+            Err(error) => {
+                let message = format!("Invalid lint options: {}", error);
+                return Err(js_sys::Error::new(&message).into());
+            }
+        };
+        if let Err(name) = linter.configure(&options) {
+            let message = format!("Unknown lint rule: {}", name);
+            return Err(js_sys::Error::new(&message).into());
+        }
+    }
+    let diagnostics = lint_str(&code, &linter).map_err(|e| utils::to_js_error(&e))?;
+    utils::to_js_value(&diagnostics)
+}
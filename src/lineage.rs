@@ -0,0 +1,723 @@
+// column-level lineage of queries
+// which columns of which tables each output column of `SELECT` depends on
+// (see tables.rs for table-level references)
+mod tables;
+#[cfg(test)]
+mod tests;
+
+pub use tables::{table_refs, Access, TableRef};
+
+use crate::ast::{
+    Expr, FrameBound, InsertInput, JoinCondition, Path, SelectItem, Statement, TableExpr, Window,
+    With,
+};
+use crate::names::eq;
+use crate::resolve::{self, Found, Relation as _, Scope, Scopes};
+use crate::token::Span;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::hash::BuildHasher;
+
+// column names of tables, used to expand `SELECT *`
+pub trait Schema {
+    // `table` is the name as it is written in the query (e.g. `project.dataset.table`)
+    // None if the table is unknown
+    fn columns(&self, table: &str) -> Option<Vec<String>>;
+}
+
+impl<S: BuildHasher> Schema for HashMap<String, Vec<String>, S> {
+    fn columns(&self, table: &str) -> Option<Vec<String>> {
+        self.get(table).cloned()
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Hash)]
+pub struct ColumnRef {
+    pub table: Option<String>, // None if it cannot be determined (e.g. ambiguous without schema)
+    pub column: String,        // `*` if the columns cannot be determined
+}
+
+impl fmt::Display for ColumnRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.table {
+            Some(table) => write!(f, "{}.{}", table, self.column),
+            None => write!(f, "{}", self.column),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum OutputColumn {
+    Column {
+        name: Option<String>, // None if there is no alias nor implicit name
+        sources: Vec<ColumnRef>,
+        span: Span,
+    },
+    // `SELECT *` which cannot be expanded without schema
+    Unresolved {
+        tables: Vec<String>, // the tables `*` reads from
+        except: Vec<String>,
+        replace: Vec<OutputColumn>,
+        span: Span,
+    },
+}
+
+// the lineage of each output column
+// statements other than queries (and `INSERT ... SELECT`) return an empty vector
+pub fn lineage(stmt: &Statement, schema: Option<&dyn Schema>) -> Vec<OutputColumn> {
+    let mut tracer = Tracer {
+        schema,
+        scopes: Scopes::default(),
+    };
+    tracer
+        .statement(stmt)
+        .into_iter()
+        .map(Output::into_output_column)
+        .collect()
+}
+
+// `project.dataset.table` may be written as one quoted identifier
+fn segments(path: &Path) -> Vec<String> {
+    path.parts
+        .iter()
+        .flat_map(|p| p.value.split('.').map(|s| s.to_string()))
+        .collect()
+}
+
+fn unresolved(name: &str) -> BTreeSet<ColumnRef> {
+    let mut sources = BTreeSet::new();
+    sources.insert(ColumnRef {
+        table: None,
+        column: name.to_string(),
+    });
+    sources
+}
+
+#[derive(Debug, Clone)]
+struct Column {
+    name: Option<String>,
+    sources: BTreeSet<ColumnRef>,
+}
+
+#[derive(Debug, Clone)]
+struct Star {
+    relations: Vec<Relation>,
+    tables: BTreeSet<String>,
+    except: Vec<String>,
+    replace: Vec<(Column, Span)>,
+    span: Span,
+}
+
+#[derive(Debug, Clone)]
+enum Output {
+    Column(Column, Span),
+    Star(Star),
+}
+
+impl Output {
+    fn into_output_column(self) -> OutputColumn {
+        match self {
+            Output::Column(column, span) => OutputColumn::Column {
+                name: column.name,
+                sources: column.sources.into_iter().collect(),
+                span,
+            },
+            Output::Star(star) => OutputColumn::Unresolved {
+                tables: star.tables.into_iter().collect(),
+                except: star.except,
+                replace: star
+                    .replace
+                    .into_iter()
+                    .map(|(c, span)| Output::Column(c, span).into_output_column())
+                    .collect(),
+                span: star.span,
+            },
+        }
+    }
+}
+
+// columns which are not listed in `Relation.columns`
+#[derive(Debug, Clone)]
+enum Unknown {
+    Table(String), // a table whose schema is not supplied
+    Star(Star),
+}
+
+// FROM item (table, CTE, subquery, UNNEST, ...)
+// names are resolved in the same way as `resolve()` (see `resolve::Scopes`)
+#[derive(Debug, Clone, Default)]
+struct Relation {
+    alias: Option<String>,
+    columns: Vec<Column>,
+    unknown: Vec<Unknown>,
+    value: Option<BTreeSet<ColumnRef>>, // UNNEST(arr) AS x (`x` is not a table but a value)
+}
+
+impl Relation {
+    fn from_outputs(outputs: Vec<Output>, alias: Option<String>) -> Relation {
+        let mut relation = Relation {
+            alias,
+            ..Default::default()
+        };
+        for output in outputs {
+            match output {
+                Output::Column(column, _) => relation.columns.push(column),
+                Output::Star(mut star) => {
+                    for (column, _) in &star.replace {
+                        relation.columns.push(column.clone());
+                        star.except.extend(column.name.clone());
+                    }
+                    relation.unknown.push(Unknown::Star(star));
+                }
+            }
+        }
+        relation
+    }
+    fn column(&self, name: &str) -> Option<&Column> {
+        self.columns
+            .iter()
+            .find(|c| c.name.as_deref().is_some_and(|n| eq(n, name)))
+    }
+    // the sources of the column (the columns of unknown tables may be the sources)
+    // None if the relation does not have the column
+    fn sources(&self, name: &str) -> Option<BTreeSet<ColumnRef>> {
+        if let Some(column) = self.column(name) {
+            return Some(column.sources.clone());
+        }
+        let mut found = None;
+        for unknown in &self.unknown {
+            let sources = match unknown {
+                Unknown::Table(table) => {
+                    let mut sources = BTreeSet::new();
+                    sources.insert(ColumnRef {
+                        table: Some(table.clone()),
+                        column: name.to_string(),
+                    });
+                    sources
+                }
+                Unknown::Star(star) => {
+                    if star.except.iter().any(|e| eq(e, name)) {
+                        continue;
+                    }
+                    match find_in(&star.relations, name) {
+                        Some(sources) => sources,
+                        None => continue,
+                    }
+                }
+            };
+            found.get_or_insert_with(BTreeSet::new).extend(sources);
+        }
+        found
+    }
+    fn tables(&self) -> BTreeSet<String> {
+        let mut tables = BTreeSet::new();
+        let sources = self
+            .columns
+            .iter()
+            .flat_map(|c| c.sources.iter())
+            .chain(self.value.iter().flatten());
+        tables.extend(sources.filter_map(|s| s.table.clone()));
+        for unknown in &self.unknown {
+            match unknown {
+                Unknown::Table(table) => {
+                    tables.insert(table.clone());
+                }
+                Unknown::Star(star) => tables.extend(star.tables.iter().cloned()),
+            }
+        }
+        tables
+    }
+}
+
+impl resolve::Relation for Relation {
+    fn name(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
+    fn is_value(&self) -> bool {
+        self.value.is_some()
+    }
+    fn has_column(&self, name: &str) -> Option<bool> {
+        match (self.column(name), self.sources(name)) {
+            (Some(_), _) => Some(true),
+            (None, Some(_)) => None,
+            (None, None) => Some(false),
+        }
+    }
+}
+
+// the sources of the column `name` of the relation found by `resolve::find_column()`
+fn sources(found: Found<Relation>, name: &str) -> BTreeSet<ColumnRef> {
+    match found {
+        Found::Value(relation) => relation.value.clone().unwrap_or_default(),
+        Found::Column(relation) => relation.sources(name).unwrap_or_else(|| unresolved(name)),
+        // JOIN ... USING (col) refers to the columns of both sides
+        Found::Using(relations) => relations
+            .iter()
+            .filter_map(|r| r.sources(name))
+            .flatten()
+            .collect(),
+        Found::Ambiguous(_) => unresolved(name),
+    }
+}
+
+// the column of the relations `*` reads from (None if no relation has the column)
+fn find_in(relations: &[Relation], name: &str) -> Option<BTreeSet<ColumnRef>> {
+    let found = resolve::find_column(relations, &[], name, true)
+        .or_else(|| resolve::find_column(relations, &[], name, false))?;
+    Some(sources(found, name))
+}
+
+// traces the sources of output columns
+struct Tracer<'a> {
+    schema: Option<&'a dyn Schema>,
+    scopes: Scopes<Relation>,
+}
+
+impl<'a> Tracer<'a> {
+    // returns the number of CTEs before the WITH clause
+    fn push_with(&mut self, with: &Option<With>) -> usize {
+        let len = self.scopes.ctes.len();
+        if let Some(with) = with {
+            for cte in &with.ctes {
+                let outputs = self.statement(&cte.stmt);
+                let relation = Relation::from_outputs(outputs, Some(cte.name.value.clone()));
+                self.scopes.ctes.push(relation);
+            }
+        }
+        len
+    }
+    fn statement(&mut self, stmt: &Statement) -> Vec<Output> {
+        match stmt {
+            Statement::Select(select) => {
+                let len = self.push_with(&select.with);
+                self.scopes.scopes.push(Scope::default());
+                if let Some(from) = &select.from {
+                    self.from(from);
+                }
+                let mut outputs = Vec::new();
+                for item in &select.exprs {
+                    self.select_item(item, &mut outputs);
+                }
+                self.scopes.scopes.pop();
+                self.scopes.ctes.truncate(len);
+                // SELECT AS STRUCT | SELECT AS VALUE returns only one column
+                let all_columns = outputs.iter().all(|o| matches!(o, Output::Column(..)));
+                if select.as_struct_or_value.is_some() && all_columns {
+                    let mut sources = BTreeSet::new();
+                    for output in outputs {
+                        if let Output::Column(column, _) = output {
+                            sources.extend(column.sources);
+                        }
+                    }
+                    let column = Column {
+                        name: None,
+                        sources,
+                    };
+                    outputs = vec![Output::Column(column, select.span.clone())];
+                }
+                outputs
+            }
+            Statement::SetOperation(set) => {
+                let len = self.push_with(&set.with);
+                let left = self.statement(&set.left);
+                let right = self.statement(&set.right);
+                self.scopes.ctes.truncate(len);
+                let all_columns = left
+                    .iter()
+                    .chain(right.iter())
+                    .all(|o| matches!(o, Output::Column(..)));
+                if all_columns && left.len() == right.len() {
+                    // columns are matched by position and named after the left side
+                    left.into_iter()
+                        .zip(right)
+                        .map(|pair| match pair {
+                            (Output::Column(mut l, span), Output::Column(r, _)) => {
+                                l.sources.extend(r.sources);
+                                Output::Column(l, span)
+                            }
+                            _ => unreachable!(),
+                        })
+                        .collect()
+                } else {
+                    let relations = vec![
+                        Relation::from_outputs(left, None),
+                        Relation::from_outputs(right, None),
+                    ];
+                    let tables = relations.iter().flat_map(|r| r.tables()).collect();
+                    vec![Output::Star(Star {
+                        relations,
+                        tables,
+                        except: Vec::new(),
+                        replace: Vec::new(),
+                        span: set.span.clone(),
+                    })]
+                }
+            }
+            Statement::Grouped(grouped) => self.statement(&grouped.stmt),
+            Statement::Insert(insert) => match &insert.input {
+                InsertInput::Query(query) => self.statement(query),
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        }
+    }
+    fn push_relation(&mut self, relation: Relation) {
+        self.scopes.push_relation(relation);
+    }
+    fn from(&mut self, table_expr: &TableExpr) {
+        match table_expr {
+            TableExpr::Table { name, alias, .. } => {
+                let alias = alias.as_ref().map(|a| a.value.clone());
+                let relation = self.table(name, alias);
+                self.push_relation(relation);
+            }
+            TableExpr::Subquery { stmt, alias, .. } => {
+                let outputs = self.statement(stmt);
+                let alias = alias.as_ref().map(|a| a.value.clone());
+                self.push_relation(Relation::from_outputs(outputs, alias));
+            }
+            TableExpr::Unnest {
+                expr,
+                alias,
+                with_offset,
+                offset_alias,
+                ..
+            } => {
+                let sources = self.expr(expr);
+                self.push_relation(Relation {
+                    alias: alias.as_ref().map(|a| a.value.clone()),
+                    value: Some(sources),
+                    ..Default::default()
+                });
+                if *with_offset {
+                    let offset_alias = match offset_alias {
+                        Some(alias) => alias.value.clone(),
+                        None => "offset".to_string(),
+                    };
+                    self.push_relation(Relation {
+                        alias: Some(offset_alias),
+                        value: Some(BTreeSet::new()),
+                        ..Default::default()
+                    });
+                }
+            }
+            TableExpr::Call { func, alias, .. } => {
+                // the output of table functions is unknown
+                let alias = alias.as_ref().map(|a| a.value.clone());
+                self.push_relation(Relation {
+                    alias,
+                    unknown: vec![Unknown::Table(func.to_string())],
+                    ..Default::default()
+                });
+            }
+            TableExpr::Join {
+                left,
+                right,
+                condition,
+                ..
+            } => {
+                self.from(left);
+                self.from(right);
+                if let Some(JoinCondition::Using(columns)) = condition {
+                    let using = &mut self.scopes.innermost_mut().using;
+                    using.extend(columns.iter().map(|c| c.value.clone()));
+                }
+            }
+        }
+    }
+    fn table(&self, name: &Path, alias: Option<String>) -> Relation {
+        let parts = segments(name);
+        let alias = alias.or_else(|| parts.last().cloned());
+        if parts.len() == 1 {
+            if let Some(cte) = self.scopes.find_cte(&parts[0]) {
+                return Relation {
+                    alias,
+                    ..cte.clone()
+                };
+            }
+        }
+        // FROM t, t.arr AS x (implicit UNNEST)
+        if parts.len() > 1 && self.scopes.find_relation(&parts[0]).is_some() {
+            return Relation {
+                alias,
+                value: Some(self.path(name)),
+                ..Default::default()
+            };
+        }
+        let table = name.to_string();
+        match self.schema.and_then(|s| s.columns(&table)) {
+            Some(columns) => Relation {
+                alias,
+                columns: columns
+                    .into_iter()
+                    .map(|column| {
+                        let mut sources = BTreeSet::new();
+                        sources.insert(ColumnRef {
+                            table: Some(table.clone()),
+                            column: column.clone(),
+                        });
+                        Column {
+                            name: Some(column),
+                            sources,
+                        }
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+            None => Relation {
+                alias,
+                unknown: vec![Unknown::Table(table)],
+                ..Default::default()
+            },
+        }
+    }
+    fn select_item(&mut self, item: &SelectItem, outputs: &mut Vec<Output>) {
+        let (qualifier, except, replace, span) = match item {
+            SelectItem::Expr(aliased) => {
+                let column = Column {
+                    name: aliased.name.clone(),
+                    sources: self.expr(&aliased.expr),
+                };
+                outputs.push(Output::Column(column, aliased.expr.span().clone()));
+                return;
+            }
+            SelectItem::Wildcard {
+                qualifier,
+                except,
+                replace,
+                span,
+            } => (qualifier, except, replace, span),
+        };
+        let except: Vec<String> = except.iter().map(|e| e.value.clone()).collect();
+        let replace: Vec<(Column, Span)> = replace
+            .iter()
+            .map(|r| {
+                let column = Column {
+                    name: r.alias.as_ref().map(|a| a.value.clone()),
+                    sources: self.expr(&r.expr),
+                };
+                (column, r.expr.span().clone())
+            })
+            .collect();
+        let scope = self.scopes.innermost().unwrap();
+        let relations: Vec<Relation> = match qualifier {
+            None => scope.relations.clone(),
+            Some(Expr::Path(path))
+                if path.parts.len() == 1
+                    && scope
+                        .relations
+                        .iter()
+                        .any(|r| r.is_named(&path.parts[0].value)) =>
+            {
+                let relation = scope
+                    .relations
+                    .iter()
+                    .find(|r| r.is_named(&path.parts[0].value))
+                    .unwrap();
+                vec![relation.clone()]
+            }
+            // struct_column.*
+            Some(expr) => {
+                let tables = self.expr(expr).into_iter().filter_map(|s| s.table);
+                outputs.push(Output::Star(Star {
+                    relations: Vec::new(),
+                    tables: tables.collect(),
+                    except,
+                    replace,
+                    span: span.clone(),
+                }));
+                return;
+            }
+        };
+        if relations.iter().any(|r| !r.unknown.is_empty()) {
+            outputs.push(Output::Star(Star {
+                tables: relations.iter().flat_map(|r| r.tables()).collect(),
+                relations,
+                except,
+                replace,
+                span: span.clone(),
+            }));
+            return;
+        }
+        // expand `*`
+        let using = &scope.using;
+        let mut expanded: Vec<String> = Vec::new();
+        for relation in relations {
+            let columns = match relation.value {
+                Some(sources) => vec![Column {
+                    name: relation.alias,
+                    sources,
+                }],
+                None => relation.columns,
+            };
+            for column in columns {
+                let name = column.name.clone().unwrap_or_default();
+                // the columns of JOIN ... USING appear only once
+                if using.iter().any(|u| eq(u, &name)) && expanded.iter().any(|e| eq(e, &name)) {
+                    continue;
+                }
+                expanded.push(name.clone());
+                if except.iter().any(|e| eq(e, &name)) {
+                    continue;
+                }
+                let replaced = replace
+                    .iter()
+                    .find(|(c, _)| c.name.as_deref().is_some_and(|n| eq(n, &name)));
+                match replaced {
+                    Some((column, span)) => {
+                        outputs.push(Output::Column(column.clone(), span.clone()))
+                    }
+                    None => outputs.push(Output::Column(column, span.clone())),
+                }
+            }
+        }
+    }
+    // source columns of the path (col | t.col | col.field | ...)
+    fn path(&self, path: &Path) -> BTreeSet<ColumnRef> {
+        let parts = segments(path);
+        if parts.len() > 1 {
+            if let Some(relation) = self.scopes.find_relation(&parts[0]) {
+                if let Some(sources) = &relation.value {
+                    return sources.clone();
+                }
+                return relation
+                    .sources(&parts[1])
+                    .unwrap_or_else(|| unresolved(&parts[1]));
+            }
+        }
+        let found = self
+            .scopes
+            .find_column(&parts[0], true)
+            .or_else(|| self.scopes.find_column(&parts[0], false));
+        match found {
+            Some(found) => sources(found, &parts[0]),
+            None => unresolved(&parts[0]),
+        }
+    }
+    // source columns of the expression
+    fn expr(&mut self, expr: &Expr) -> BTreeSet<ColumnRef> {
+        let mut sources = BTreeSet::new();
+        self.collect(expr, &mut sources);
+        sources
+    }
+    fn collect(&mut self, expr: &Expr, sources: &mut BTreeSet<ColumnRef>) {
+        match expr {
+            Expr::Path(path) => sources.extend(self.path(path)),
+            Expr::Literal(_)
+            | Expr::TypedLiteral { .. }
+            | Expr::Parameter { .. }
+            | Expr::Wildcard { .. } => (),
+            Expr::Interval { value, .. } => self.collect(value, sources),
+            Expr::Unary { expr, .. } | Expr::Field { expr, .. } => self.collect(expr, sources),
+            Expr::NamedArgument { value, .. } => self.collect(value, sources),
+            Expr::Binary { left, right, .. } => {
+                self.collect(left, sources);
+                self.collect(right, sources);
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                self.collect(expr, sources);
+                self.collect(low, sources);
+                self.collect(high, sources);
+            }
+            Expr::In { expr, list, .. } => {
+                self.collect(expr, sources);
+                for e in list {
+                    self.collect(e, sources);
+                }
+            }
+            Expr::Case {
+                operand,
+                whens,
+                else_,
+                ..
+            } => {
+                for e in operand.iter().chain(else_.iter()) {
+                    self.collect(e, sources);
+                }
+                for when in whens {
+                    self.collect(&when.condition, sources);
+                    self.collect(&when.result, sources);
+                }
+            }
+            Expr::Call {
+                args,
+                orderby,
+                limit,
+                over,
+                ..
+            } => {
+                for e in args {
+                    self.collect(e, sources);
+                }
+                for o in orderby {
+                    self.collect(&o.expr, sources);
+                }
+                if let Some(e) = limit {
+                    self.collect(e, sources);
+                }
+                if let Some(Window::Spec(spec)) = over.as_deref() {
+                    for e in &spec.partitionby {
+                        self.collect(e, sources);
+                    }
+                    for o in &spec.orderby {
+                        self.collect(&o.expr, sources);
+                    }
+                    if let Some(frame) = &spec.frame {
+                        for bound in Some(&frame.start).into_iter().chain(frame.end.iter()) {
+                            if let FrameBound::Preceding(e) | FrameBound::Following(e) = bound {
+                                self.collect(e, sources);
+                            }
+                        }
+                    }
+                }
+            }
+            Expr::Cast { expr, format, .. } => {
+                self.collect(expr, sources);
+                if let Some(e) = format {
+                    self.collect(e, sources);
+                }
+            }
+            Expr::Extract {
+                expr, time_zone, ..
+            } => {
+                self.collect(expr, sources);
+                if let Some(e) = time_zone {
+                    self.collect(e, sources);
+                }
+            }
+            Expr::Array { elements, .. } => {
+                for e in elements {
+                    self.collect(e, sources);
+                }
+            }
+            Expr::Struct { fields, .. } => {
+                for f in fields {
+                    self.collect(&f.expr, sources);
+                }
+            }
+            Expr::Index { expr, index, .. } => {
+                self.collect(expr, sources);
+                self.collect(index, sources);
+            }
+            // every column the subquery returns
+            Expr::Subquery { stmt, .. } => {
+                for output in self.statement(stmt) {
+                    match output {
+                        Output::Column(column, _) => sources.extend(column.sources),
+                        Output::Star(star) => {
+                            sources.extend(star.tables.into_iter().map(|table| ColumnRef {
+                                table: Some(table),
+                                column: "*".to_string(),
+                            }));
+                            for (column, _) in star.replace {
+                                sources.extend(column.sources);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,191 @@
+// tables referenced by statements (DDL and scripting are also supported, so this works on CST)
+use crate::cst::{Node, NodeType};
+use crate::token::{Span, TokenKind};
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct TableRef {
+    pub name: String, // `project.dataset.table` (without backquotes)
+    pub access: Access,
+    pub span: Span, // the name of the table (not including alias)
+}
+
+// every table the statement reads or writes, in source order
+// CTE names are filtered out
+pub fn table_refs(stmt: &Node) -> Vec<TableRef> {
+    let mut collector = Collector {
+        ctes: Vec::new(),
+        aliases: Vec::new(),
+        refs: Vec::new(),
+    };
+    collector.node(stmt);
+    collector.refs
+}
+
+fn unquote(node: &Node) -> Option<String> {
+    let token = node.token.as_ref()?;
+    if token.kind == TokenKind::QuotedIdent {
+        Some(token.literal[1..token.literal.len() - 1].to_string())
+    } else {
+        Some(token.literal.clone())
+    }
+}
+
+// `a.b.c` is parsed as nested `DotOperator`
+fn path(node: &Node, parts: &mut Vec<String>, spans: &mut Vec<Span>) -> Option<()> {
+    match node.node_type {
+        NodeType::Identifier => {
+            parts.push(unquote(node)?);
+            spans.push(node.token.as_ref()?.span());
+        }
+        NodeType::DotOperator => {
+            path(node.get_node("left")?, parts, spans)?;
+            path(node.get_node("right")?, parts, spans)?;
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+fn table_name(node: &Node) -> Option<(Vec<String>, Span)> {
+    let mut parts = Vec::new();
+    let mut spans = Vec::new();
+    path(node, &mut parts, &mut spans)?;
+    let first = spans.first()?;
+    let last = spans.last()?;
+    let span = Span {
+        line: first.line,
+        column: first.column,
+        end_line: last.end_line,
+        end_column: last.end_column,
+        start: first.start,
+        end: last.end,
+        start_utf16: first.start_utf16,
+        end_utf16: last.end_utf16,
+    };
+    Some((parts, span))
+}
+
+struct Collector {
+    ctes: Vec<String>,
+    aliases: Vec<String>, // FROM t, t.arr (`t.arr` is not a table)
+    refs: Vec<TableRef>,
+}
+
+impl Collector {
+    fn push(&mut self, node: &Node, access: Access) {
+        if let Some((parts, span)) = table_name(node) {
+            self.refs.push(TableRef {
+                name: parts.join("."),
+                access,
+                span,
+            });
+        }
+    }
+    fn node(&mut self, node: &Node) {
+        let ctes = self.ctes.len();
+        let aliases = self.aliases.len();
+        // CTE names are visible in the whole statement
+        if let Some(with) = node.get_node("with") {
+            for query in with.get_node_vec("queries").into_iter().flatten() {
+                self.ctes.extend(unquote(query));
+            }
+        }
+        match node.node_type {
+            NodeType::InsertStatement => {
+                if let Some(target) = node.get_node("target_name") {
+                    self.push(target, Access::Write);
+                }
+            }
+            NodeType::UpdateStatement
+            | NodeType::DeleteStatement
+            | NodeType::MergeStatement
+            | NodeType::TruncateStatement => {
+                if let Some(target) = node.get_node("table_name") {
+                    self.push(target, Access::Write);
+                }
+            }
+            NodeType::CreateTableStatement
+            | NodeType::CreateViewStatement
+            | NodeType::AlterTableStatement => {
+                if let Some(ident) = node.get_node("ident") {
+                    self.push(ident, Access::Write);
+                }
+            }
+            NodeType::DropStatement => {
+                let what = node.get_node("what").and_then(|w| w.token.as_ref());
+                if what.is_some_and(|w| w.in_(&vec!["TABLE", "VIEW"])) {
+                    self.push(node.get_node("ident").unwrap(), Access::Write);
+                }
+            }
+            _ => (),
+        }
+        for (key, child) in node.children_in_source_order() {
+            let is_table_expr = match (&node.node_type, key) {
+                (NodeType::SelectStatement, "from") | (NodeType::UpdateStatement, "from") => {
+                    child.node_type == NodeType::KeywordWithExpr
+                }
+                (NodeType::MergeStatement, "using") | (NodeType::CreateTableStatement, "clone") => {
+                    true
+                }
+                _ => false,
+            };
+            match child.get_node("expr") {
+                Some(expr) if is_table_expr => self.table_expr(expr),
+                _ => self.node(child),
+            }
+        }
+        self.ctes.truncate(ctes);
+        self.aliases.truncate(aliases);
+    }
+    fn table_expr(&mut self, node: &Node) {
+        match node.node_type {
+            NodeType::JoinOperator => {
+                for (key, child) in node.children_in_source_order() {
+                    match key {
+                        "left" | "right" => self.table_expr(child),
+                        _ => self.node(child),
+                    }
+                }
+            }
+            NodeType::Identifier | NodeType::DotOperator => {
+                let (parts, _) = match table_name(node) {
+                    Some(name) => name,
+                    None => return self.node(node),
+                };
+                let is_cte = parts.len() == 1 && contains(&self.ctes, &parts[0]);
+                let is_unnest = parts.len() > 1 && contains(&self.aliases, &parts[0]);
+                if !is_cte && !is_unnest {
+                    self.push(node, Access::Read);
+                }
+                match node.get_node("alias") {
+                    Some(alias) => self.aliases.extend(unquote(alias)),
+                    None => self.aliases.extend(parts.last().cloned()),
+                }
+                // FOR SYSTEM_TIME AS OF, PIVOT, ...
+                for (key, child) in node.children_in_source_order() {
+                    if key != "left" && key != "right" {
+                        self.node(child);
+                    }
+                }
+            }
+            // (SELECT ...) AS s, UNNEST(...) AS x, ...
+            _ => {
+                if let Some(alias) = node.get_node("alias") {
+                    self.aliases.extend(unquote(alias));
+                }
+                self.node(node)
+            }
+        }
+    }
+}
+
+// names are case insensitive
+fn contains(names: &[String], name: &str) -> bool {
+    names.iter().any(|n| n.eq_ignore_ascii_case(name))
+}
@@ -0,0 +1,247 @@
+use super::*;
+use crate::ast::lower;
+use crate::parser::Parser;
+
+// one line per output column
+fn test_lineage(code: &str, schema: Option<&dyn Schema>, expected: &str) {
+    let stmts = Parser::new(code.to_string()).unwrap().parse_code().unwrap();
+    let stmt = lower(&stmts).unwrap().remove(0);
+    fn describe(column: &OutputColumn) -> String {
+        match column {
+            OutputColumn::Column { name, sources, .. } => {
+                let sources: Vec<String> = sources.iter().map(|s| s.to_string()).collect();
+                let res = format!(
+                    "{} <- {}",
+                    name.as_deref().unwrap_or("?"),
+                    sources.join(", ")
+                );
+                res.trim_end().to_string()
+            }
+            OutputColumn::Unresolved {
+                tables,
+                except,
+                replace,
+                ..
+            } => {
+                let mut res = format!("* <- {}", tables.join(", "));
+                if !except.is_empty() {
+                    res += &format!(" EXCEPT ({})", except.join(", "));
+                }
+                for r in replace {
+                    res += &format!(" REPLACE ({})", describe(r));
+                }
+                res
+            }
+        }
+    }
+    let result: Vec<String> = lineage(&stmt, schema).iter().map(describe).collect();
+    assert_eq!(expected, result.join("\n"), "{}", code);
+}
+
+fn schema() -> HashMap<String, Vec<String>> {
+    let mut schema = HashMap::new();
+    let columns = |cs: &[&str]| cs.iter().map(|c| c.to_string()).collect();
+    schema.insert("p.d.t1".to_string(), columns(&["id", "a", "b"]));
+    schema.insert("p.d.t2".to_string(), columns(&["id", "c"]));
+    schema
+}
+
+#[test]
+fn test_lineage_select() {
+    test_lineage(
+        "SELECT a, t.b AS bb, a + b, 1 AS one, s.f FROM `p.d.t1` AS t",
+        None,
+        "\
+a <- p.d.t1.a
+bb <- p.d.t1.b
+? <- p.d.t1.a, p.d.t1.b
+one <-
+f <- p.d.t1.s",
+    );
+    // ambiguous without schema
+    test_lineage(
+        "SELECT x.a, b, COUNT(*) OVER (PARTITION BY id) c FROM p.d.t1 x JOIN p.d.t2 y USING (id)",
+        None,
+        "\
+a <- p.d.t1.a
+b <- b
+c <- p.d.t1.id, p.d.t2.id",
+    );
+    let schema = schema();
+    test_lineage(
+        "SELECT b, c FROM p.d.t1 x JOIN p.d.t2 y ON x.id = y.id",
+        Some(&schema),
+        "\
+b <- p.d.t1.b
+c <- p.d.t2.c",
+    );
+}
+
+#[test]
+fn test_lineage_cte_and_subquery() {
+    test_lineage(
+        "\
+WITH cte AS (SELECT a AS x, b FROM p.d.t1)
+SELECT
+  x,
+  sub.y,
+  (SELECT MAX(c) FROM p.d.t2 WHERE p.d.t2.id = cte.b) AS m
+FROM cte, (SELECT UPPER(c) y FROM p.d.t2) AS sub",
+        None,
+        "\
+x <- p.d.t1.a
+y <- p.d.t2.c
+m <- p.d.t2.c",
+    );
+    // UNION is matched by position
+    test_lineage(
+        "SELECT a FROM p.d.t1 UNION ALL (SELECT c AS d FROM p.d.t2)",
+        None,
+        "a <- p.d.t1.a, p.d.t2.c",
+    );
+    // UNNEST
+    test_lineage(
+        "SELECT x, i, e.f FROM p.d.t1 t, UNNEST(t.arr) AS x WITH OFFSET AS i, t.structs e",
+        None,
+        "\
+x <- p.d.t1.arr
+i <-
+f <- p.d.t1.structs",
+    );
+    // INSERT ... SELECT
+    test_lineage(
+        "INSERT INTO p.d.t3 (x) SELECT a FROM p.d.t1",
+        None,
+        "a <- p.d.t1.a",
+    );
+    test_lineage("DELETE FROM p.d.t1 WHERE true", None, "");
+}
+
+#[test]
+fn test_lineage_wildcard() {
+    // without schema
+    test_lineage(
+        "SELECT * EXCEPT (a), t.* REPLACE (b + 1 AS b), id FROM p.d.t1 t",
+        None,
+        "\
+* <- p.d.t1 EXCEPT (a)
+* <- p.d.t1 REPLACE (b <- p.d.t1.b)
+id <- p.d.t1.id",
+    );
+    test_lineage(
+        "WITH cte AS (SELECT * FROM p.d.t1) SELECT a FROM cte",
+        None,
+        "a <- p.d.t1.a",
+    );
+    // with schema
+    let schema = schema();
+    test_lineage(
+        "SELECT * EXCEPT (a) FROM p.d.t1 JOIN p.d.t2 USING (id)",
+        Some(&schema),
+        "\
+id <- p.d.t1.id
+b <- p.d.t1.b
+c <- p.d.t2.c",
+    );
+    test_lineage(
+        "SELECT * REPLACE (a + id AS b) FROM p.d.t1",
+        Some(&schema),
+        "\
+id <- p.d.t1.id
+a <- p.d.t1.a
+b <- p.d.t1.a, p.d.t1.id",
+    );
+    test_lineage(
+        "WITH cte AS (SELECT *, 1 AS one FROM p.d.t2) SELECT cte.*, t.a FROM cte, p.d.t1 AS t",
+        Some(&schema),
+        "\
+id <- p.d.t2.id
+c <- p.d.t2.c
+one <-
+a <- p.d.t1.a",
+    );
+    // unknown table
+    test_lineage(
+        "SELECT * FROM p.d.t1, p.d.unknown",
+        Some(&schema),
+        "* <- p.d.t1, p.d.unknown",
+    );
+}
+
+fn test_table_refs(code: &str, expected: &str) {
+    let stmts = Parser::new(code.to_string()).unwrap().parse_code().unwrap();
+    let refs: Vec<String> = stmts
+        .iter()
+        .flat_map(table_refs)
+        .map(|r| {
+            let access = match r.access {
+                Access::Read => "read",
+                Access::Write => "write",
+            };
+            format!("{} {} ({}:{})", access, r.name, r.span.line, r.span.column)
+        })
+        .collect();
+    assert_eq!(expected, refs.join("\n"), "{}", code);
+}
+
+#[test]
+fn test_table_refs_query() {
+    test_table_refs(
+        "\
+WITH cte AS (SELECT * FROM `p.d`.t1)
+SELECT (SELECT MAX(x) FROM p.d.t2)
+FROM cte
+JOIN (SELECT 1 FROM Cte, `p.d.t3` FOR SYSTEM_TIME AS OF CURRENT_TIMESTAMP()) AS sub ON TRUE,
+  sub.arr,
+  UNNEST(cte.arr) AS x,
+  x.y",
+        "\
+read p.d.t1 (1:28)
+read p.d.t2 (2:28)
+read p.d.t3 (4:26)",
+    );
+    // CTE names are visible only in the statement
+    test_table_refs(
+        "WITH t AS (SELECT 1) SELECT * FROM t; SELECT * FROM t;",
+        "read t (1:53)",
+    );
+}
+
+#[test]
+fn test_table_refs_dml_and_ddl() {
+    test_table_refs(
+        "\
+INSERT INTO t1 SELECT * FROM t2;
+UPDATE t1 SET a = 1 FROM t2 WHERE t1.id = t2.id;
+DELETE FROM t1 WHERE id IN (SELECT id FROM t2);
+MERGE t1 USING (SELECT * FROM t2) AS s ON FALSE WHEN NOT MATCHED THEN INSERT ROW;
+TRUNCATE TABLE t1;
+CREATE TABLE t1 AS WITH t2 AS (SELECT 1) SELECT * FROM t2;
+CREATE TABLE t1 CLONE t2;
+CREATE VIEW v AS SELECT 1 FROM t2;
+ALTER TABLE t1 SET OPTIONS ();
+DROP TABLE t1;
+DROP SCHEMA s;
+BEGIN
+  SELECT * FROM t3;
+END;",
+        "\
+write t1 (1:13)
+read t2 (1:30)
+write t1 (2:8)
+read t2 (2:26)
+write t1 (3:13)
+read t2 (3:44)
+write t1 (4:7)
+read t2 (4:31)
+write t1 (5:16)
+write t1 (6:14)
+write t1 (7:14)
+read t2 (7:23)
+write v (8:13)
+read t2 (8:32)
+write t1 (9:13)
+write t1 (10:12)
+read t3 (13:17)",
+    );
+}
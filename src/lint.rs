@@ -0,0 +1,270 @@
+// lint rules over CST
+// diagnostics can be suppressed by `-- bq2cst-disable-next-line rule-a, rule-b`
+// (every rule is suppressed if no rule is specified)
+mod rules;
+#[cfg(test)]
+mod tests;
+
+use crate::catalog::Catalog;
+use crate::cst::{Node, NodeType};
+use crate::infer::{self, Types};
+use crate::token::{Span, Token, TokenKind};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const DISABLE_NEXT_LINE: &str = "bq2cst-disable-next-line";
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Severity {
+    Off,
+    Warning,
+    Error,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Diagnostic {
+    pub rule: String,
+    pub severity: Severity, // never `Off`
+    pub message: String,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub span: Span,
+}
+
+// reported by `Rule::check()`, the rule name and severity are filled by `Linter`
+#[derive(PartialEq, Debug, Clone)]
+pub struct Violation {
+    pub span: Span,
+    pub message: String,
+}
+
+impl Violation {
+    // the span of `node` (comments and the trailing comma are excluded)
+    pub fn new(node: &Node, message: &str) -> Violation {
+        let mut tokens = Vec::new();
+        collect_tokens(node, &mut tokens);
+        tokens.sort_by_key(|t| t.start);
+        let span = match (tokens.first(), tokens.last()) {
+            (Some(first), Some(last)) => Span {
+                end_line: last.end_line,
+                end_column: last.end_column,
+                end: last.end,
+                end_utf16: last.end_utf16,
+                ..first.span()
+            },
+            _ => Token::eof(1, 1).span(),
+        };
+        Violation {
+            span,
+            message: message.to_string(),
+        }
+    }
+    // the span of the single token (e.g. the keyword of the node)
+    pub fn at_token(token: &Token, message: &str) -> Violation {
+        Violation {
+            span: token.span(),
+            message: message.to_string(),
+        }
+    }
+}
+
+fn collect_tokens<'a>(node: &'a Node, tokens: &mut Vec<&'a Token>) {
+    tokens.extend(node.token.as_ref());
+    for (key, child) in node.children_in_source_order() {
+        if !is_comment_key(key) && key != "comma" && key != "semicolon" {
+            collect_tokens(child, tokens);
+        }
+    }
+}
+
+fn is_comment_key(key: &str) -> bool {
+    key == "leading_comments" || key == "trailing_comments"
+}
+
+pub trait Rule {
+    // kebab-case, used in `LintOptions` and suppression comments
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    // called for every node except comments
+    // `ancestors` are ordered from the statement to the parent of `node`
+    fn check(&self, node: &Node, ancestors: &[&Node], violations: &mut Vec<Violation>);
+    // called instead of `check()`
+    // implement this if the rule depends on the types of expressions
+    fn check_with_types(
+        &self,
+        node: &Node,
+        ancestors: &[&Node],
+        _types: &Types,
+        violations: &mut Vec<Violation>,
+    ) {
+        self.check(node, ancestors, violations)
+    }
+}
+
+// severities which override the default ones, e.g. { "comma-join": "off" }
+#[derive(PartialEq, Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct LintOptions {
+    pub rules: HashMap<String, Severity>,
+}
+
+// the registry of rules
+pub struct Linter {
+    rules: Vec<(Box<dyn Rule>, Severity)>,
+    catalog: Catalog, // used to infer the types of columns
+}
+
+// every built-in rule with its default severity
+impl Default for Linter {
+    fn default() -> Linter {
+        let mut linter = Linter::empty();
+        for rule in rules::builtin() {
+            linter.register(rule);
+        }
+        linter
+    }
+}
+
+impl Linter {
+    pub fn empty() -> Linter {
+        Linter {
+            rules: Vec::new(),
+            catalog: Catalog::new(),
+        }
+    }
+    pub fn set_catalog(&mut self, catalog: Catalog) {
+        self.catalog = catalog;
+    }
+    // a rule with the same name is replaced
+    pub fn register(&mut self, rule: Box<dyn Rule>) {
+        let severity = rule.default_severity();
+        self.rules.retain(|(r, _)| r.name() != rule.name());
+        self.rules.push((rule, severity));
+    }
+    // false if the rule is not registered
+    pub fn set_severity(&mut self, name: &str, severity: Severity) -> bool {
+        match self.rules.iter_mut().find(|(r, _)| r.name() == name) {
+            Some((_, s)) => {
+                *s = severity;
+                true
+            }
+            None => false,
+        }
+    }
+    // Err(name) if an unknown rule is specified
+    pub fn configure(&mut self, options: &LintOptions) -> Result<(), String> {
+        // sorted to return the same error every time
+        let mut names: Vec<&String> = options.rules.keys().collect();
+        names.sort();
+        for name in names {
+            if !self.set_severity(name, options.rules[name]) {
+                return Err(name.clone());
+            }
+        }
+        Ok(())
+    }
+    pub fn rules(&self) -> impl Iterator<Item = (&dyn Rule, Severity)> {
+        self.rules.iter().map(|(r, s)| (r.as_ref(), *s))
+    }
+    // `stmts` is expected to be the result of `Parser::parse_code()`
+    // diagnostics are sorted by position
+    pub fn lint(&self, stmts: &[Node]) -> Vec<Diagnostic> {
+        let mut walker = Walker {
+            rules: self
+                .rules
+                .iter()
+                .filter(|(_, s)| *s != Severity::Off)
+                .map(|(r, s)| (r.as_ref(), *s))
+                .collect(),
+            types: infer::infer(stmts, &self.catalog),
+            ancestors: Vec::new(),
+            diagnostics: Vec::new(),
+            suppressions: Vec::new(),
+        };
+        for stmt in stmts {
+            walker.node(stmt);
+        }
+        let Walker {
+            mut diagnostics,
+            suppressions,
+            ..
+        } = walker;
+        diagnostics.retain(|d| {
+            !suppressions.iter().any(|(line, rules)| {
+                *line == d.span.line && (rules.is_empty() || rules.contains(&d.rule))
+            })
+        });
+        diagnostics.sort_by_key(|d| d.span.start);
+        diagnostics
+    }
+}
+
+struct Walker<'a> {
+    rules: Vec<(&'a dyn Rule, Severity)>,
+    types: Types,
+    ancestors: Vec<&'a Node>,
+    diagnostics: Vec<Diagnostic>,
+    suppressions: Vec<(usize, Vec<String>)>, // (the suppressed line, rules)
+}
+
+impl<'a> Walker<'a> {
+    fn node(&mut self, node: &'a Node) {
+        if node.token.is_some() && node.node_type != NodeType::EOF {
+            for (rule, severity) in &self.rules {
+                let mut violations = Vec::new();
+                rule.check_with_types(node, &self.ancestors, &self.types, &mut violations);
+                self.diagnostics
+                    .extend(violations.into_iter().map(|v| Diagnostic {
+                        rule: rule.name().to_string(),
+                        severity: *severity,
+                        message: v.message,
+                        span: v.span,
+                    }));
+            }
+        }
+        self.ancestors.push(node);
+        for (key, child) in node.children_in_source_order() {
+            if is_comment_key(key) {
+                self.comment(child);
+            } else {
+                self.node(child);
+            }
+        }
+        self.ancestors.pop();
+    }
+    fn comment(&mut self, node: &Node) {
+        let token = match &node.token {
+            Some(token) => token,
+            None => return,
+        };
+        let text = match token.kind {
+            TokenKind::Comment { .. } => token.literal.as_str(),
+            _ => return,
+        };
+        let text = text
+            .trim_start_matches("--")
+            .trim_start_matches('#')
+            .trim_start_matches("/*")
+            .trim_end_matches("*/")
+            .trim();
+        if let Some(rules) = text.strip_prefix(DISABLE_NEXT_LINE) {
+            // `bq2cst-disable-next-line-xxx` is not a suppression comment
+            if !rules.is_empty() && !rules.starts_with(char::is_whitespace) {
+                return;
+            }
+            let rules = rules
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|r| !r.is_empty())
+                .map(|r| r.to_string())
+                .collect();
+            self.suppressions.push((token.end_line + 1, rules));
+        }
+    }
+}
@@ -0,0 +1,347 @@
+// built-in rules
+use super::{Rule, Severity, Violation};
+use crate::cst::{Node, NodeType};
+use crate::infer::Types;
+
+pub fn builtin() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(SelectStarInView),
+        Box::new(BareSetOperator),
+        Box::new(CommaJoin),
+        Box::new(UnaliasedExpression),
+        Box::new(OrderByWithoutLimit),
+        Box::new(DmlWithoutWhere),
+        Box::new(IncompatibleComparison),
+    ]
+}
+
+// the nearest ancestor which is not a part of the query
+// (parentheses and set operators are skipped)
+fn query_parent<'a>(ancestors: &[&'a Node]) -> Option<&'a Node> {
+    ancestors
+        .iter()
+        .rev()
+        .find(|n| n.node_type != NodeType::GroupedStatement && n.node_type != NodeType::SetOperator)
+        .copied()
+}
+
+fn is_asterisk(expr: &Node) -> bool {
+    match expr.node_type {
+        NodeType::Asterisk => true,
+        NodeType::DotOperator => expr.get_node("right").is_some_and(is_asterisk),
+        _ => false,
+    }
+}
+
+// SELECT * in a view is expanded when the view is created,
+// so the view is not updated even if the columns of the table change
+struct SelectStarInView;
+
+impl Rule for SelectStarInView {
+    fn name(&self) -> &'static str {
+        "select-star-in-view"
+    }
+    fn description(&self) -> &'static str {
+        "`SELECT *` in `CREATE VIEW`"
+    }
+    fn check(&self, node: &Node, ancestors: &[&Node], violations: &mut Vec<Violation>) {
+        if node.node_type != NodeType::SelectStatement
+            || !ancestors
+                .iter()
+                .any(|n| n.node_type == NodeType::CreateViewStatement)
+        {
+            return;
+        }
+        for expr in node.get_node_vec("exprs").into_iter().flatten() {
+            if is_asterisk(expr) {
+                violations.push(Violation::new(
+                    expr,
+                    "columns of the view should be listed explicitly",
+                ));
+            }
+        }
+    }
+}
+
+// BigQuery requires `ALL` or `DISTINCT`
+struct BareSetOperator;
+
+impl Rule for BareSetOperator {
+    fn name(&self) -> &'static str {
+        "bare-set-operator"
+    }
+    fn description(&self) -> &'static str {
+        "`UNION`, `INTERSECT` or `EXCEPT` without `ALL` or `DISTINCT`"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn check(&self, node: &Node, _: &[&Node], violations: &mut Vec<Violation>) {
+        if node.node_type != NodeType::SetOperator {
+            return;
+        }
+        let quantifier = node
+            .get_node("distinct_or_all")
+            .and_then(|n| n.token.as_ref());
+        if quantifier.is_some_and(|t| t.in_(&vec!["ALL", "DISTINCT"])) {
+            return;
+        }
+        let operator = node.token.as_ref().unwrap();
+        let message = format!(
+            "`{}` should be followed by `ALL` or `DISTINCT`",
+            operator.literal.to_uppercase()
+        );
+        violations.push(Violation::at_token(operator, &message));
+    }
+}
+
+// `FROM a, b` is easily mistaken for a missing join condition
+// (`FROM t, UNNEST(t.arr)` is allowed)
+struct CommaJoin;
+
+impl Rule for CommaJoin {
+    fn name(&self) -> &'static str {
+        "comma-join"
+    }
+    fn description(&self) -> &'static str {
+        "implicit cross join by comma"
+    }
+    fn check(&self, node: &Node, _: &[&Node], violations: &mut Vec<Violation>) {
+        if node.node_type != NodeType::JoinOperator
+            || !node.token.as_ref().is_some_and(|t| t.is(","))
+        {
+            return;
+        }
+        let right = node.get_node("right");
+        if right.is_some_and(|r| r.node_type == NodeType::CallingUnnest) {
+            return;
+        }
+        let comma = node.token.as_ref().unwrap();
+        violations.push(Violation::at_token(comma, "use `CROSS JOIN` explicitly"));
+    }
+}
+
+// BigQuery names such columns `f0_`, `f1_`, ...
+struct UnaliasedExpression;
+
+impl UnaliasedExpression {
+    // false if the SELECT is a scalar subquery, `IN (SELECT ...)`, `INSERT ... SELECT`, ...
+    fn names_matter(node: &Node, ancestors: &[&Node]) -> bool {
+        // only the first SELECT of `UNION ALL` determines the names
+        let mut child = node;
+        for ancestor in ancestors.iter().rev() {
+            match ancestor.node_type {
+                NodeType::GroupedStatement => (),
+                NodeType::SetOperator => {
+                    let right = ancestor.get_node("right");
+                    if right.is_some_and(|r| std::ptr::eq(r, child)) {
+                        return false;
+                    }
+                }
+                _ => break,
+            }
+            child = ancestor;
+        }
+        let parent = match query_parent(ancestors) {
+            Some(parent) => parent,
+            None => return true,
+        };
+        match &parent.node_type {
+            NodeType::WithQuery
+            | NodeType::JoinOperator
+            | NodeType::KeywordWithStatement
+            | NodeType::KeywordWithStatements
+            | NodeType::ElseIfClause => true,
+            NodeType::KeywordWithExpr => {
+                let token = parent.token.as_ref();
+                token.is_some_and(|t| t.in_(&vec!["FROM", "USING"]))
+            }
+            NodeType::SelectStatement | NodeType::InsertStatement => false,
+            // BEGIN ... END, IF ..., LOOP ..., ...
+            node_type => node_type
+                .schema()
+                .is_some_and(|s| s.extends.contains(&"XXXStatement")),
+        }
+    }
+}
+
+impl Rule for UnaliasedExpression {
+    fn name(&self) -> &'static str {
+        "unaliased-expression"
+    }
+    fn description(&self) -> &'static str {
+        "computed column without alias"
+    }
+    fn check(&self, node: &Node, ancestors: &[&Node], violations: &mut Vec<Violation>) {
+        if node.node_type != NodeType::SelectStatement {
+            return;
+        }
+        // SELECT AS VALUE
+        let as_value = node.get_node_vec("as_struct_or_value").is_some_and(|ns| {
+            ns.iter()
+                .any(|n| n.token.as_ref().is_some_and(|t| t.is("VALUE")))
+        });
+        if as_value || !UnaliasedExpression::names_matter(node, ancestors) {
+            return;
+        }
+        for expr in node.get_node_vec("exprs").into_iter().flatten() {
+            let is_column = match expr.node_type {
+                NodeType::Identifier | NodeType::DotOperator => true,
+                _ => is_asterisk(expr),
+            };
+            if !is_column && expr.get_node("alias").is_none() {
+                violations.push(Violation::new(expr, "computed column should have an alias"));
+            }
+        }
+    }
+}
+
+// the order of rows in a subquery is not guaranteed
+// (except for `ARRAY(SELECT ...)`)
+struct OrderByWithoutLimit;
+
+impl Rule for OrderByWithoutLimit {
+    fn name(&self) -> &'static str {
+        "order-by-without-limit"
+    }
+    fn description(&self) -> &'static str {
+        "`ORDER BY` without `LIMIT` in a subquery"
+    }
+    fn check(&self, node: &Node, ancestors: &[&Node], violations: &mut Vec<Violation>) {
+        if node.node_type != NodeType::SelectStatement || node.get_node("limit").is_some() {
+            return;
+        }
+        let orderby = match node.get_node("orderby") {
+            Some(orderby) => orderby,
+            None => return,
+        };
+        let is_subquery = ancestors.iter().any(|n| {
+            matches!(
+                n.node_type,
+                NodeType::SelectStatement
+                    | NodeType::UpdateStatement
+                    | NodeType::DeleteStatement
+                    | NodeType::MergeStatement
+            )
+        });
+        let in_array = query_parent(ancestors).is_some_and(|p| {
+            p.node_type == NodeType::CallingFunction
+                && p.get_node("func")
+                    .and_then(|f| f.token.as_ref())
+                    .is_some_and(|t| t.is("ARRAY"))
+        });
+        if is_subquery && !in_array {
+            violations.push(Violation::new(
+                orderby,
+                "`ORDER BY` in a subquery has no effect without `LIMIT`",
+            ));
+        }
+    }
+}
+
+// BigQuery requires `WHERE` (use `WHERE TRUE` to affect every row)
+struct DmlWithoutWhere;
+
+impl Rule for DmlWithoutWhere {
+    fn name(&self) -> &'static str {
+        "dml-without-where"
+    }
+    fn description(&self) -> &'static str {
+        "`DELETE` or `UPDATE` without `WHERE`"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn check(&self, node: &Node, ancestors: &[&Node], violations: &mut Vec<Violation>) {
+        if !matches!(
+            node.node_type,
+            NodeType::DeleteStatement | NodeType::UpdateStatement
+        ) || node.get_node("where").is_some()
+        {
+            return;
+        }
+        // WHEN MATCHED THEN UPDATE SET ...
+        if ancestors
+            .iter()
+            .any(|n| n.node_type == NodeType::MergeStatement)
+        {
+            return;
+        }
+        let keyword = node.token.as_ref().unwrap();
+        let message = format!("`{}` should have `WHERE`", keyword.literal.to_uppercase());
+        violations.push(Violation::at_token(keyword, &message));
+    }
+}
+
+// e.g. `int_col = '1'` fails because INT64 and STRING are not implicitly coerced
+struct IncompatibleComparison;
+
+impl Rule for IncompatibleComparison {
+    fn name(&self) -> &'static str {
+        "incompatible-comparison"
+    }
+    fn description(&self) -> &'static str {
+        "Comparison between values of incompatible types"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn check(&self, _: &Node, _: &[&Node], _: &mut Vec<Violation>) {}
+    fn check_with_types(
+        &self,
+        node: &Node,
+        _: &[&Node],
+        types: &Types,
+        violations: &mut Vec<Violation>,
+    ) {
+        let operator = node.token.as_ref().unwrap();
+        // (the left operand, the right operands)
+        let (left, rights): (_, Vec<&Node>) = match node.node_type {
+            NodeType::BinaryOperator
+                if ["=", "!=", "<>", "<", ">", "<=", ">="].contains(&operator.literal.as_str()) =>
+            {
+                (
+                    node.get_node("left"),
+                    node.get_node("right").into_iter().collect(),
+                )
+            }
+            NodeType::BetweenOperator => (
+                node.get_node("left"),
+                vec![node.get_node("right_min"), node.get_node("right_max")]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            ),
+            // `x IN (a, b)` (subqueries and UNNEST are not checked)
+            NodeType::InOperator => match node.get_node("right") {
+                Some(right) if right.node_type == NodeType::GroupedExprs => (
+                    node.get_node("left"),
+                    right.get_node_vec("exprs").into_iter().flatten().collect(),
+                ),
+                _ => return,
+            },
+            // CASE x WHEN a THEN ...
+            NodeType::CaseExpr => (
+                node.get_node("expr"),
+                node.get_node_vec("arms")
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|arm| arm.get_node("expr"))
+                    .collect(),
+            ),
+            _ => return,
+        };
+        let left = match left {
+            Some(left) => left,
+            None => return,
+        };
+        for right in rights {
+            if types.comparable(left, right) {
+                continue;
+            }
+            let (l, r) = (types.type_of(left).unwrap(), types.type_of(right).unwrap());
+            let message = format!("`{}` cannot be compared with `{}`", l, r);
+            violations.push(Violation::new(right, &message));
+        }
+    }
+}
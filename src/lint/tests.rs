@@ -0,0 +1,177 @@
+use super::*;
+use crate::catalog::{Field, Type};
+use crate::parser::Parser;
+
+// one line per diagnostic: `line:column-end_line:end_column severity rule`
+fn test_lint(code: &str, linter: &Linter, expected: &str) {
+    let stmts = Parser::new(code.to_string()).unwrap().parse_code().unwrap();
+    let result: Vec<String> = linter
+        .lint(&stmts)
+        .iter()
+        .map(|d| {
+            let severity = match d.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Off => "off",
+            };
+            format!(
+                "{}:{}-{}:{} {} {}",
+                d.span.line, d.span.column, d.span.end_line, d.span.end_column, severity, d.rule
+            )
+        })
+        .collect();
+    assert_eq!(expected, result.join("\n"), "{}", code);
+}
+
+#[test]
+fn test_lint_rules() {
+    let linter = Linter::default();
+    test_lint(
+        "\
+CREATE VIEW v AS
+SELECT *, t.* EXCEPT (a), COUNT(*) AS cnt FROM t GROUP BY 1;
+SELECT 1 AS x INTERSECT DISTINCT SELECT 2 EXCEPT ALL (SELECT 3);
+SELECT * FROM a, b JOIN c USING (id), UNNEST(arr);
+SELECT
+  a + 1,
+  x.y,
+  f(a) AS b,
+  -- comment
+  (SELECT MAX(a) + 1 FROM t) AS c,
+  a IN (SELECT x + 1 FROM t) AS d
+FROM (SELECT 1 + 1, 2 AS b) AS x;
+WITH cte AS (SELECT a FROM t ORDER BY a)
+SELECT ARRAY(SELECT x FROM cte ORDER BY x) AS x, (SELECT a FROM cte ORDER BY a LIMIT 1) AS y
+FROM cte
+ORDER BY 1;
+UPDATE t SET a = 1;
+DELETE FROM t;
+DELETE t AS x;
+DELETE t WHERE TRUE;
+MERGE t USING s ON FALSE WHEN MATCHED THEN UPDATE SET a = 1;",
+        &linter,
+        "\
+2:8-2:9 warning select-star-in-view
+2:11-2:25 warning select-star-in-view
+4:16-4:17 warning comma-join
+6:3-6:8 warning unaliased-expression
+12:14-12:19 warning unaliased-expression
+13:30-13:40 warning order-by-without-limit
+17:1-17:7 error dml-without-where
+18:1-18:7 error dml-without-where
+19:1-19:7 error dml-without-where",
+    );
+    test_lint(
+        "SELECT 1 AS x UNION SELECT 2 UNION DISTINCT (SELECT 3)",
+        &linter,
+        "1:15-1:20 error bare-set-operator",
+    );
+}
+
+#[test]
+fn test_lint_suppression() {
+    let linter = Linter::default();
+    test_lint(
+        "\
+-- bq2cst-disable-next-line comma-join
+SELECT a + 1 FROM a, b;
+SELECT a + 1 FROM a, b; # bq2cst-disable-next-line
+SELECT a + 1 FROM a, b;
+/* bq2cst-disable-next-line unaliased-expression, dml-without-where */
+SELECT a + 1 FROM a, b;
+-- bq2cst-disable-next-line-with-typo
+SELECT a + 1 FROM a, b;",
+        &linter,
+        "\
+2:8-2:13 warning unaliased-expression
+3:8-3:13 warning unaliased-expression
+3:20-3:21 warning comma-join
+6:20-6:21 warning comma-join
+8:8-8:13 warning unaliased-expression
+8:20-8:21 warning comma-join",
+    );
+}
+
+#[test]
+fn test_linter() {
+    let mut linter = Linter::default();
+    assert_eq!(
+        linter.rules().map(|(r, _)| r.name()).collect::<Vec<_>>(),
+        vec![
+            "select-star-in-view",
+            "bare-set-operator",
+            "comma-join",
+            "unaliased-expression",
+            "order-by-without-limit",
+            "dml-without-where",
+            "incompatible-comparison",
+        ]
+    );
+    let mut options = LintOptions::default();
+    options
+        .rules
+        .insert("comma-join".to_string(), Severity::Error);
+    options
+        .rules
+        .insert("unaliased-expression".to_string(), Severity::Off);
+    assert_eq!(linter.configure(&options), Ok(()));
+    test_lint(
+        "SELECT a + 1 FROM a, b",
+        &linter,
+        "1:20-1:21 error comma-join",
+    );
+    options.rules.insert("unknown".to_string(), Severity::Off);
+    assert_eq!(linter.configure(&options), Err("unknown".to_string()));
+
+    // custom rule
+    struct NoTruncate;
+    impl Rule for NoTruncate {
+        fn name(&self) -> &'static str {
+            "no-truncate"
+        }
+        fn description(&self) -> &'static str {
+            "TRUNCATE TABLE"
+        }
+        fn check(&self, node: &Node, _: &[&Node], violations: &mut Vec<Violation>) {
+            if node.node_type == NodeType::TruncateStatement {
+                violations.push(Violation::new(node, "do not truncate tables"));
+            }
+        }
+    }
+    let mut linter = Linter::empty();
+    linter.register(Box::new(NoTruncate));
+    test_lint(
+        "TRUNCATE TABLE t; -- comment",
+        &linter,
+        "1:1-1:17 warning no-truncate",
+    );
+}
+
+#[test]
+fn test_lint_types() {
+    let mut catalog = Catalog::new();
+    catalog.add_table(
+        "ds.t",
+        vec![Field::new("id", Type::Int64), Field::new("dt", Type::Date)],
+    );
+    let mut linter = Linter::default();
+    linter.set_catalog(catalog);
+    test_lint(
+        "\
+SELECT *
+FROM ds.t
+WHERE
+  id = '1'
+  AND id BETWEEN 1 AND 1.5
+  AND id IN (1, TRUE)
+  AND dt < '2020-01-01' AND dt = CURRENT_TIMESTAMP()
+  AND CASE dt WHEN DATE '2020-01-01' THEN TRUE WHEN 1 THEN FALSE ELSE NULL END
+  AND unknown_col = 1 AND NULL = id",
+        &linter,
+        "\
+4:8-4:11 error incompatible-comparison
+6:17-6:21 error incompatible-comparison
+7:34-7:53 error incompatible-comparison
+8:53-8:54 error incompatible-comparison",
+    );
+}
@@ -0,0 +1,322 @@
+use bq2cst::catalog::{self, Catalog};
+use bq2cst::infer::infer;
+use bq2cst::lexer::Lexer;
+use bq2cst::lint::Severity;
+use bq2cst::{lint_str, parse_str, Error, Linter, Node, NodeType};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process;
+
+const USAGE: &str = "\
+Usage:
+  bq2cst parse <FILE> [--format json|yaml|tree]
+  bq2cst check <FILE>...
+  bq2cst lint <FILE>... [--rule <RULE>=off|warning|error]... [--table <NAME>=<SCHEMA.json>]... [--ddl <FILE.sql>]...
+  bq2cst lint --list-rules
+  bq2cst validate <FILE>... [--table <NAME>=<SCHEMA.json>]... [--ddl <FILE.sql>]...
+  bq2cst schema <FILE> [--table <NAME>=<SCHEMA.json>]... [--ddl <FILE.sql>]...
+  bq2cst tokens <FILE>
+
+<FILE> can be `-` to read from stdin.";
+
+// exit codes
+const SYNTAX_ERROR: i32 = 1;
+const LINT_ERROR: i32 = 1; // warnings do not affect the exit code
+const VALIDATION_ERROR: i32 = 1;
+const UNKNOWN_SCHEMA: i32 = 1;
+const USAGE_ERROR: i32 = 2;
+
+enum Format {
+    Json,
+    Yaml,
+    Tree,
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let res = match args.first().map(|s| s.as_str()) {
+        Some("parse") => parse(&args[1..]),
+        Some("check") => check(&args[1..]),
+        Some("lint") => lint(&args[1..]),
+        Some("validate") => validate(&args[1..]),
+        Some("schema") => schema(&args[1..]),
+        Some("tokens") => tokens(&args[1..]),
+        Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(usage_error("a subcommand is required")),
+    };
+    if let Err(code) = res {
+        process::exit(code);
+    }
+}
+
+fn parse(args: &[String]) -> Result<(), i32> {
+    let mut format = Format::Json;
+    let mut path = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--format" | "-f" => {
+                format = match iter.next().map(|s| s.as_str()) {
+                    Some("json") => Format::Json,
+                    Some("yaml") => Format::Yaml,
+                    Some("tree") => Format::Tree,
+                    _ => return Err(usage_error("--format should be json, yaml or tree")),
+                }
+            }
+            _ if path.is_none() => path = Some(arg.as_str()),
+            _ => return Err(usage_error(&format!("unexpected argument `{}`", arg))),
+        }
+    }
+    let path = path.ok_or_else(|| usage_error("<FILE> is required"))?;
+    let code = read(path)?;
+    let stmts = parse_str(&code).map_err(|e| syntax_error(path, &e))?;
+    let res = match format {
+        Format::Json => serde_json::to_string_pretty(&stmts).unwrap() + "\n",
+        Format::Yaml => serde_yaml::to_string(&stmts).unwrap(),
+        Format::Tree => stmts.iter().map(Node::to_string).collect(),
+    };
+    write(&res);
+    Ok(())
+}
+
+fn check(args: &[String]) -> Result<(), i32> {
+    if args.is_empty() {
+        return Err(usage_error("<FILE> is required"));
+    }
+    let mut exit_code = 0;
+    for path in args {
+        let res = read(path).and_then(|code| match parse_str(&code) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(syntax_error(path, &e)),
+        });
+        if let Err(code) = res {
+            exit_code = exit_code.max(code);
+        }
+    }
+    if exit_code == 0 {
+        Ok(())
+    } else {
+        Err(exit_code)
+    }
+}
+
+fn lint(args: &[String]) -> Result<(), i32> {
+    let mut linter = Linter::default();
+    // used to infer the types of columns
+    let mut catalog = Catalog::new();
+    let mut paths = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--rule" | "-r" => {
+                let (name, severity) = iter
+                    .next()
+                    .and_then(|s| s.split_once('='))
+                    .ok_or_else(|| usage_error("--rule should be <RULE>=<SEVERITY>"))?;
+                let severity = match severity {
+                    "off" => Severity::Off,
+                    "warning" => Severity::Warning,
+                    "error" => Severity::Error,
+                    _ => return Err(usage_error("severity should be off, warning or error")),
+                };
+                if !linter.set_severity(name, severity) {
+                    return Err(usage_error(&format!("unknown rule `{}`", name)));
+                }
+            }
+            "--list-rules" => {
+                let res: String = linter
+                    .rules()
+                    .map(|(rule, _)| format!("{}\t{}\n", rule.name(), rule.description()))
+                    .collect();
+                write(&res);
+                return Ok(());
+            }
+            "--table" | "-t" | "--ddl" | "-d" => add_to_catalog(&mut catalog, arg, iter.next())?,
+            _ => paths.push(arg.as_str()),
+        }
+    }
+    if paths.is_empty() {
+        return Err(usage_error("<FILE> is required"));
+    }
+    linter.set_catalog(catalog);
+    let mut exit_code = 0;
+    for path in paths {
+        let res = read(path).and_then(|code| {
+            let diagnostics = lint_str(&code, &linter).map_err(|e| syntax_error(path, &e))?;
+            let res: String = diagnostics
+                .iter()
+                .map(|d| {
+                    let severity = match d.severity {
+                        Severity::Error => "error",
+                        _ => "warning",
+                    };
+                    format!(
+                        "{}:{}:{}: {}: {} [{}]\n",
+                        path, d.span.line, d.span.column, severity, d.message, d.rule
+                    )
+                })
+                .collect();
+            write(&res);
+            if diagnostics.iter().any(|d| d.severity == Severity::Error) {
+                Err(LINT_ERROR)
+            } else {
+                Ok(())
+            }
+        });
+        if let Err(code) = res {
+            exit_code = exit_code.max(code);
+        }
+    }
+    if exit_code == 0 {
+        Ok(())
+    } else {
+        Err(exit_code)
+    }
+}
+
+// --table <NAME>=<SCHEMA.json> (the output of `bq show --schema`) or --ddl <FILE.sql>
+fn add_to_catalog(catalog: &mut Catalog, option: &str, value: Option<&String>) -> Result<(), i32> {
+    if option == "--table" || option == "-t" {
+        let (name, path) = value
+            .and_then(|s| s.split_once('='))
+            .ok_or_else(|| usage_error("--table should be <NAME>=<SCHEMA.json>"))?;
+        let json = read(path)?;
+        catalog.add_table_json(name, &json).map_err(|e| {
+            eprintln!("{}: {}", path, e);
+            USAGE_ERROR
+        })
+    } else {
+        let path = value.ok_or_else(|| usage_error("--ddl should be <FILE.sql>"))?;
+        let code = read(path)?;
+        let stmts = parse_str(&code).map_err(|e| syntax_error(path, &e))?;
+        catalog.add_ddl(&stmts);
+        Ok(())
+    }
+}
+
+fn validate(args: &[String]) -> Result<(), i32> {
+    let mut catalog = Catalog::new();
+    let mut paths = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--table" | "-t" | "--ddl" | "-d" => add_to_catalog(&mut catalog, arg, iter.next())?,
+            _ => paths.push(arg.as_str()),
+        }
+    }
+    if paths.is_empty() {
+        return Err(usage_error("<FILE> is required"));
+    }
+    let mut exit_code = 0;
+    for path in paths {
+        let res = read(path).and_then(|code| {
+            let stmts = parse_str(&code).map_err(|e| syntax_error(path, &e))?;
+            let errors = catalog::validate(&stmts, &catalog);
+            let res: String = errors
+                .iter()
+                .map(|e| {
+                    format!(
+                        "{}:{}:{}: {}\n",
+                        path, e.span.line, e.span.column, e.message
+                    )
+                })
+                .collect();
+            write(&res);
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(VALIDATION_ERROR)
+            }
+        });
+        if let Err(code) = res {
+            exit_code = exit_code.max(code);
+        }
+    }
+    if exit_code == 0 {
+        Ok(())
+    } else {
+        Err(exit_code)
+    }
+}
+
+// the schema of the last statement in the format of `bq show --schema`
+fn schema(args: &[String]) -> Result<(), i32> {
+    let mut catalog = Catalog::new();
+    let mut path = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--table" | "-t" | "--ddl" | "-d" => add_to_catalog(&mut catalog, arg, iter.next())?,
+            _ if path.is_none() => path = Some(arg.as_str()),
+            _ => return Err(usage_error(&format!("unexpected argument `{}`", arg))),
+        }
+    }
+    let path = path.ok_or_else(|| usage_error("<FILE> is required"))?;
+    let code = read(path)?;
+    let stmts = parse_str(&code).map_err(|e| syntax_error(path, &e))?;
+    let types = infer(&stmts, &catalog);
+    let columns = stmts
+        .iter()
+        .rev()
+        .find(|s| s.node_type != NodeType::EOF)
+        .and_then(|s| types.schema_of(s));
+    match columns {
+        Some(columns) => {
+            write(&(serde_json::to_string_pretty(&columns).unwrap() + "\n"));
+            Ok(())
+        }
+        None => {
+            eprintln!("{}: the schema of the last statement is unknown", path);
+            Err(UNKNOWN_SCHEMA)
+        }
+    }
+}
+
+fn tokens(args: &[String]) -> Result<(), i32> {
+    let path = match args {
+        [path] => path,
+        [] => return Err(usage_error("<FILE> is required")),
+        _ => return Err(usage_error("only one <FILE> is allowed")),
+    };
+    let code = read(path)?;
+    let mut lexer = Lexer::new(code);
+    let tokens = lexer.tokenize_code().map_err(|e| syntax_error(path, &e))?;
+    let res: String = tokens
+        .iter()
+        .map(|t| format!("{}:{}\t{:?}\n", t.line, t.column, t.literal))
+        .collect();
+    write(&res);
+    Ok(())
+}
+
+fn read(path: &str) -> Result<String, i32> {
+    let mut code = String::new();
+    let res = if path == "-" {
+        io::stdin().read_to_string(&mut code).map(|_| code)
+    } else {
+        fs::read_to_string(path)
+    };
+    res.map_err(|e| {
+        eprintln!("{}: {}", path, e);
+        USAGE_ERROR
+    })
+}
+
+fn write(s: &str) {
+    // ignore the error caused by `bq2cst parse x.sql | head`
+    let _ = io::stdout().write_all(s.as_bytes());
+}
+
+fn usage_error(message: &str) -> i32 {
+    eprintln!("error: {}\n\n{}", message, USAGE);
+    USAGE_ERROR
+}
+
+// path:line:column: message
+fn syntax_error(path: &str, error: &Error) -> i32 {
+    eprintln!("{}:{}", path, error);
+    SYNTAX_ERROR
+}
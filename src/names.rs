@@ -0,0 +1,130 @@
+// identifiers and the names BigQuery gives to columns
+// shared by name resolution, type inference, lineage, ...
+use crate::catalog::Field;
+use crate::cst::{Node, NodeType};
+use crate::token::{Span, TokenKind};
+
+// date parts and niladic functions which look like identifiers
+pub(crate) const IMPLICIT_NAMES: &[&str] = &[
+    "CURRENT_DATE",
+    "CURRENT_DATETIME",
+    "CURRENT_TIME",
+    "CURRENT_TIMESTAMP",
+];
+pub(crate) const DATE_PARTS: &[&str] = &[
+    "MICROSECOND",
+    "MILLISECOND",
+    "SECOND",
+    "MINUTE",
+    "HOUR",
+    "DAY",
+    "DAYOFWEEK",
+    "DAYOFYEAR",
+    "WEEK",
+    "ISOWEEK",
+    "MONTH",
+    "QUARTER",
+    "YEAR",
+    "ISOYEAR",
+    "DATE",
+    "DATETIME",
+    "TIME",
+    "SUNDAY",
+    "MONDAY",
+    "TUESDAY",
+    "WEDNESDAY",
+    "THURSDAY",
+    "FRIDAY",
+    "SATURDAY",
+];
+
+pub(crate) fn contains(names: &[&str], name: &str) -> bool {
+    names.iter().any(|n| n.eq_ignore_ascii_case(name))
+}
+
+// column names and aliases are case insensitive
+pub(crate) fn eq(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+pub(crate) fn unquote(node: &Node) -> String {
+    match &node.token {
+        Some(token) if token.kind == TokenKind::QuotedIdent => {
+            token.literal[1..token.literal.len() - 1].to_string()
+        }
+        Some(token) => token.literal.clone(),
+        None => String::new(),
+    }
+}
+
+// NOTE every node passed to this function has its token
+pub(crate) fn span(node: &Node) -> Span {
+    node.token.as_ref().unwrap().span()
+}
+
+// from the first token of `first` to the last token of `last`
+pub(crate) fn span_between(first: &Node, last: &Node) -> Span {
+    let (first, last) = (span(first), span(last));
+    Span {
+        end_line: last.end_line,
+        end_column: last.end_column,
+        end: last.end,
+        end_utf16: last.end_utf16,
+        ..first
+    }
+}
+
+// identifiers of `a.b.c`
+pub(crate) fn path(node: &Node) -> Option<Vec<&Node>> {
+    match node.node_type {
+        NodeType::Identifier => Some(vec![node]),
+        NodeType::DotOperator => {
+            let mut parts = path(node.get_node("left")?)?;
+            let right = node.get_node("right")?;
+            if right.node_type != NodeType::Identifier {
+                return None;
+            }
+            parts.push(right);
+            Some(parts)
+        }
+        _ => None,
+    }
+}
+
+// `project.dataset.table` (without backquotes)
+pub(crate) fn table_name(node: &Node) -> Option<String> {
+    let parts: Vec<String> = path(node)?.into_iter().map(unquote).collect();
+    Some(parts.join("."))
+}
+
+// the name of the column in SELECT list or STRUCT (empty if anonymous)
+// `col`, `t.col` and `f(x).col` are named `col`
+pub(crate) fn implicit_name(expr: &Node) -> String {
+    if let Some(alias) = expr.get_node("alias") {
+        return unquote(alias);
+    }
+    let last = match expr.node_type {
+        NodeType::Identifier => Some(expr),
+        NodeType::DotOperator => expr.get_node("right"),
+        _ => None,
+    };
+    match last {
+        Some(ident) if ident.node_type == NodeType::Identifier => unquote(ident),
+        _ => String::new(),
+    }
+}
+
+// BigQuery names anonymous columns `f0_`, `f1_`, ...
+pub(crate) fn implicit_names(columns: &[Field]) -> Vec<Field> {
+    let mut n = 0;
+    let mut res = Vec::new();
+    for column in columns {
+        let mut column = column.clone();
+        if column.name.is_empty() {
+            column.name = format!("f{}_", n);
+            n += 1;
+        }
+        res.push(column);
+    }
+    res
+}
@@ -1,4 +1,7 @@
 use super::*;
+use crate::cst::{schema::ChildKind, ContentType};
+use crate::format::{format, FormatOptions, KeywordCase};
+use crate::unparser::unparse;
 use difference::Changeset;
 
 mod tests_core;
@@ -9,6 +12,8 @@ mod tests_dcl;
 mod tests_script;
 mod tests_debug;
 mod tests_other;
+mod tests_error;
+mod tests_recovery;
 
 struct TestCase {
     code: String,
@@ -23,8 +28,8 @@ impl TestCase {
         }
     }
     pub fn test(&self, index: usize) {
-        let mut p = Parser::new(self.code.clone());
-        let stmts = p.parse_code();
+        let mut p = Parser::new(self.code.clone()).unwrap();
+        let stmts = p.parse_code().unwrap();
         println!(
             "\
 ========== testing ==========
@@ -35,8 +40,62 @@ impl TestCase {
         );
         let result = stmts[index].to_string();
         let changeset = Changeset::new(self.expected_output.as_str(), result.as_str(), "\n");
-        println!("{}\n", changeset.to_string());
+        println!("{}\n", changeset);
         assert_eq!(self.expected_output, result);
+        // every token should be kept in CST
+        assert_eq!(self.code, unparse(&stmts));
+        // every child should be declared in the schema
+        for stmt in &stmts {
+            check_schema(stmt);
+        }
+        // formatting should keep every token and should be idempotent
+        let options = FormatOptions {
+            keyword_case: KeywordCase::Preserve,
+            ..Default::default()
+        };
+        let formatted = format(&stmts, &options);
+        let reparsed = Parser::new(formatted.clone())
+            .unwrap()
+            .parse_code()
+            .unwrap();
+        assert_eq!(literals(&stmts), literals(&reparsed));
+        assert_eq!(formatted, format(&reparsed, &options));
     }
 }
 
+fn check_schema(node: &Node) {
+    let schema = node.node_type.schema().unwrap();
+    for (key, child) in node.children() {
+        let kind = match child {
+            ContentType::Node(_) => ChildKind::Node,
+            ContentType::NodeVec(_) => ChildKind::NodeVec,
+        };
+        assert_eq!(
+            schema.child(key).map(|c| c.kind),
+            Some(kind),
+            "`{}` of {:?}",
+            key,
+            node.node_type
+        );
+    }
+    for key in schema.keys() {
+        let optional = schema.child(key).unwrap().optional;
+        assert!(
+            optional || node.children().get(key).is_some(),
+            "`{}` of {:?} is missing",
+            key,
+            node.node_type
+        );
+    }
+    for (_, child) in node.children_in_source_order() {
+        check_schema(child);
+    }
+}
+
+fn literals(stmts: &[Node]) -> Vec<String> {
+    stmts
+        .iter()
+        .flat_map(|stmt| stmt.tokens())
+        .map(|t| t.literal.clone())
+        .collect()
+}
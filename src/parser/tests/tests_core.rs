@@ -5,7 +5,7 @@ fn test_parse_code_no_statement() {
         TestCase::new(
             "",
             "\
-self: None (EOF)
+self:  (EOF)
 ",
         ),
         TestCase::new(
@@ -13,7 +13,7 @@ self: None (EOF)
 -- comment
 ",
             "\
-self: None (EOF)
+self:  (EOF)
 leading_comments:
 - self: -- comment (Comment)
 ",
@@ -32,7 +32,7 @@ fn test_parse_code_eof() {
 SELECT 1;
 ",
             "\
-self: None (EOF)
+self:  (EOF)
 ",
         ),
         TestCase::new(
@@ -41,7 +41,7 @@ SELECT 1;
 -- EOF
 ",
             "\
-self: None (EOF)
+self:  (EOF)
 leading_comments:
 - self: -- EOF (Comment)
 ",
@@ -104,11 +104,9 @@ exprs:
     self: , (Symbol)
   right:
     self: 1 (NumericLiteral)
-- self: r (UnaryOperator)
+- self: r'xxx' (StringLiteral)
   comma:
     self: , (Symbol)
-  right:
-    self: 'xxx' (StringLiteral)
 - self: DATE (UnaryOperator)
   comma:
     self: , (Symbol)
@@ -118,9 +116,7 @@ exprs:
   comma:
     self: , (Symbol)
   right:
-    self: r (UnaryOperator)
-    right:
-      self: '2020-01-01' (StringLiteral)
+    self: r'2020-01-01' (StringLiteral)
 - self: NOT (UnaryOperator)
   comma:
     self: , (Symbol)
@@ -540,6 +536,26 @@ exprs:
       self: ) (Symbol)
   rparen:
     self: ] (Symbol)
+",
+        ),
+        TestCase::new(
+            "\
+SELECT arr[i + 1]
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: [ (ArrayAccessing)
+  left:
+    self: arr (Identifier)
+  right:
+    self: + (BinaryOperator)
+    left:
+      self: i (Identifier)
+    right:
+      self: 1 (NumericLiteral)
+  rparen:
+    self: ] (Symbol)
 ",
         ),
     // ----- struct -----
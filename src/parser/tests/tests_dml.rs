@@ -109,6 +109,34 @@ where:
         ),
         TestCase::new(
             "\
+DELETE FROM table_name;
+",
+            "\
+self: DELETE (DeleteStatement)
+from:
+  self: FROM (Keyword)
+semicolon:
+  self: ; (Symbol)
+table_name:
+  self: table_name (Identifier)
+",
+        ),
+        TestCase::new(
+            "\
+DELETE table_name AS t
+",
+            "\
+self: DELETE (DeleteStatement)
+table_name:
+  self: table_name (Identifier)
+  alias:
+    self: t (Identifier)
+  as:
+    self: AS (Keyword)
+",
+        ),
+        TestCase::new(
+            "\
 DELETE table_name t WHERE TRUE;
 ",
             "\
@@ -584,4 +612,3 @@ whens:
         t.test(0);
     }
 }
-
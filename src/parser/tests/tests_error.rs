@@ -0,0 +1,97 @@
+use super::*;
+
+struct ErrorTestCase {
+    code: String,
+    line: usize,
+    column: usize,
+    expected: Vec<String>,
+    message: String,
+}
+
+impl ErrorTestCase {
+    fn new(code: &str, line: usize, column: usize, expected: Vec<&str>, message: &str) -> Self {
+        ErrorTestCase {
+            code: code.to_string(),
+            line,
+            column,
+            expected: expected.iter().map(|s| s.to_string()).collect(),
+            message: message.to_string(),
+        }
+    }
+    fn test(&self) {
+        println!(
+            "\
+========== testing ==========
+{}
+=============================
+",
+            self.code.trim()
+        );
+        let error = Parser::new(self.code.clone())
+            .and_then(|mut p| p.parse_code())
+            .unwrap_err();
+        assert_eq!(self.message, error.message);
+        assert_eq!((self.line, self.column), (error.line, error.column));
+        assert_eq!(self.expected, error.expected);
+    }
+}
+
+#[test]
+fn test_parse_code_error() {
+    let test_cases = vec![
+        // ----- lexer -----
+        ErrorTestCase::new(
+            "SELECT 'abc",
+            1,
+            8,
+            vec!["'"],
+            "This string literal is not closed",
+        ),
+        ErrorTestCase::new(
+            "\
+SELECT 1;
+/* comment
+",
+            2,
+            1,
+            vec!["*/"],
+            "This comment is not closed",
+        ),
+        // ----- parser -----
+        ErrorTestCase::new(
+            "SELECT 1 NOT 2;",
+            1,
+            14,
+            vec!["IN", "LIKE", "BETWEEN"],
+            "Expected `IN`, `LIKE` or `BETWEEN` but got: token `2`",
+        ),
+        ErrorTestCase::new(
+            "\
+SELECT 1;
+FOO 1;
+",
+            2,
+            1,
+            constants::STATEMENT_KEYWORDS.to_vec(),
+            "Expected the beginning of a statement but got: `FOO`",
+        ),
+        ErrorTestCase::new(
+            "CREATE OR REPLACE x;",
+            1,
+            8,
+            vec!["SCHEMA", "TABLE", "VIEW", "FUNCTION", "PROCEDURE"],
+            "Expected `SCHEMA`, `TABLE`, `VIEW`, `FUNCTION` or `PROCEDURE` but got: token `OR`",
+        ),
+        ErrorTestCase::new(
+            "ALTER TABLE t FOO;",
+            1,
+            15,
+            vec!["SET", "ADD", "RENAME", "DROP", "ALTER"],
+            "Expected `SET`, `ADD`, `RENAME`, `DROP` or `ALTER` but got: token `FOO`",
+        ),
+        ErrorTestCase::new("SELECT (1", 1, 10, vec![], "Unexpected EOF"),
+    ];
+    for t in test_cases {
+        t.test();
+    }
+}
@@ -0,0 +1,205 @@
+use super::*;
+
+struct RecoveryTestCase {
+    code: String,
+    expected_outputs: Vec<String>,
+    expected_messages: Vec<String>,
+}
+
+impl RecoveryTestCase {
+    fn new(code: &str, expected_outputs: Vec<&str>, expected_messages: Vec<&str>) -> Self {
+        RecoveryTestCase {
+            code: code.to_string(),
+            expected_outputs: expected_outputs.iter().map(|s| s.to_string()).collect(),
+            expected_messages: expected_messages.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+    fn test(&self) {
+        println!(
+            "\
+========== testing ==========
+{}
+=============================
+",
+            self.code.trim()
+        );
+        let mut p = Parser::new(self.code.clone()).unwrap();
+        let (stmts, errors) = p.parse_code_with_recovery();
+        let messages: Vec<String> = errors.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(self.expected_messages, messages);
+        // the last node is always EOF
+        // skipped tokens should be kept in CST
+        assert_eq!(self.code, unparse(&stmts));
+        assert_eq!(self.expected_outputs.len() + 1, stmts.len());
+        assert_eq!(NodeType::EOF, stmts[stmts.len() - 1].node_type);
+        for (expected_output, stmt) in self.expected_outputs.iter().zip(stmts.iter()) {
+            let result = stmt.to_string();
+            let changeset = Changeset::new(expected_output.as_str(), result.as_str(), "\n");
+            println!("{}\n", changeset);
+            assert_eq!(expected_output, &result);
+        }
+    }
+}
+
+#[test]
+fn test_parse_code_with_recovery() {
+    let test_cases = vec![
+        // ----- no error -----
+        RecoveryTestCase::new(
+            "SELECT 1;",
+            vec![
+                "\
+self: SELECT (SelectStatement)
+exprs:
+- self: 1 (NumericLiteral)
+semicolon:
+  self: ; (Symbol)
+",
+            ],
+            vec![],
+        ),
+        // ----- skipped until `;` -----
+        RecoveryTestCase::new(
+            "\
+SELEC (;);
+SELECT 2;
+",
+            vec![
+                "\
+self: SELEC (Error)
+semicolon:
+  self: ; (Symbol)
+skipped_tokens:
+- self: ( (Unknown)
+- self: ; (Unknown)
+- self: ) (Unknown)
+",
+                "\
+self: SELECT (SelectStatement)
+exprs:
+- self: 2 (NumericLiteral)
+semicolon:
+  self: ; (Symbol)
+",
+            ],
+            vec!["1:1: Expected the beginning of a statement but got: `SELEC`"],
+        ),
+        // ----- skipped until the next statement -----
+        RecoveryTestCase::new(
+            "\
+SELECT a NOT b
+SELECT 2
+",
+            vec![
+                "\
+self: SELECT (Error)
+skipped_tokens:
+- self: a (Identifier)
+- self: NOT (Unknown)
+- self: b (Identifier)
+",
+                "\
+self: SELECT (SelectStatement)
+exprs:
+- self: 2 (NumericLiteral)
+",
+            ],
+            vec!["1:14: Expected `IN`, `LIKE` or `BETWEEN` but got: token `b`"],
+        ),
+        // ----- multiple errors -----
+        RecoveryTestCase::new(
+            "\
+SELEC 1;
+-- comment
+SELECT 2;
+ALTER TABLE t FOO;
+",
+            vec![
+                "\
+self: SELEC (Error)
+semicolon:
+  self: ; (Symbol)
+skipped_tokens:
+- self: 1 (NumericLiteral)
+",
+                "\
+self: SELECT (SelectStatement)
+exprs:
+- self: 2 (NumericLiteral)
+leading_comments:
+- self: -- comment (Comment)
+semicolon:
+  self: ; (Symbol)
+",
+                "\
+self: ALTER (Error)
+semicolon:
+  self: ; (Symbol)
+skipped_tokens:
+- self: TABLE (Identifier)
+- self: t (Identifier)
+- self: FOO (Identifier)
+",
+            ],
+            vec![
+                "1:1: Expected the beginning of a statement but got: `SELEC`",
+                "4:15: Expected `SET`, `ADD`, `RENAME`, `DROP` or `ALTER` but got: token `FOO`",
+            ],
+        ),
+        // ----- keywords inside the statement are not the beginning of a statement -----
+        RecoveryTestCase::new(
+            "\
+MERGE t USING s ON t.id = s.id
+WHEN MATCHED THEN
+  UPDATE SET x = a NOT b
+WHEN NOT MATCHED THEN
+  INSERT ROW
+SELECT 2;
+",
+            vec![
+                "\
+self: MERGE (Error)
+skipped_tokens:
+- self: t (Identifier)
+- self: USING (Unknown)
+- self: s (Identifier)
+- self: ON (Unknown)
+- self: t (Identifier)
+- self: . (Unknown)
+- self: id (Identifier)
+- self: = (Unknown)
+- self: s (Identifier)
+- self: . (Unknown)
+- self: id (Identifier)
+- self: WHEN (Unknown)
+- self: MATCHED (Identifier)
+- self: THEN (Unknown)
+- self: UPDATE (Identifier)
+- self: SET (Unknown)
+- self: x (Identifier)
+- self: = (Unknown)
+- self: a (Identifier)
+- self: NOT (Unknown)
+- self: b (Identifier)
+- self: WHEN (Unknown)
+- self: NOT (Unknown)
+- self: MATCHED (Identifier)
+- self: THEN (Unknown)
+- self: INSERT (Identifier)
+- self: ROW (Identifier)
+",
+                "\
+self: SELECT (SelectStatement)
+exprs:
+- self: 2 (NumericLiteral)
+semicolon:
+  self: ; (Symbol)
+",
+            ],
+            vec!["3:24: Expected `IN`, `LIKE` or `BETWEEN` but got: token `b`"],
+        ),
+    ];
+    for t in test_cases {
+        t.test();
+    }
+}
@@ -110,6 +110,29 @@ semicolon:
 self: EXCEPT (SetOperator)
 distinct_or_all:
   self: DISTINCT (Keyword)
+left:
+  self: ( (GroupedStatement)
+  rparen:
+    self: ) (Symbol)
+  stmt:
+    self: SELECT (SelectStatement)
+    exprs:
+    - self: 1 (NumericLiteral)
+right:
+  self: SELECT (SelectStatement)
+  exprs:
+  - self: 2 (NumericLiteral)
+semicolon:
+  self: ; (Symbol)
+",
+        ),
+        // without ALL or DISTINCT (invalid in BigQuery)
+        TestCase::new(
+            "\
+(SELECT 1) UNION SELECT 2;
+",
+            "\
+self: UNION (SetOperator)
 left:
   self: ( (GroupedStatement)
   rparen:
@@ -1580,4 +1603,3 @@ limit:
         t.test(0);
     }
 }
-
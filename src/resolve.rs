@@ -0,0 +1,487 @@
+// name resolution
+// links identifiers to their declarations (FROM items, CTEs, script variables, ...)
+// see walk.rs for the traversal, columns.rs for the columns of queries
+// and `Binder` for the binding itself
+mod columns;
+#[cfg(test)]
+mod tests;
+mod walk;
+
+use crate::catalog::{Catalog, Field, Issue, Table, Type};
+use crate::cst::Node;
+use crate::names::{contains, eq, path, span, unquote, IMPLICIT_NAMES};
+use crate::token::Span;
+use std::collections::HashMap;
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Binding {
+    Cte(Span),              // WITH cte AS (...)
+    Relation(Span), // FROM item referenced by its alias (`t` in `t.col`, `x` in `UNNEST(arr) AS x`)
+    Column { table: Span }, // a column of the FROM item
+    SelectAlias(Span), // SELECT expr AS alias ... ORDER BY alias
+    Window(Span),   // WINDOW w AS (...)
+    Variable(Span), // DECLARE x
+    Parameter(Span), // CREATE PROCEDURE p(x INT64)
+    Unresolved,
+    Ambiguous(Vec<Span>), // the declarations of the candidates
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Reference {
+    pub name: String, // without backquotes
+    pub span: Span,
+    pub binding: Binding,
+}
+
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Resolution {
+    pub references: Vec<Reference>, // in source order
+}
+
+impl Resolution {
+    // the binding of `Identifier` node
+    pub fn binding(&self, node: &Node) -> Option<&Binding> {
+        let start = node.token.as_ref()?.start;
+        self.references
+            .binary_search_by_key(&start, |r| r.span.start)
+            .ok()
+            .map(|i| &self.references[i].binding)
+    }
+}
+
+// statements are resolved together because script variables live across statements
+pub fn resolve(stmts: &[Node]) -> Resolution {
+    analyze(stmts, None, &Untyped).resolution
+}
+
+// the types of expressions are given by `Typing` while names are resolved
+// because the columns of FROM items (e.g. subqueries) and their types depend on them
+// `infer::Inference` infers the types, `resolve()` does not
+pub(crate) trait Typing {
+    // NumericLiteral, StringLiteral, BooleanLiteral and IntervalLiteral
+    fn literal(&self, node: &Node) -> Type;
+    fn unary(&self, operator: &str, operand: Type) -> Type;
+    fn binary(&self, operator: &str, left: &Type, right: &Type) -> Type;
+    // CASE, [a, b, ...], ...
+    fn common_type(&self, exprs: &[(&Node, Type)]) -> Type;
+    // `name` is upper case without `SAFE.`
+    fn function(&self, name: &str, args: &[(&Node, Type)]) -> Type;
+    // the columns of set operations
+    fn supertype(&self, a: &Type, b: &Type) -> Option<Type>;
+}
+
+// every expression is `Type::Unknown`
+struct Untyped;
+
+impl Typing for Untyped {
+    fn literal(&self, _: &Node) -> Type {
+        Type::Unknown
+    }
+    fn unary(&self, _: &str, _: Type) -> Type {
+        Type::Unknown
+    }
+    fn binary(&self, _: &str, _: &Type, _: &Type) -> Type {
+        Type::Unknown
+    }
+    fn common_type(&self, _: &[(&Node, Type)]) -> Type {
+        Type::Unknown
+    }
+    fn function(&self, _: &str, _: &[(&Node, Type)]) -> Type {
+        Type::Unknown
+    }
+    fn supertype(&self, _: &Type, _: &Type) -> Option<Type> {
+        Some(Type::Unknown)
+    }
+}
+
+pub(crate) struct Analysis {
+    pub resolution: Resolution,
+    pub issues: Vec<Issue>,          // `catalog::validate()` reports them
+    pub exprs: HashMap<usize, Type>, // keyed by the start of the token of the expression
+    pub queries: HashMap<usize, Vec<Field>>, // anonymous columns have empty names
+    pub tables: Vec<Table>,          // created by the statements (only if the catalog is given)
+}
+
+pub(crate) fn analyze(stmts: &[Node], catalog: Option<&Catalog>, typing: &dyn Typing) -> Analysis {
+    let mut analyzer = Analyzer {
+        binder: Binder::default(),
+        typing,
+        catalog,
+        tables: Vec::new(),
+        issues: Vec::new(),
+        exprs: HashMap::new(),
+        queries: HashMap::new(),
+    };
+    for stmt in stmts {
+        analyzer.node(stmt);
+    }
+    let mut issues = std::mem::take(&mut analyzer.binder.issues);
+    issues.extend(analyzer.issues);
+    Analysis {
+        resolution: analyzer.binder.into_resolution(),
+        issues,
+        exprs: analyzer.exprs,
+        queries: analyzer.queries,
+        tables: analyzer.tables,
+    }
+}
+
+struct Analyzer<'a> {
+    binder: Binder,
+    typing: &'a dyn Typing,
+    catalog: Option<&'a Catalog>,
+    tables: Vec<Table>, // created by the statements (only if the catalog is given)
+    issues: Vec<Issue>, // unknown tables, fields, ... (unknown names are found by `binder`)
+    exprs: HashMap<usize, Type>,
+    queries: HashMap<usize, Vec<Field>>,
+}
+
+// FROM item (table, CTE, subquery, UNNEST, ...)
+// the scopes below are shared with `lineage()`, whose relations know the sources of columns
+pub(crate) trait Relation {
+    // the alias or the name of the table (None if anonymous)
+    fn name(&self) -> Option<&str>;
+    // UNNEST(arr) AS x (`x` is not a table but a value)
+    fn is_value(&self) -> bool;
+    // None if it is unknown (e.g. tables without schema)
+    fn has_column(&self, name: &str) -> Option<bool>;
+    fn is_named(&self, name: &str) -> bool {
+        self.name().is_some_and(|n| eq(n, name))
+    }
+}
+
+pub(crate) enum Found<'a, R> {
+    Value(&'a R), // UNNEST(arr) AS x
+    Column(&'a R),
+    Using(Vec<&'a R>), // JOIN ... USING (col) (every relation which has the column)
+    Ambiguous(Vec<&'a R>),
+}
+
+// the relation which has the column
+// if `certain`, the relations which certainly have the column (and values) are searched
+// otherwise, the relations whose columns are unknown are searched
+pub(crate) fn find_column<'a, R: Relation>(
+    relations: &'a [R],
+    using: &[String],
+    name: &str,
+    certain: bool,
+) -> Option<Found<'a, R>> {
+    if certain {
+        if let Some(value) = relations.iter().find(|r| r.is_value() && r.is_named(name)) {
+            return Some(Found::Value(value));
+        }
+    }
+    let expected = if certain { Some(true) } else { None };
+    let mut candidates: Vec<&R> = relations
+        .iter()
+        .filter(|r| !r.is_value() && r.has_column(name) == expected)
+        .collect();
+    match candidates.len() {
+        0 => None,
+        1 => candidates.pop().map(Found::Column),
+        _ if using.iter().any(|u| eq(u, name)) => Some(Found::Using(candidates)),
+        _ => Some(Found::Ambiguous(candidates)),
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Scope<R> {
+    pub relations: Vec<R>,
+    pub using: Vec<String>,
+    pub aliases: Vec<(String, Span)>, // SELECT expr AS alias
+    pub windows: Vec<(String, Span)>, // WINDOW w AS (...)
+}
+
+impl<R> Default for Scope<R> {
+    fn default() -> Scope<R> {
+        Scope {
+            relations: Vec::new(),
+            using: Vec::new(),
+            aliases: Vec::new(),
+            windows: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Scopes<R> {
+    pub ctes: Vec<R>,
+    pub scopes: Vec<Scope<R>>, // the last one is the innermost
+}
+
+impl<R> Default for Scopes<R> {
+    fn default() -> Scopes<R> {
+        Scopes {
+            ctes: Vec::new(),
+            scopes: Vec::new(),
+        }
+    }
+}
+
+impl<R: Relation> Scopes<R> {
+    pub fn innermost(&self) -> Option<&Scope<R>> {
+        self.scopes.last()
+    }
+    pub fn innermost_mut(&mut self) -> &mut Scope<R> {
+        self.scopes.last_mut().unwrap()
+    }
+    pub fn push_relation(&mut self, relation: R) {
+        self.innermost_mut().relations.push(relation);
+    }
+    // every relation from the innermost scope
+    pub fn relations(&self) -> impl Iterator<Item = &R> {
+        self.scopes.iter().rev().flat_map(|s| &s.relations)
+    }
+    pub fn find_cte(&self, name: &str) -> Option<&R> {
+        self.ctes.iter().rev().find(|c| c.is_named(name))
+    }
+    pub fn find_relation(&self, name: &str) -> Option<&R> {
+        self.relations().find(|r| r.is_named(name))
+    }
+    // the inner scopes are searched first (see `find_column()` for `certain`)
+    pub fn find_column(&self, name: &str, certain: bool) -> Option<Found<'_, R>> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|s| find_column(&s.relations, &s.using, name, certain))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct FromItem {
+    pub name: Option<String>,
+    pub span: Span,                  // the declaration (alias or table name)
+    pub columns: Option<Vec<Field>>, // None if unknown
+    pub value: Option<Type>,         // UNNEST(arr) AS x (`x` is not a table but a value)
+}
+
+impl Relation for FromItem {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    fn is_value(&self) -> bool {
+        self.value.is_some()
+    }
+    fn has_column(&self, name: &str) -> Option<bool> {
+        let columns = self.columns.as_ref()?;
+        Some(columns.iter().any(|c| eq(&c.name, name)))
+    }
+}
+
+// the declaration of FROM item: its alias or the last part of its name
+pub(crate) fn declare(node: &Node, default: Option<&Node>) -> (Option<String>, Span) {
+    match node.get_node("alias").or(default) {
+        Some(alias) => (Some(unquote(alias)), span(alias)),
+        None => (None, span(node)),
+    }
+}
+
+// the scopes and the references found so far
+// unknown names are reported as issues, which `catalog::validate()` turns into errors
+#[derive(Debug, Default)]
+pub(crate) struct Binder {
+    references: Vec<Reference>,
+    pub scopes: Scopes<FromItem>,
+    pub variables: Vec<(String, Binding, Type)>, // script variables and parameters
+    pub prefer_aliases: bool,                    // in GROUP BY, ORDER BY, ...
+    pub issues: Vec<Issue>,
+}
+
+impl Binder {
+    pub fn into_resolution(self) -> Resolution {
+        let mut references = self.references;
+        references.sort_by_key(|r| r.span.start);
+        Resolution { references }
+    }
+    pub fn push_reference(&mut self, node: &Node, binding: Binding) {
+        self.references.push(Reference {
+            name: unquote(node),
+            span: span(node),
+            binding,
+        });
+    }
+    pub fn push_scope(&mut self) {
+        self.scopes.scopes.push(Scope::default());
+    }
+    pub fn pop_scope(&mut self) {
+        self.scopes.scopes.pop();
+    }
+    // the aliases and the named windows of `SelectStatement`
+    pub fn declare_select(&mut self, node: &Node) {
+        let scope = self.scopes.innermost_mut();
+        for expr in node.get_node_vec("exprs").into_iter().flatten() {
+            if let Some(alias) = expr.get_node("alias") {
+                scope.aliases.push((unquote(alias), span(alias)));
+            }
+        }
+        let window = node.get_node("window");
+        for w in window
+            .and_then(|w| w.get_node_vec("window_exprs"))
+            .into_iter()
+            .flatten()
+        {
+            scope.windows.push((unquote(w), span(w)));
+        }
+    }
+    pub fn window(&mut self, node: &Node) {
+        let name = unquote(node);
+        let found = self
+            .scopes
+            .innermost()
+            .and_then(|s| s.windows.iter().find(|(w, _)| eq(w, &name)));
+        let binding = match found {
+            Some((_, span)) => Binding::Window(span.clone()),
+            None => Binding::Unresolved,
+        };
+        self.push_reference(node, binding);
+    }
+    // `t` of `t.*` (None if `t` is not a FROM item)
+    pub fn relation(&mut self, node: &Node) -> Option<FromItem> {
+        let relation = match path(node) {
+            Some(parts) if parts.len() == 1 => self.scopes.find_relation(&unquote(node))?.clone(),
+            _ => return None,
+        };
+        self.push_reference(node, Binding::Relation(relation.span.clone()));
+        Some(relation)
+    }
+    // col | t.col | col.field | t.col.field | ...
+    // returns the type of the column and the number of the parts which are bound
+    // (the rest are the fields of STRUCT)
+    // None if the first part is a niladic function (e.g. CURRENT_DATE)
+    pub fn bind_path(&mut self, parts: &[&Node]) -> Option<(Type, usize)> {
+        let first = unquote(parts[0]);
+        if parts.len() > 1 {
+            if let Some(relation) = self.scopes.find_relation(&first).cloned() {
+                self.push_reference(parts[0], Binding::Relation(relation.span.clone()));
+                if let Some(value) = relation.value {
+                    return Some((value, 1));
+                }
+                let column = unquote(parts[1]);
+                let field = relation
+                    .columns
+                    .as_ref()
+                    .map(|columns| columns.iter().find(|c| eq(&c.name, &column)));
+                let (binding, ty) = match field {
+                    Some(None) => {
+                        self.issues.push(Issue::UnknownColumn {
+                            relation: first,
+                            column,
+                            span: span(parts[1]),
+                        });
+                        (Binding::Unresolved, Type::Unknown)
+                    }
+                    field => {
+                        let ty = field
+                            .flatten()
+                            .map_or(Type::Unknown, |f| f.data_type.clone());
+                        let binding = Binding::Column {
+                            table: relation.span,
+                        };
+                        (binding, ty)
+                    }
+                };
+                self.push_reference(parts[1], binding);
+                return Some((ty, 2));
+            }
+        }
+        match self.column(&first) {
+            Some((binding, ty)) => {
+                if let Binding::Ambiguous(spans) = &binding {
+                    // the tables without schema may not have the column
+                    let certain = self
+                        .scopes
+                        .relations()
+                        .any(|r| r.span == spans[0] && r.columns.is_some());
+                    if certain {
+                        self.issues.push(Issue::AmbiguousColumn {
+                            name: first,
+                            span: span(parts[0]),
+                        });
+                    }
+                }
+                self.push_reference(parts[0], binding);
+                Some((ty, 1))
+            }
+            None if contains(IMPLICIT_NAMES, &first) => None,
+            None => {
+                self.push_reference(parts[0], Binding::Unresolved);
+                // UNNEST without alias may have the column
+                let uncertain = self
+                    .scopes
+                    .relations()
+                    .any(|r| r.name.is_none() && r.value.as_ref() == Some(&Type::Unknown));
+                if !uncertain {
+                    self.issues.push(Issue::UnknownName {
+                        name: first,
+                        span: span(parts[0]),
+                    });
+                }
+                Some((Type::Unknown, 1))
+            }
+        }
+    }
+    // `col` of `SET col = ...` is a column of the target table of UPDATE or MERGE
+    // false if there is no target
+    pub fn bind_target(&mut self, node: &Node) -> bool {
+        let target = match self.scopes.innermost().and_then(|s| s.relations.first()) {
+            Some(target) => target.clone(),
+            None => return false,
+        };
+        let column = unquote(node);
+        if target.has_column(&column) == Some(false) {
+            self.issues.push(Issue::UnknownName {
+                name: column,
+                span: span(node),
+            });
+        }
+        self.push_reference(node, Binding::Column { table: target.span });
+        true
+    }
+    fn column(&self, name: &str) -> Option<(Binding, Type)> {
+        let alias = self.scopes.innermost().and_then(|s| {
+            let alias = s.aliases.iter().rev().find(|(a, _)| eq(a, name));
+            alias.map(|(_, span)| (Binding::SelectAlias(span.clone()), Type::Unknown))
+        });
+        if self.prefer_aliases && alias.is_some() {
+            return alias;
+        }
+        // the columns which certainly exist
+        if let Some(found) = self.scopes.find_column(name, true) {
+            return Some(Binder::bind(found, name));
+        }
+        let variable = self.variables.iter().rev().find(|(v, _, _)| eq(v, name));
+        if let Some((_, binding, ty)) = variable {
+            return Some((binding.clone(), ty.clone()));
+        }
+        // the columns which may exist (tables without schema, `SELECT *`, ...)
+        if let Some(found) = self.scopes.find_column(name, false) {
+            return Some(Binder::bind(found, name));
+        }
+        alias
+    }
+    fn bind(found: Found<FromItem>, name: &str) -> (Binding, Type) {
+        let column = |relation: &FromItem| {
+            let table = relation.span.clone();
+            let column = relation
+                .columns
+                .iter()
+                .flatten()
+                .find(|c| eq(&c.name, name));
+            let ty = column.map_or(Type::Unknown, |c| c.data_type.clone());
+            (Binding::Column { table }, ty)
+        };
+        match found {
+            Found::Value(relation) => (
+                Binding::Relation(relation.span.clone()),
+                relation.value.clone().unwrap(),
+            ),
+            Found::Column(relation) => column(relation),
+            // JOIN ... USING (col)
+            Found::Using(relations) => column(relations[0]),
+            Found::Ambiguous(relations) => {
+                let spans = relations.iter().map(|r| r.span.clone()).collect();
+                (Binding::Ambiguous(spans), Type::Unknown)
+            }
+        }
+    }
+}
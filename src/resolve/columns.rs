@@ -0,0 +1,179 @@
+// the columns of queries and the tables created by statements
+use super::Analyzer;
+use crate::catalog::{self, Field, Type};
+use crate::cst::{Node, NodeType};
+use crate::names::{eq, implicit_name, implicit_names, path, unquote};
+
+impl<'a> Analyzer<'a> {
+    // None if any column is unknown (e.g. `SELECT *` from tables without schema)
+    pub fn select_columns(&self, node: &Node) -> Option<Vec<Field>> {
+        let mut columns = Vec::new();
+        for expr in node.get_node_vec("exprs")? {
+            let asterisk = match expr.node_type {
+                NodeType::Asterisk => expr,
+                NodeType::DotOperator
+                    if expr
+                        .get_node("right")
+                        .is_some_and(|r| r.node_type == NodeType::Asterisk) =>
+                {
+                    expr.get_node("right").unwrap()
+                }
+                _ => {
+                    columns.push(Field::new(&implicit_name(expr), self.type_of(expr)));
+                    continue;
+                }
+            };
+            let mut fields = match expr.get_node("left") {
+                Some(left) => self.expand(left)?,
+                None => self.asterisk()?,
+            };
+            for except in grouped_exprs(asterisk, "except") {
+                fields.retain(|f| !eq(&f.name, &unquote(except)));
+            }
+            for replace in grouped_exprs(asterisk, "replace") {
+                let name = implicit_name(replace);
+                for field in fields.iter_mut().filter(|f| eq(&f.name, &name)) {
+                    field.data_type = self.type_of(replace);
+                }
+            }
+            columns.extend(fields);
+        }
+        match as_struct_or_value(node).as_deref() {
+            // SELECT AS VALUE STRUCT(...) is same as SELECT AS STRUCT ...
+            Some("VALUE") => match columns.pop() {
+                Some(Field {
+                    data_type: Type::Struct(fields),
+                    ..
+                }) => Some(fields),
+                Some(column) => Some(vec![Field::new("", column.data_type)]),
+                None => None,
+            },
+            _ => Some(columns),
+        }
+    }
+    // `SELECT *`
+    fn asterisk(&self) -> Option<Vec<Field>> {
+        let scope = self.binder.scopes.innermost()?;
+        let mut fields: Vec<Field> = Vec::new();
+        for relation in &scope.relations {
+            match (&relation.columns, &relation.value) {
+                (Some(columns), _) => {
+                    for column in columns {
+                        // the columns of `USING (...)` appear once
+                        let joined = scope.using.iter().any(|u| eq(u, &column.name))
+                            && fields.iter().any(|f| eq(&f.name, &column.name));
+                        if !joined {
+                            fields.push(column.clone());
+                        }
+                    }
+                }
+                // UNNEST(arr) AS x, WITH OFFSET AS o
+                (None, Some(value)) => {
+                    let name = relation.name.as_deref().unwrap_or("");
+                    fields.push(Field::new(name, value.clone()));
+                }
+                (None, None) => return None,
+            }
+        }
+        Some(fields)
+    }
+    // `t.*` or `struct_col.*`
+    fn expand(&self, left: &Node) -> Option<Vec<Field>> {
+        let relation = match path(left) {
+            Some(parts) if parts.len() == 1 => self.binder.scopes.find_relation(&unquote(left)),
+            _ => None,
+        };
+        let ty = match relation {
+            Some(relation) => match (&relation.columns, &relation.value) {
+                (Some(columns), _) => return Some(columns.clone()),
+                (None, Some(value)) => value.clone(),
+                (None, None) => return None,
+            },
+            None => self.type_of(left),
+        };
+        match ty {
+            Type::Struct(fields) => Some(fields),
+            _ => None,
+        }
+    }
+    fn type_of(&self, expr: &Node) -> Type {
+        let ty = expr.token.as_ref().and_then(|t| self.exprs.get(&t.start));
+        ty.cloned().unwrap_or(Type::Unknown)
+    }
+    // the columns of `SelectStatement`, `SetOperator`, `GroupedStatement` or `CREATE TABLE`
+    pub fn query(&self, node: &Node) -> Option<&Vec<Field>> {
+        match node.node_type {
+            NodeType::GroupedStatement => self.query(node.get_node("stmt")?),
+            _ => self.queries.get(&node.token.as_ref()?.start),
+        }
+    }
+    // `(SELECT ...)` or `ARRAY(SELECT ...)`
+    pub fn query_type(&self, query: &Node) -> Type {
+        let columns = match self.query(query) {
+            Some(columns) => columns,
+            None => return Type::Unknown,
+        };
+        match as_struct_or_value(query).as_deref() {
+            Some("STRUCT") => Type::Struct(columns.clone()),
+            Some("VALUE") if columns.len() != 1 || !columns[0].name.is_empty() => {
+                Type::Struct(columns.clone())
+            }
+            _ if columns.len() == 1 => columns[0].data_type.clone(),
+            _ => Type::Unknown,
+        }
+    }
+    // the columns are coerced to the common types
+    // (NULL and unknown columns are coerced to the type of the other side)
+    pub fn set_operator_columns(&self, node: &Node) -> Option<Vec<Field>> {
+        let query = |key| node.get_node(key).and_then(|q| self.query(q));
+        let columns = match (query("left"), query("right")) {
+            (Some(left), Some(right)) if left.len() == right.len() => left
+                .iter()
+                .zip(right)
+                .map(|(l, r)| {
+                    let ty = match (&l.data_type, &r.data_type) {
+                        (Type::Unknown, ty) | (ty, Type::Unknown) => ty.clone(),
+                        (l, r) => self.typing.supertype(l, r).unwrap_or(Type::Unknown),
+                    };
+                    Field::new(&l.name, ty)
+                })
+                .collect(),
+            (Some(left), _) => left.clone(),
+            (None, _) => return None,
+        };
+        Some(columns)
+    }
+    // CREATE TABLE ... AS SELECT
+    pub fn created_table(&mut self, node: &Node) {
+        let query = node.get_node("as").and_then(|a| a.get_node("stmt"));
+        let columns = query.and_then(|q| self.query(q));
+        let columns = columns.map(|c| implicit_names(c));
+        if let Some(table) = catalog::created_table(node, columns) {
+            if let Some(columns) = &table.columns {
+                let start = node.token.as_ref().unwrap().start;
+                self.queries.insert(start, columns.clone());
+            }
+            self.tables.push(table);
+        }
+    }
+}
+
+// the exprs of `EXCEPT (...)` and `REPLACE (...)`
+fn grouped_exprs<'a>(node: &'a Node, key: &str) -> &'a [Node] {
+    let group = node.get_node(key).and_then(|n| n.get_node("group"));
+    group
+        .and_then(|g| g.get_node_vec("exprs"))
+        .map_or(&[], |e| e)
+}
+
+// `STRUCT` or `VALUE` of `SELECT AS STRUCT` (the first SELECT of set operations)
+fn as_struct_or_value(query: &Node) -> Option<String> {
+    match query.node_type {
+        NodeType::GroupedStatement => as_struct_or_value(query.get_node("stmt")?),
+        NodeType::SetOperator => as_struct_or_value(query.get_node("left")?),
+        _ => {
+            let keywords = query.get_node_vec("as_struct_or_value")?;
+            Some(keywords.last()?.token.as_ref()?.literal.to_uppercase())
+        }
+    }
+}
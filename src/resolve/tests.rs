@@ -0,0 +1,148 @@
+use super::*;
+use crate::parser::Parser;
+
+// one line per reference: `name (line:column) -> binding (line:column of the declaration)`
+fn test_resolve(code: &str, expected: &str) {
+    let stmts = Parser::new(code.to_string()).unwrap().parse_code().unwrap();
+    let pos = |s: &Span| format!("{}:{}", s.line, s.column);
+    let result: Vec<String> = resolve(&stmts)
+        .references
+        .iter()
+        .map(|r| {
+            let binding = match &r.binding {
+                Binding::Cte(s) => format!("cte {}", pos(s)),
+                Binding::Relation(s) => format!("relation {}", pos(s)),
+                Binding::Column { table } => format!("column of {}", pos(table)),
+                Binding::SelectAlias(s) => format!("alias {}", pos(s)),
+                Binding::Window(s) => format!("window {}", pos(s)),
+                Binding::Variable(s) => format!("variable {}", pos(s)),
+                Binding::Parameter(s) => format!("parameter {}", pos(s)),
+                Binding::Unresolved => "unresolved".to_string(),
+                Binding::Ambiguous(ss) => {
+                    let ss: Vec<String> = ss.iter().map(pos).collect();
+                    format!("ambiguous {}", ss.join(", "))
+                }
+            };
+            format!("{} ({}) -> {}", r.name, pos(&r.span), binding)
+        })
+        .collect();
+    assert_eq!(expected, result.join("\n"), "{}", code);
+}
+
+#[test]
+fn test_resolve_select() {
+    test_resolve(
+        "\
+SELECT t.a, b, c, u.x.y AS d
+FROM t JOIN (SELECT 1 AS c) AS u ON t.id = u.id
+ORDER BY d",
+        "\
+t (1:8) -> relation 2:6
+a (1:10) -> column of 2:6
+b (1:13) -> column of 2:6
+c (1:16) -> column of 2:32
+u (1:19) -> relation 2:32
+x (1:21) -> unresolved
+t (2:37) -> relation 2:6
+id (2:39) -> column of 2:6
+u (2:44) -> relation 2:32
+id (2:46) -> unresolved
+d (3:10) -> alias 1:28",
+    );
+    // ambiguous without schema
+    test_resolve(
+        "SELECT a, id FROM t1 JOIN t2 USING (id) WHERE missing IS NULL",
+        "\
+a (1:8) -> ambiguous 1:19, 1:27
+id (1:11) -> column of 1:19
+missing (1:47) -> ambiguous 1:19, 1:27",
+    );
+    // correlated subquery
+    test_resolve(
+        "SELECT (SELECT MAX(x) FROM u WHERE u.id = t.id) FROM t",
+        "\
+x (1:20) -> column of 1:28
+u (1:36) -> relation 1:28
+id (1:38) -> column of 1:28
+t (1:43) -> relation 1:54
+id (1:45) -> column of 1:54",
+    );
+}
+
+#[test]
+fn test_resolve_cte_and_unnest() {
+    test_resolve(
+        "\
+WITH cte AS (SELECT 1 AS a)
+SELECT a, x, o, x.f, cte.a, e, SUM(a) OVER w
+FROM cte, UNNEST(cte.arr) AS x WITH OFFSET AS o, cte.structs AS e
+WINDOW w AS (ORDER BY o)",
+        "\
+a (2:8) -> column of 3:6
+x (2:11) -> relation 3:30
+o (2:14) -> relation 3:47
+x (2:17) -> relation 3:30
+cte (2:22) -> relation 3:6
+a (2:26) -> column of 3:6
+e (2:29) -> relation 3:65
+a (2:36) -> column of 3:6
+w (2:44) -> window 4:8
+cte (3:6) -> cte 1:6
+cte (3:18) -> relation 3:6
+arr (3:22) -> unresolved
+cte (3:50) -> relation 3:6
+structs (3:54) -> unresolved
+o (4:23) -> relation 3:47",
+    );
+}
+
+#[test]
+fn test_resolve_script() {
+    test_resolve(
+        "\
+DECLARE x INT64 DEFAULT 1;
+BEGIN
+  DECLARE y INT64;
+  SET y = x + 1;
+  SELECT x, y, z FROM t WHERE x > 0;
+END;
+SELECT y;
+CREATE PROCEDURE p(a INT64)
+BEGIN
+  SELECT a;
+END;
+UPDATE t SET c = x FROM u WHERE t.id = u.id;",
+        "\
+y (4:7) -> variable 3:11
+x (4:11) -> variable 1:9
+x (5:10) -> variable 1:9
+y (5:13) -> variable 3:11
+z (5:16) -> column of 5:23
+x (5:31) -> variable 1:9
+y (7:8) -> unresolved
+a (10:10) -> parameter 8:20
+c (12:14) -> column of 12:8
+x (12:18) -> variable 1:9
+t (12:33) -> relation 12:8
+id (12:35) -> column of 12:8
+u (12:40) -> relation 12:25
+id (12:42) -> column of 12:25",
+    );
+}
+
+#[test]
+fn test_resolution_binding() {
+    let stmts = Parser::new("SELECT x FROM UNNEST([1]) AS x".to_string())
+        .unwrap()
+        .parse_code()
+        .unwrap();
+    let resolution = resolve(&stmts);
+    let exprs = stmts[0].get_node_vec("exprs").unwrap();
+    assert!(matches!(
+        resolution.binding(&exprs[0]),
+        Some(Binding::Relation(_))
+    ));
+    // not a reference
+    let from = stmts[0].get_node("from").unwrap();
+    assert_eq!(resolution.binding(from), None);
+}
@@ -0,0 +1,653 @@
+// the traversal of statements
+// names are bound by `Binder` and the types of expressions (see `Typing`) are recorded on the way
+use super::{declare, Analyzer, Binding, FromItem};
+use crate::catalog::{self, Field, Issue, Type};
+use crate::cst::{Node, NodeType};
+use crate::names::DATE_PARTS;
+use crate::names::{contains, eq, implicit_name, path, span, span_between, table_name, unquote};
+
+impl<'a> Analyzer<'a> {
+    fn children(&mut self, node: &Node, skipped: &[&str]) {
+        for (key, child) in node.children_in_source_order() {
+            if !skipped.contains(&key) {
+                self.node(child);
+            }
+        }
+    }
+    pub fn node(&mut self, node: &Node) {
+        match node.node_type {
+            NodeType::SelectStatement => self.select(node),
+            NodeType::SetOperator => self.set_operator(node),
+            NodeType::Identifier
+            | NodeType::DotOperator
+            | NodeType::ArrayAccessing
+            | NodeType::CallingFunction
+            | NodeType::CallingArrayAccessingFunction
+            | NodeType::CastArgument
+            | NodeType::ExtractArgument
+            | NodeType::UnaryOperator
+            | NodeType::BinaryOperator
+            | NodeType::BetweenOperator
+            | NodeType::InOperator
+            | NodeType::CaseExpr
+            | NodeType::GroupedExpr
+            | NodeType::StructLiteral
+            | NodeType::ArrayLiteral
+            | NodeType::NumericLiteral
+            | NodeType::StringLiteral
+            | NodeType::BooleanLiteral
+            | NodeType::IntervalLiteral
+            | NodeType::NullLiteral
+            | NodeType::Parameter
+            | NodeType::GroupedStatement => {
+                self.expr(node);
+            }
+            NodeType::CallingTableFunction | NodeType::CallingUnnest => {
+                for (key, child) in node.children_in_source_order() {
+                    match key {
+                        "func" | "alias" | "offset_alias" => (),
+                        // DATE_TRUNC(d, MONTH), WEEK(MONDAY)
+                        "args"
+                            if child.node_type == NodeType::Identifier
+                                && contains(DATE_PARTS, &unquote(child)) => {}
+                        _ => self.node(child),
+                    }
+                }
+            }
+            NodeType::OverClause => match node.get_node("window") {
+                Some(w) if w.node_type == NodeType::Identifier => self.binder.window(w),
+                _ => self.children(node, &[]),
+            },
+            NodeType::WindowSpecification => {
+                if let Some(name) = node.get_node("name") {
+                    self.binder.window(name);
+                }
+                self.children(node, &["name"]);
+            }
+            NodeType::Type | NodeType::TypeDeclaration | NodeType::GroupedTypeDeclarations => (),
+            NodeType::BeginStatement => {
+                let len = self.binder.variables.len();
+                self.children(node, &[]);
+                self.binder.variables.truncate(len);
+            }
+            NodeType::DeclareStatement => {
+                self.children(node, &["idents"]);
+                let variable_type = node.get_node("variable_type");
+                let ty = variable_type.map_or(Type::Unknown, Type::from_node);
+                for ident in node.get_node_vec("idents").into_iter().flatten() {
+                    let binding = Binding::Variable(span(ident));
+                    self.binder
+                        .variables
+                        .push((unquote(ident), binding, ty.clone()));
+                }
+            }
+            NodeType::CreateFunctionStatement | NodeType::CreateProcedureStatement => {
+                let len = self.binder.variables.len();
+                let group = node.get_node("group");
+                let params = group.and_then(|g| g.get_node_vec("declarations"));
+                for param in params.into_iter().flatten().filter(|p| p.token.is_some()) {
+                    let binding = Binding::Parameter(span(param));
+                    let ty = param
+                        .get_node("type")
+                        .map_or(Type::Unknown, Type::from_node);
+                    self.binder.variables.push((unquote(param), binding, ty));
+                }
+                self.children(node, &["ident", "group", "returns", "options", "language"]);
+                self.binder.variables.truncate(len);
+            }
+            NodeType::InsertStatement => {
+                self.insert(node);
+                self.children(node, &["target_name", "columns"]);
+            }
+            NodeType::UpdateStatement | NodeType::DeleteStatement | NodeType::MergeStatement => {
+                self.dml(node)
+            }
+            // RAISE USING MESSAGE = '', GRANT `roles/...`
+            NodeType::RaiseStatement | NodeType::GrantStatement | NodeType::RevokeStatement => (),
+            _ => {
+                // the names of tables, new columns, ...
+                let skipped = [
+                    "alias",
+                    "ident",
+                    "what",
+                    "options",
+                    "table_name",
+                    "pivot",
+                    "unpivot",
+                    "clone",
+                    "partitionby",
+                    "clusterby",
+                    "column_name_list",
+                    "with_partition_columns",
+                    "add_columns",
+                    "drop_columns",
+                    "alter_column_stmt",
+                    "to",
+                ];
+                self.children(node, &skipped);
+                if self.catalog.is_some() {
+                    self.created_table(node);
+                }
+            }
+        }
+    }
+    fn with(&mut self, node: &Node) -> usize {
+        let len = self.binder.scopes.ctes.len();
+        let with = node.get_node("with");
+        let queries = with.and_then(|w| w.get_node_vec("queries"));
+        for query in queries.into_iter().flatten() {
+            let stmt = query.get_node("stmt");
+            if let Some(stmt) = stmt {
+                self.node(stmt);
+            }
+            self.binder.scopes.ctes.push(FromItem {
+                name: Some(unquote(query)),
+                span: span(query),
+                columns: stmt.and_then(|s| self.query(s)).cloned(),
+                value: None,
+            });
+        }
+        len
+    }
+    fn select(&mut self, node: &Node) {
+        let len = self.with(node);
+        self.binder.push_scope();
+        if let Some(from) = node.get_node("from").and_then(|f| f.get_node("expr")) {
+            self.table_expr(from);
+        }
+        self.binder.declare_select(node);
+        for (key, child) in node.children_in_source_order() {
+            match key {
+                "with" | "from" => (),
+                "exprs" if child.node_type == NodeType::Asterisk => {
+                    self.children(child, &["except"]);
+                }
+                // t.* | struct_col.*
+                "exprs"
+                    if child.get_node("right").map(|r| &r.node_type)
+                        == Some(&NodeType::Asterisk) =>
+                {
+                    let left = child.get_node("left").unwrap();
+                    if self.binder.relation(left).is_none() {
+                        self.node(left);
+                    }
+                    self.children(child.get_node("right").unwrap(), &["except"]);
+                }
+                "groupby" | "having" | "qualify" | "orderby" => {
+                    self.binder.prefer_aliases = true;
+                    self.node(child);
+                    self.binder.prefer_aliases = false;
+                }
+                _ => self.node(child),
+            }
+        }
+        if let Some(columns) = self.select_columns(node) {
+            let start = node.token.as_ref().unwrap().start;
+            self.queries.insert(start, columns);
+        }
+        self.binder.pop_scope();
+        self.binder.scopes.ctes.truncate(len);
+    }
+    fn set_operator(&mut self, node: &Node) {
+        let len = self.with(node);
+        self.children(node, &["with"]);
+        self.binder.scopes.ctes.truncate(len);
+        if let Some(columns) = self.set_operator_columns(node) {
+            let start = node.token.as_ref().unwrap().start;
+            self.queries.insert(start, columns);
+        }
+    }
+    fn dml(&mut self, node: &Node) {
+        // UPDATE and INSERT in MERGE statement use the scope of MERGE
+        let target = node.get_node("table_name");
+        if target.is_some() {
+            self.binder.push_scope();
+        }
+        if let Some(target) = target {
+            self.table_expr(target);
+        }
+        for (key, child) in node.children_in_source_order() {
+            match key {
+                "table_name" => (),
+                "from" | "using" => {
+                    if let Some(expr) = child.get_node("expr") {
+                        self.table_expr(expr);
+                    }
+                }
+                "set" => {
+                    for assignment in child.get_node_vec("exprs").into_iter().flatten() {
+                        // the left side is a column of the target table
+                        let left = assignment.get_node("left").and_then(path);
+                        match left {
+                            Some(parts)
+                                if parts.len() == 1 && self.binder.bind_target(parts[0]) =>
+                            {
+                                self.children(assignment, &["left"]);
+                            }
+                            _ => self.node(assignment),
+                        }
+                    }
+                }
+                _ => self.node(child),
+            }
+        }
+        if target.is_some() {
+            self.binder.pop_scope();
+        }
+    }
+    // the columns of the table (None if unknown), reports an issue if it is not found
+    fn table(&mut self, node: &Node) -> Option<Vec<Field>> {
+        let name = table_name(node)?;
+        let created = self
+            .tables
+            .iter()
+            .rev()
+            .find(|t| catalog::same_table(&t.name, &name));
+        match created.or_else(|| self.catalog?.table(&name)) {
+            Some(table) => table.columns.clone(),
+            None => {
+                let parts = path(node).unwrap();
+                let span = span_between(parts[0], parts[parts.len() - 1]);
+                self.issues.push(Issue::UnknownTable { name, span });
+                None
+            }
+        }
+    }
+    fn table_expr(&mut self, node: &Node) {
+        match node.node_type {
+            NodeType::JoinOperator => {
+                for (key, child) in node.children_in_source_order() {
+                    match key {
+                        "left" | "right" => self.table_expr(child),
+                        // USING (col)
+                        "using" => {
+                            let columns = child.get_node_vec("args").into_iter().flatten();
+                            let using: Vec<String> = columns.map(unquote).collect();
+                            self.binder.scopes.innermost_mut().using.extend(using);
+                        }
+                        _ => self.node(child),
+                    }
+                }
+            }
+            NodeType::GroupedExpr => {
+                if let Some(expr) = node.get_node("expr") {
+                    self.table_expr(expr)
+                }
+            }
+            NodeType::Identifier | NodeType::DotOperator => {
+                let parts = match path(node) {
+                    Some(parts) => parts,
+                    None => return self.node(node),
+                };
+                let (mut name, mut decl) = declare(node, parts.last().copied());
+                let mut columns = None;
+                let mut value = None;
+                let first = unquote(parts[0]);
+                let cte = self.binder.scopes.find_cte(&first).cloned();
+                match cte {
+                    Some(cte) if parts.len() == 1 => {
+                        self.binder.push_reference(parts[0], Binding::Cte(cte.span));
+                        columns = cte.columns;
+                    }
+                    // FROM t, t.arr (implicit UNNEST)
+                    _ if parts.len() > 1 && self.binder.scopes.find_relation(&first).is_some() => {
+                        value = Some(element(self.reference(node)));
+                    }
+                    _ => columns = self.table(node),
+                }
+                // PIVOT and UNPIVOT change the columns
+                for key in &["pivot", "unpivot"] {
+                    if let Some(operator) = node.get_node(key) {
+                        if let Some(alias) = operator.get_node("alias") {
+                            name = Some(unquote(alias));
+                            decl = span(alias);
+                        }
+                        columns = None;
+                    }
+                }
+                self.binder.scopes.push_relation(FromItem {
+                    name,
+                    span: decl,
+                    columns,
+                    value,
+                });
+                self.children(
+                    node,
+                    &["left", "right", "alias", "pivot", "unpivot", "offset_alias"],
+                );
+                self.offset(node);
+            }
+            NodeType::GroupedStatement => {
+                if let Some(stmt) = node.get_node("stmt") {
+                    self.node(stmt);
+                }
+                let (name, decl) = declare(node, None);
+                let columns = node.get_node("stmt").and_then(|s| self.query(s));
+                self.binder.scopes.push_relation(FromItem {
+                    name,
+                    span: decl,
+                    columns: columns.cloned(),
+                    value: None,
+                });
+            }
+            NodeType::CallingUnnest => {
+                let args: &[Node] = node.get_node_vec("args").map_or(&[], |a| a);
+                let element = match args.first() {
+                    Some(arg) => element(self.expr(arg)),
+                    None => Type::Unknown,
+                };
+                for arg in args.iter().skip(1) {
+                    self.node(arg);
+                }
+                self.children(node, &["func", "args", "alias", "offset_alias"]);
+                let (name, decl) = declare(node, None);
+                // the fields of STRUCT are columns if UNNEST has no alias
+                let (columns, value) = match (&name, element) {
+                    (None, Type::Struct(fields)) => (Some(fields), None),
+                    (_, element) => (None, Some(element)),
+                };
+                self.binder.scopes.push_relation(FromItem {
+                    name,
+                    span: decl,
+                    columns,
+                    value,
+                });
+                self.offset(node);
+            }
+            NodeType::CallingTableFunction => {
+                self.node(node);
+                let (name, decl) = declare(node, None);
+                self.binder.scopes.push_relation(FromItem {
+                    name,
+                    span: decl,
+                    columns: None,
+                    value: None,
+                });
+                self.offset(node);
+            }
+            _ => self.node(node),
+        }
+    }
+    // WITH OFFSET AS o
+    fn offset(&mut self, node: &Node) {
+        if let Some(alias) = node.get_node("offset_alias") {
+            self.binder.scopes.push_relation(FromItem {
+                name: Some(unquote(alias)),
+                span: span(alias),
+                columns: None,
+                value: Some(Type::Int64),
+            });
+        }
+    }
+    // INSERT t (col, ...)
+    fn insert(&mut self, node: &Node) {
+        // INSERT in MERGE statement inserts into the target of MERGE
+        let (name, columns) = match node.get_node("target_name") {
+            Some(target) => (table_name(target), self.table(target)),
+            None => match self
+                .binder
+                .scopes
+                .innermost()
+                .and_then(|s| s.relations.first())
+            {
+                Some(target) => (target.name.clone(), target.columns.clone()),
+                None => return,
+            },
+        };
+        let (name, columns) = match (name, columns) {
+            (Some(name), Some(columns)) => (name, columns),
+            _ => return,
+        };
+        let list = node
+            .get_node("columns")
+            .and_then(|c| c.get_node_vec("exprs"));
+        for column in list.into_iter().flatten() {
+            let column_name = unquote(column);
+            if !columns.iter().any(|c| eq(&c.name, &column_name)) {
+                self.issues.push(Issue::UnknownInsertColumn {
+                    table: name.clone(),
+                    column: column_name,
+                    span: span(column),
+                });
+            }
+        }
+    }
+    // visits the expression and returns its type (Unknown if it is not inferred)
+    fn expr(&mut self, node: &Node) -> Type {
+        let ty = match node.node_type {
+            NodeType::Identifier | NodeType::DotOperator if path(node).is_some() => {
+                let ty = self.reference(node);
+                self.children(node, &["left", "right", "alias"]);
+                ty
+            }
+            // STRUCT(1 AS a).a, f(x).a, ...
+            NodeType::DotOperator => {
+                let left = node
+                    .get_node("left")
+                    .map_or(Type::Unknown, |l| self.expr(l));
+                self.children(node, &["left", "right", "alias"]);
+                match node.get_node("right") {
+                    Some(right) if right.node_type == NodeType::Identifier => {
+                        self.field(left, right)
+                    }
+                    _ => Type::Unknown,
+                }
+            }
+            // arr[OFFSET(0)]
+            NodeType::ArrayAccessing => {
+                let left = node
+                    .get_node("left")
+                    .map_or(Type::Unknown, |l| self.expr(l));
+                self.children(node, &["left", "alias"]);
+                match left {
+                    Type::Array(element) => *element,
+                    // JSON subscript and STRUCT subscript
+                    Type::Json => Type::Json,
+                    Type::Struct(_) | Type::Unknown => Type::Unknown,
+                    ty => {
+                        if let Some(span) = node.span() {
+                            self.issues.push(Issue::NotArray { ty, span });
+                        }
+                        Type::Unknown
+                    }
+                }
+            }
+            NodeType::CallingFunction | NodeType::CallingArrayAccessingFunction => {
+                let mut args = Vec::new();
+                for (key, child) in node.children_in_source_order() {
+                    match key {
+                        "func" | "alias" => (),
+                        // DATE_TRUNC(d, MONTH), WEEK(MONDAY)
+                        "args"
+                            if child.node_type == NodeType::Identifier
+                                && contains(DATE_PARTS, &unquote(child)) =>
+                        {
+                            args.push((child, Type::Unknown))
+                        }
+                        "args" => {
+                            let ty = self.expr(child);
+                            args.push((child, ty));
+                        }
+                        _ => self.node(child),
+                    }
+                }
+                match node.get_node("func").and_then(table_name) {
+                    Some(name) => {
+                        let name = name.to_uppercase();
+                        let name = name.strip_prefix("SAFE.").unwrap_or(&name);
+                        self.typing.function(name, &args)
+                    }
+                    None => Type::Unknown,
+                }
+            }
+            // CAST(x AS STRING)
+            NodeType::CastArgument => {
+                self.children(node, &["cast_to"]);
+                let cast_to = node.get_node("cast_to");
+                cast_to.map_or(Type::Unknown, Type::from_node)
+            }
+            NodeType::ExtractArgument => {
+                self.children(node, &["extract_datepart"]);
+                Type::Unknown
+            }
+            // f(name => value)
+            NodeType::BinaryOperator if node.token.as_ref().is_some_and(|t| t.is("=>")) => {
+                let right = node.get_node("right");
+                self.children(node, &["left", "right"]);
+                right.map_or(Type::Unknown, |r| self.expr(r))
+            }
+            NodeType::BinaryOperator => {
+                let left = node
+                    .get_node("left")
+                    .map_or(Type::Unknown, |l| self.expr(l));
+                let right = node
+                    .get_node("right")
+                    .map_or(Type::Unknown, |r| self.expr(r));
+                self.children(node, &["left", "right", "alias"]);
+                let operator = &node.token.as_ref().unwrap().literal;
+                self.typing.binary(operator, &left, &right)
+            }
+            NodeType::UnaryOperator => {
+                let right = node
+                    .get_node("right")
+                    .map_or(Type::Unknown, |r| self.expr(r));
+                self.children(node, &["right", "alias"]);
+                self.typing
+                    .unary(&node.token.as_ref().unwrap().literal, right)
+            }
+            NodeType::BetweenOperator | NodeType::InOperator => {
+                self.children(node, &["alias"]);
+                Type::Bool
+            }
+            NodeType::CaseExpr => {
+                let mut results = Vec::new();
+                for (key, child) in node.children_in_source_order() {
+                    match key {
+                        "arms" => {
+                            self.children(child, &["result"]);
+                            if let Some(result) = child.get_node("result") {
+                                let ty = self.expr(result);
+                                results.push((result, ty));
+                            }
+                        }
+                        "alias" => (),
+                        _ => self.node(child),
+                    }
+                }
+                self.typing.common_type(&results)
+            }
+            NodeType::GroupedExpr => {
+                let expr = node.get_node("expr");
+                self.children(node, &["expr", "alias"]);
+                expr.map_or(Type::Unknown, |e| self.expr(e))
+            }
+            // STRUCT(1 AS a, b), (1, 2), STRUCT<a INT64>(1)
+            NodeType::StructLiteral => {
+                let mut fields = Vec::new();
+                for expr in node.get_node_vec("exprs").into_iter().flatten() {
+                    fields.push(Field::new(&implicit_name(expr), self.expr(expr)));
+                }
+                self.children(node, &["exprs", "alias"]);
+                match node.get_node("type") {
+                    Some(ty) if ty.get_node("type_declaration").is_some() => Type::from_node(ty),
+                    _ => Type::Struct(fields),
+                }
+            }
+            // [1, 2], ARRAY<INT64>[1, 2]
+            NodeType::ArrayLiteral => {
+                let mut elements = Vec::new();
+                for expr in node.get_node_vec("exprs").into_iter().flatten() {
+                    let ty = self.expr(expr);
+                    elements.push((expr, ty));
+                }
+                self.children(node, &["exprs", "alias"]);
+                match node.get_node("type") {
+                    Some(ty) if ty.get_node("type_declaration").is_some() => Type::from_node(ty),
+                    _ => Type::Array(Box::new(self.typing.common_type(&elements))),
+                }
+            }
+            NodeType::NumericLiteral
+            | NodeType::StringLiteral
+            | NodeType::BooleanLiteral
+            | NodeType::IntervalLiteral => {
+                self.children(node, &["alias"]);
+                self.typing.literal(node)
+            }
+            // NULL can be any type
+            NodeType::NullLiteral => {
+                self.children(node, &["alias"]);
+                return Type::Unknown;
+            }
+            NodeType::Parameter => {
+                self.children(node, &["alias"]);
+                Type::Unknown
+            }
+            // (SELECT ...), ARRAY(SELECT ...), EXISTS(SELECT ...)
+            NodeType::GroupedStatement => {
+                self.children(node, &["alias"]);
+                self.query_type(node)
+            }
+            NodeType::SelectStatement | NodeType::SetOperator => {
+                self.node(node);
+                self.query_type(node)
+            }
+            _ => {
+                self.node(node);
+                return Type::Unknown;
+            }
+        };
+        if let Some(token) = &node.token {
+            self.exprs.insert(token.start, ty.clone());
+        }
+        ty
+    }
+    // STRUCT field access (`col.field`)
+    fn field(&mut self, ty: Type, node: &Node) -> Type {
+        let name = unquote(node);
+        match &ty {
+            Type::Struct(_) => match ty.field(&name) {
+                Some(field) => field.data_type.clone(),
+                None => {
+                    self.issues.push(Issue::UnknownField {
+                        field: name,
+                        ty,
+                        span: span(node),
+                    });
+                    Type::Unknown
+                }
+            },
+            Type::Json | Type::Unknown => ty,
+            _ => {
+                self.issues.push(Issue::NotStruct {
+                    field: name,
+                    ty,
+                    span: span(node),
+                });
+                Type::Unknown
+            }
+        }
+    }
+    fn fields(&mut self, ty: Type, parts: &[&Node]) -> Type {
+        parts.iter().fold(ty, |ty, part| self.field(ty, part))
+    }
+    // col | t.col | col.field | t.col.field | ...
+    fn reference(&mut self, node: &Node) -> Type {
+        let parts = path(node).unwrap();
+        match self.binder.bind_path(&parts) {
+            Some((ty, bound)) => self.fields(ty, &parts[bound..]),
+            // CURRENT_DATE, ...
+            None => {
+                let name = unquote(parts[0]).to_uppercase();
+                self.fields(self.typing.function(&name, &[]), &parts[1..])
+            }
+        }
+    }
+}
+
+// `ARRAY<T>` -> `T`
+fn element(ty: Type) -> Type {
+    match ty {
+        Type::Array(element) => *element,
+        _ => Type::Unknown,
+    }
+}
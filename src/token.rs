@@ -2,145 +2,297 @@
 mod tests;
 
 use crate::constants;
-use regex::Regex;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+// NOTE `end_xxx` is exclusive (points at the next character of the last character)
+// `line` and `column` are counted in `char`, `start` and `end` are byte offsets
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub start: usize,
+    pub end: usize,
+    pub start_utf16: usize, // for JavaScript string
+    pub end_utf16: usize,
+}
+
+// assigned by the lexer
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[allow(clippy::upper_case_acronyms)]
+pub enum TokenKind {
+    Ident,       // x | TABLE (non-reserved keywords are identifiers)
+    QuotedIdent, // `x`
+    Keyword,     // reserved keywords
+    String {
+        raw: bool,    // r'x'
+        bytes: bool,  // b'x'
+        triple: bool, // '''x'''
+    },
+    Number,
+    Param, // ? | @param | @@system_variable
+    Comment {
+        style: CommentStyle,
+    }, // -- x | /* x */ | # x
+    Operator, // + | <= | || | ...
+    Punctuation, // ( | , | ; | ...
+    EOF,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CommentStyle {
+    Line,  // -- x
+    Block, // /* x */
+    Hash,  // # x
+}
+
+impl TokenKind {
+    // classify the literal of a single token
+    // `Lexer` does not use this because it knows what it has just read
+    pub fn guess(literal: &str) -> TokenKind {
+        let mut chars = literal.chars();
+        let first = match chars.next() {
+            Some(c) => c,
+            None => return TokenKind::EOF,
+        };
+        let second = chars.next();
+        match (first, second) {
+            ('-', Some('-')) => {
+                return TokenKind::Comment {
+                    style: CommentStyle::Line,
+                }
+            }
+            ('/', Some('*')) => {
+                return TokenKind::Comment {
+                    style: CommentStyle::Block,
+                }
+            }
+            ('#', _) => {
+                return TokenKind::Comment {
+                    style: CommentStyle::Hash,
+                }
+            }
+            ('?', _) | ('@', _) => return TokenKind::Param,
+            ('`', _) if 2 <= literal.len() && literal.ends_with('`') => {
+                return TokenKind::QuotedIdent
+            }
+            _ => (),
+        }
+        // string literals (with or without the prefix)
+        if let Some(i) = literal.find(['\'', '"']) {
+            let (prefix, body) = literal.split_at(i);
+            let quote = &body[..1];
+            let is_valid_prefix = matches!(
+                prefix.to_ascii_uppercase().as_str(),
+                "" | "R" | "B" | "RB" | "BR"
+            );
+            if is_valid_prefix && 2 <= body.len() && body.ends_with(quote) {
+                let triple = 6 <= body.len() && body.starts_with(&quote.repeat(3));
+                let prefix = prefix.to_ascii_uppercase();
+                return TokenKind::String {
+                    raw: prefix.contains('R'),
+                    bytes: prefix.contains('B'),
+                    triple,
+                };
+            }
+        }
+        if is_numeric_literal(literal) {
+            return TokenKind::Number;
+        }
+        if is_reserved_keyword(literal) {
+            return TokenKind::Keyword;
+        }
+        if (first.is_alphabetic() || first == '_')
+            && literal.chars().all(|c| c.is_alphanumeric() || c == '_')
+        {
+            return TokenKind::Ident;
+        }
+        if is_punctuation(literal) {
+            TokenKind::Punctuation
+        } else {
+            TokenKind::Operator
+        }
+    }
+}
+
+// the classification for syntax highlighters and so on
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[allow(clippy::upper_case_acronyms)]
+pub enum TokenCategory {
+    Keyword,         // TABLE | DECLARE | ...
+    ReservedKeyword, // SELECT | FROM | ...
+    Identifier,
+    QuotedIdentifier, // `x`
+    String,
+    Bytes,     // b'abc' (both the prefix and the string)
+    RawString, // r'abc' (both the prefix and the string)
+    Numeric,
+    Parameter, // ? | @param | @@system_variable
+    Comment,
+    Operator,    // + | <= | || | ...
+    Punctuation, // ( | , | ; | ...
+    #[cfg_attr(feature = "serde", serde(rename = "eof"))]
+    EOF,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Token {
     pub line: usize,
     pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub start: usize, // offsets are set by the lexer
+    pub end: usize,
+    pub start_utf16: usize,
+    pub end_utf16: usize,
     pub literal: String,
+    pub leading_whitespace: String, // spaces, tabs and newlines between the previous token and this token
+    pub kind: TokenKind,
 }
 
 impl Token {
+    // NOTE `kind` is guessed from `literal`, tokens returned by `Lexer` have the exact one
     pub fn new(line: usize, column: usize, literal: String) -> Token {
+        let kind = TokenKind::guess(&literal);
+        Token::with_kind(line, column, literal, kind)
+    }
+    pub fn with_kind(line: usize, column: usize, literal: String, kind: TokenKind) -> Token {
+        // string literals and comments may contain newlines
+        let (end_line, end_column) = match literal.rfind('\n') {
+            Some(i) => (
+                line + literal.matches('\n').count(),
+                literal[i + 1..].chars().count() + 1,
+            ),
+            None => (line, column + literal.chars().count()),
+        };
         Token {
             line,
             column,
+            end_line,
+            end_column,
+            start: 0,
+            end: 0,
+            start_utf16: 0,
+            end_utf16: 0,
             literal,
+            leading_whitespace: "".to_string(),
+            kind,
         }
     }
-    pub fn eof() -> Token {
-        // `const EOF` is not allowed
-        // because of `"".to_string()`
-        Token {
-            line: usize::MAX,
-            column: usize::MAX,
-            literal: "".to_string(),
+    pub fn eof(line: usize, column: usize) -> Token {
+        Token::with_kind(line, column, "".to_string(), TokenKind::EOF)
+    }
+    pub fn span(&self) -> Span {
+        Span {
+            line: self.line,
+            column: self.column,
+            end_line: self.end_line,
+            end_column: self.end_column,
+            start: self.start,
+            end: self.end,
+            start_utf16: self.start_utf16,
+            end_utf16: self.end_utf16,
         }
     }
     pub fn is_string(&self) -> bool {
-        if self.quoted_by('"') {
-            true
-        } else if self.quoted_by('\'') {
-            true
-        } else {
-            false
-        }
+        matches!(self.kind, TokenKind::String { .. })
     }
     pub fn in_(&self, vec: &Vec<&str>) -> bool {
-        for v in vec {
-            if self.literal.to_uppercase() == v.to_uppercase() {
-                return true;
-            };
-        }
-        false
+        vec.iter().any(|v| self.is(v))
     }
     pub fn is(&self, literal: &str) -> bool {
-        self.literal.to_uppercase() == literal.to_uppercase()
+        self.literal.eq_ignore_ascii_case(literal)
     }
     pub fn is_identifier(&self) -> bool {
-        if self.quoted_by('`') {
-            return true;
-        }
-        if self.is_reserved_keyword() {
-            return false;
-        }
-        for kw in constants::KEYWORDS.iter() {
-            // `PartialEq<&str>` is implemented for String
-            if self.literal.to_uppercase() == *kw {
-                return false;
-            }
-        }
-        let mut iterator = self.literal.chars();
-        match iterator.next() {
-            Some('a'..='z') | Some('A'..='Z') | Some('_') => (),
-            _ => return false, // "", ";", ...
-        }
-        for i in iterator {
-            match i {
-                'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => (),
-                _ => return false,
-            }
-        }
-        true
+        matches!(self.kind, TokenKind::Ident | TokenKind::QuotedIdent)
     }
     pub fn is_parameter(&self) -> bool {
-        let mut iterator = self.literal.chars();
-        match iterator.next() {
-            Some('?') | Some('@') => return true,
-            _ => return false
-        }
+        self.kind == TokenKind::Param
     }
     pub fn is_numeric(&self) -> bool {
-        let re = Regex::new(r"^([0-9]+|([0-9]*\.[0-9]+))([eE][\+\-]?[0-9]+)?$").unwrap();
-        re.is_match(self.literal.as_str())
+        self.kind == TokenKind::Number
     }
     pub fn is_boolean(&self) -> bool {
-        self.literal.to_uppercase() == "TRUE" || self.literal.to_uppercase() == "FALSE"
+        self.kind == TokenKind::Keyword && (self.is("TRUE") || self.is("FALSE"))
     }
     pub fn is_reserved_keyword(&self) -> bool {
-        for kw in constants::KEYWORDS.iter() {
-            // `PartialEq<&str>` is implemented for String
-            if self.literal.to_uppercase() == *kw {
-                return true;
-            }
-        }
-        false
+        self.kind == TokenKind::Keyword
     }
     pub fn is_comment(&self) -> bool {
-        let mut iter = self.literal.chars();
-        let first_char = match iter.next() {
-            Some(c) => match c {
-                '-' | '/' => c,
-                '#' => return true,
-                _ => return false,
-            },
-            None => return false,
-        };
-        let second_char = match iter.next() {
-            Some(c) => c,
-            None => return false,
-        };
-        if first_char == '-' && second_char == '-' || first_char == '/' && second_char == '*' {
-            true
-        } else {
-            false
-        }
+        matches!(self.kind, TokenKind::Comment { .. })
+    }
+    pub fn is_non_reserved_keyword(&self) -> bool {
+        self.kind == TokenKind::Ident
+            && constants::NON_RESERVED_KEYWORDS
+                .iter()
+                .any(|kw| self.literal.eq_ignore_ascii_case(kw))
     }
-    fn quoted_by(&self, ch: char) -> bool {
-        if self.literal.len() < 2 {
-            return false;
+    pub fn category(&self) -> TokenCategory {
+        match self.kind {
+            TokenKind::Ident if self.is_non_reserved_keyword() => TokenCategory::Keyword,
+            TokenKind::Ident => TokenCategory::Identifier,
+            TokenKind::QuotedIdent => TokenCategory::QuotedIdentifier,
+            TokenKind::Keyword => TokenCategory::ReservedKeyword,
+            TokenKind::String { bytes: true, .. } => TokenCategory::Bytes,
+            TokenKind::String { raw: true, .. } => TokenCategory::RawString,
+            TokenKind::String { .. } => TokenCategory::String,
+            TokenKind::Number => TokenCategory::Numeric,
+            TokenKind::Param => TokenCategory::Parameter,
+            TokenKind::Comment { .. } => TokenCategory::Comment,
+            TokenKind::Operator => TokenCategory::Operator,
+            TokenKind::Punctuation => TokenCategory::Punctuation,
+            TokenKind::EOF => TokenCategory::EOF,
         }
-        // unwrap is safe because the length is longer then 2
-        self.literal.chars().next().unwrap() == ch && self.literal.chars().last().unwrap() == ch
     }
 }
 
+pub fn categorize(tokens: &[Token]) -> Vec<TokenCategory> {
+    tokens.iter().map(|t| t.category()).collect()
+}
+
+pub fn is_reserved_keyword(literal: &str) -> bool {
+    constants::KEYWORDS
+        .iter()
+        .any(|kw| literal.eq_ignore_ascii_case(kw))
+}
+
+fn is_punctuation(literal: &str) -> bool {
+    matches!(literal, "(" | ")" | "[" | "]" | "," | ";" | "." | ":")
+}
+
+fn is_numeric_literal(literal: &str) -> bool {
+    // ([0-9]+|[0-9]*\.[0-9]+)([eE][\+\-]?[0-9]+)?
+    let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    let (mantissa, exponent) = match literal.find(['e', 'E']) {
+        Some(i) => (&literal[..i], Some(&literal[i + 1..])),
+        None => (literal, None),
+    };
+    let is_valid_mantissa = match mantissa.split_once('.') {
+        Some((int, frac)) => (int.is_empty() || is_digits(int)) && is_digits(frac),
+        None => is_digits(mantissa),
+    };
+    let is_valid_exponent = match exponent {
+        Some(e) => is_digits(e.strip_prefix(['+', '-']).unwrap_or(e)),
+        None => true,
+    };
+    is_valid_mantissa && is_valid_exponent
+}
+
 #[cfg(test)]
 impl Token {
     pub fn from_str(line: usize, column: usize, literal: &str) -> Token {
-        Token {
-            line,
-            column,
-            literal: literal.to_string(),
-        }
+        Token::new(line, column, literal.to_string())
     }
     pub fn from_str0(literal: &str) -> Token {
-        Token {
-            line: 0,
-            column: 0,
-            literal: literal.to_string(),
-        }
+        Token::new(0, 0, literal.to_string())
     }
 }
@@ -44,6 +44,11 @@ fn test_is_numeric() {
     assert!(!Token::from_str0("e10").is_numeric());
     assert!(!Token::from_str0("xxx").is_numeric());
     assert!(!Token::from_str0("x01").is_numeric());
+    assert!(!Token::from_str0("10.").is_numeric());
+    assert!(!Token::from_str0("10.11.12").is_numeric());
+    assert!(!Token::from_str0("10e").is_numeric());
+    assert!(!Token::from_str0("10e+").is_numeric());
+    assert!(!Token::from_str0("10e1.1").is_numeric());
 }
 
 #[test]
@@ -53,3 +58,51 @@ fn test_is_comment() {
     assert!(Token::from_str0("/*\nxxx\n*/").is_comment());
     assert!(Token::from_str0("# xxx").is_comment());
 }
+
+#[test]
+fn test_categorize() {
+    use crate::lexer::Lexer;
+    use TokenCategory::*;
+    let code = "SELECT `a`.b, 'c', r'd', b\"e\", r 'f', 1.5, @p -- x
+FROM table WHERE x <= (1);";
+    let mut lexer = Lexer::new(code.to_string());
+    let tokens = lexer.tokenize_code().unwrap();
+    let categories: Vec<(&str, TokenCategory)> = tokens
+        .iter()
+        .map(|t| t.literal.as_str())
+        .zip(categorize(tokens))
+        .collect();
+    assert_eq!(
+        vec![
+            ("SELECT", ReservedKeyword),
+            ("`a`", QuotedIdentifier),
+            (".", Punctuation),
+            ("b", Identifier),
+            (",", Punctuation),
+            ("'c'", String),
+            (",", Punctuation),
+            ("r'd'", RawString),
+            (",", Punctuation),
+            ("b\"e\"", Bytes),
+            (",", Punctuation),
+            ("r", Identifier), // not a prefix because of the whitespace
+            ("'f'", String),
+            (",", Punctuation),
+            ("1.5", Numeric),
+            (",", Punctuation),
+            ("@p", Parameter),
+            ("-- x", Comment),
+            ("FROM", ReservedKeyword),
+            ("table", Keyword),
+            ("WHERE", ReservedKeyword),
+            ("x", Identifier),
+            ("<=", Operator),
+            ("(", Punctuation),
+            ("1", Numeric),
+            (")", Punctuation),
+            (";", Punctuation),
+            ("", EOF),
+        ],
+        categories
+    );
+}
@@ -1,1096 +1,98 @@
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen(typescript_custom_section)]
-const NODES: &'static str = r#"
+const TYPES: &'static str = r#"
 export function parse(code: string): UnknownNode;
+export function parse_with_recovery(code: string): {
+  stmts: UnknownNode[];
+  errors: Omit<ParseError, "name" | "stack">[];
+};
+export function format(code: string, options?: Partial<FormatOptions>): string;
+export function tokenize(code: string): (Token & { category: TokenCategory })[];
+export function lint(code: string, options?: Partial<LintOptions>): LintDiagnostic[];
+
+export type TokenCategory =
+  | "keyword"
+  | "reserved_keyword"
+  | "identifier"
+  | "quoted_identifier"
+  | "string"
+  | "bytes"
+  | "raw_string"
+  | "numeric"
+  | "parameter"
+  | "comment"
+  | "operator"
+  | "punctuation"
+  | "eof";
+
+export type FormatOptions = {
+  indent_width: number;
+  keyword_case: "upper" | "lower" | "preserve";
+  max_width: number;
+  comma_style: "trailing" | "leading";
+  preserve_comments: boolean;
+};
+
+export type LintSeverity = "off" | "warning" | "error";
+
+// rule name -> severity (overrides the default severity)
+// e.g. { rules: { "comma-join": "off" } }
+export type LintOptions = {
+  rules: Record<string, LintSeverity>;
+};
+
+export type LintDiagnostic = {
+  rule: string;
+  severity: Exclude<LintSeverity, "off">;
+  message: string;
+  line: number;
+  column: number;
+  end_line: number;
+  end_column: number;
+  start: number;
+  end: number;
+  start_utf16: number;
+  end_utf16: number;
+};
 
-export type UnknownNode =
-  | AddColumnClause
-  | AlterColumnStatement
-  | AlterSchemaStatement
-  | AlterTableStatement
-  | AlterViewStatement
-  | ArrayAccessing
-  | ArrayLiteral
-  | AssertStatement
-  | Asterisk
-  | BinaryOperator
-  | BeginStatement
-  | BetweenOperator
-  | BooleanLiteral
-  | CallingArrayAccessingFunction
-  | CallingFunction
-  | CallingTableFunction
-  | CallingUnnest
-  | CallStatement
-  | CaseArm
-  | CaseExpr
-  | CastArgument
-  | Comment
-  | CreateFunctionStatement
-  | CreateProcedureStatement
-  | CreateSchemaStatement
-  | CreateTableStatement
-  | CreateViewStatement
-  | DeclareStatement
-  | DeleteStatement
-  | DotOperator
-  | DropColumnClause
-  | DropStatement
-  | ElseIfClause
-  | EOF
-  | ExecuteStatement
-  | ExportStatement
-  | ExtractArgument
-  | ForSystemTimeAsOfClause
-  | GrantStatement
-  | GroupedExpr
-  | GroupedExprs
-  | GroupedStatement
-  | GroupedTypeDeclarations
-  | GroupedType
-  | Identifier
-  | IfStatement
-  | InOperator
-  | InsertStatement
-  | IntervalLiteral
-  | JoinOperator
-  | Keyword
-  | KeywordWithExpr
-  | KeywordWithExprs
-  | KeywordWithGroupedXXX
-  | KeywordWithStatement
-  | KeywordWithStatements
-  | KeywordWithType
-  | LanguageSpecifier
-  | LimitClause
-  | LoopStatement
-  | MergeStatement
-  | NullLiteral
-  | NumericLiteral
-  | OverClause
-  | Parameter
-  | PivotOperator
-  | PivotConfig
-  | SelectStatement
-  | RaiseStatement
-  | RevokeStatement
-  | SetOperator
-  | SetStatement
-  | SingleTokenStatement
-  | StringLiteral
-  | StructLiteral
-  | Symbol_
-  | TableSampleClause
-  | TableSampleRatio
-  | TransactionStatement
-  | TruncateStatement
-  | Type
-  | TypeDeclaration
-  | UnaryOperator
-  | UnpivotConfig
-  | UnpivotOperator
-  | UpdateStatement
-  | WhenClause
-  | WhileStatement
-  | WindowClause
-  | WindowExpr
-  | WindowFrameClause
-  | WindowSpecification
-  | WithClause
-  | WithPartitionColumnsClause
-  | WithQuery
-  | XXXByExprs;
-
+// `end_xxx` is exclusive
+// `line` and `column` are counted in characters, `start` and `end` are byte offsets
 export type Token = {
   line: number;
   column: number;
+  end_line: number;
+  end_column: number;
+  start: number;
+  end: number;
+  start_utf16: number; // can be used as an index of JavaScript string
+  end_utf16: number;
   literal: string;
-};
-
-export interface BaseNode {
+  leading_whitespace: string;
+  kind: TokenKind;
+};
+
+// NOTE prefixed string literals (e.g. `r'x'`) are a single token and a single `StringLiteral` node
+export type TokenKind =
+  | { type: "Ident" } // including non-reserved keywords
+  | { type: "QuotedIdent" }
+  | { type: "Keyword" } // reserved keywords
+  | { type: "String"; raw: boolean; bytes: boolean; triple: boolean }
+  | { type: "Number" }
+  | { type: "Param" }
+  | { type: "Comment"; style: "Line" | "Block" | "Hash" }
+  | { type: "Operator" }
+  | { type: "Punctuation" }
+  | { type: "EOF" };
+
+// thrown by `parse()` when the code cannot be parsed
+export type ParseError = Error & {
+  line: number;
+  column: number;
   token: Token | null;
-  node_type: string;
-  children: {
-    leading_comments?: { NodeVec: Comment[] };
-    trailing_comments?: { NodeVec: Comment[] };
-  };
-}
-
-export type NodeChild = { Node: BaseNode };
-export type NodeVecChild = { NodeVec: BaseNode[] };
-
-// ----- sub types of BaseNode (abstract) -----
-export type CallingFunctionGeneral = Expr & {
-  children: {
-    func: { Node: Identifier | DotOperator };
-    distinct?: NodeChild;
-    args?: { NodeVec: Expr[] };
-    ignore_nulls?: NodeVecChild;
-    orderby?: NodeChild;
-    limit?: NodeChild;
-    rparen: NodeChild;
-    over?: NodeChild;
-  };
-};
-
-export type Expr = BaseNode & {
-  token: Token;
-  children: {
-    as?: NodeChild;
-    alias?: NodeChild;
-    comma?: NodeChild;
-    order?: NodeChild;
-    null_order?: NodeVecChild;
-  };
-};
-
-export type FromItemExpr = Expr & {
-  children: {
-    pivot?: NodeChild;
-    unpivot?: NodeChild;
-  };
-};
-
-export type IdentifierGeneral = FromItemExpr & {
-  children: {
-    // TABLESAMPLE SYSTEM can only be applied directly to base tables
-    tablesample?: NodeChild;
-    for_system_time_as_of?: NodeChild;
-  };
-};
-
-export type XXXStatement = BaseNode & {
-  token: Token;
-  children: {
-    semicolon?: { Node: Symbol_ };
-  };
-};
-
-export type AddColumnClause = BaseNode & {
-  node_type: "AddColumnClause";
-  children: {
-    column: NodeChild;
-    if_not_exists?: NodeVecChild;
-    type_declaration: NodeChild;
-    comma?: NodeChild;
-  };
-};
-
-// ----- sub types of BaseNode (concrete) -----
-export type AlterColumnStatement = BaseNode & {
-  // NOTE this is not XXXStatement!
-  node_type: "AlterColumnStatement";
-  children: {
-    what: NodeChild;
-    if_exists?: NodeVecChild;
-    ident: NodeChild;
-    // SET
-    set: NodeChild;
-    options: NodeChild;
-    // DROP
-    drop_not_null: NodeVecChild;
-  };
-};
-
-export type AlterSchemaStatement = XXXStatement & {
-  node_type: "AlterSchemaStatement";
-  children: {
-    what: NodeChild;
-    if_exists?: NodeVecChild;
-    ident: NodeChild;
-    set: NodeChild;
-    options: NodeChild;
-  };
-};
-
-export type AlterTableStatement = XXXStatement & {
-  node_type: "AlterTableStatement";
-  children: {
-    what: NodeChild;
-    if_exists?: NodeVecChild;
-    ident: NodeChild;
-    // SET
-    set?: NodeChild;
-    options?: NodeChild;
-    // ADD COLUMN
-    add_columns?: NodeVecChild;
-    // RENAME TO
-    rename?: NodeChild;
-    to?: NodeChild;
-    // DROP COLUMN
-    drop_columns?: NodeVecChild;
-    // ALTER COLUMN statement
-    alter_column_stmt?: NodeChild;
-  };
-};
-
-export type AlterViewStatement = XXXStatement & {
-  node_type: "AlterViewStatement";
-  children: {
-    materialized?: NodeChild;
-    what: NodeChild;
-    if_exists?: NodeVecChild;
-    ident: NodeChild;
-    set: NodeChild;
-    options: NodeChild;
-  };
-};
-
-export type ArrayAccessing = Expr & {
-  node_type: "ArrayAccessing";
-  children: {
-    not: undefined;
-    left: NodeChild;
-    right: NodeChild;
-    rparen: NodeChild;
-  };
-};
-
-export type ArrayLiteral = Expr & {
-  node_type: "ArrayLiteral";
-  children: {
-    type?: NodeChild;
-    exprs: NodeVecChild;
-    rparen: NodeChild;
-  };
-};
-
-export type AssertStatement = XXXStatement & {
-  node_type: "AssertStatement";
-  children: {
-    expr: NodeChild;
-    as: NodeChild;
-    description: NodeChild;
-  };
-};
-
-export type Asterisk = Expr & {
-  node_type: "Asterisk";
-  children: {
-    except?: NodeChild;
-    replace?: NodeChild;
-    order: undefined;
-    null_order: undefined;
-  };
-};
-
-export type BinaryOperator = Expr & {
-  node_type: "BinaryOperator";
-  children: {
-    not?: NodeChild;
-    left: NodeChild;
-    right: NodeChild;
-  };
-};
-
-export type BeginStatement = XXXStatement & {
-  node_type: "BeginStatement";
-  children: {
-    stmts?: NodeVecChild;
-    exception_when_error?: NodeVecChild;
-    then?: NodeChild;
-    end: NodeChild;
-  };
-};
-
-export type BetweenOperator = Expr & {
-  node_type: "BetweenOperator";
-  children: {
-    left: NodeChild;
-    not?: NodeChild;
-    right_min: NodeChild;
-    right_max: NodeChild;
-    and: NodeChild;
-  };
-};
-
-export type BooleanLiteral = Expr & {
-  node_type: "BooleanLiteral";
-};
-
-export type CallingArrayAccessingFunction = CallingFunctionGeneral & {
-  node_type: "CallingArrayAccessingFunction";
-  children: {
-    distinct: undefined;
-    ignore_nulls: undefined;
-    orderby: undefined;
-    limit: undefined;
-    over: undefined;
-    as: undefined;
-    alias: undefined;
-    comma: undefined;
-    order: undefined;
-    null_order: undefined;
-  };
-};
-
-export type CallingFunction = CallingFunctionGeneral & {
-  node_type: "CallingFunction";
-};
-
-export type CallingTableFunction = FromItemExpr &
-  CallingFunctionGeneral & {
-    node_type: "CallingUnnest";
-    children: {
-      distinct: undefined;
-      ignore_nulls: undefined;
-      orderby: undefined;
-      limit: undefined;
-      over: undefined;
-      comma: undefined;
-      order: undefined;
-      null_order: undefined;
-    };
-  };
-
-export type CallingUnnest = FromItemExpr &
-  CallingFunctionGeneral & {
-    node_type: "CallingUnnest";
-    children: {
-      with_offset: NodeChild;
-      offset_alias: NodeChild;
-      offset_as: NodeChild;
-      distinct: undefined;
-      ignore_nulls: undefined;
-      orderby: undefined;
-      limit: undefined;
-      over: undefined;
-      order: undefined;
-      null_order: undefined;
-      comma: undefined;
-    };
-  };
-
-export type CallStatement = XXXStatement & {
-  node_type: "CallStatement";
-  children: {
-    procedure: NodeChild;
-  };
-};
-
-export type CaseArm = BaseNode & {
-  node_type: "CaseArm";
-  children: {
-    expr?: NodeChild;
-    then?: NodeChild;
-    result: NodeChild;
-  };
-};
-
-export type CaseExpr = Expr & {
-  node_type: "CaseExpr";
-  children: {
-    expr?: NodeChild;
-    arms: NodeVecChild;
-    end: NodeChild;
-  };
-};
-
-export type CastArgument = BaseNode & {
-  token: Token;
-  node_type: "CastArgument";
-  children: {
-    cast_from: NodeChild;
-    cast_to: NodeChild;
-    format?: NodeChild;
-  };
-};
-
-export type Comment = BaseNode & {
-  token: Token;
-  node_type: "Comment";
-  children: {
-    leading_comments: undefined;
-    trailing_comments: undefined;
-  };
-};
-
-export type CreateFunctionStatement = XXXStatement & {
-  node_type: "CreateFunctionStatement";
-  children: {
-    or_replace?: NodeVecChild;
-    temp?: NodeChild;
-    table?: NodeChild;
-    what: NodeChild;
-    if_not_exists?: NodeVecChild;
-    ident: NodeChild;
-    group: NodeChild;
-    returns?: NodeChild;
-    determinism?: NodeVecChild;
-    language?: NodeChild;
-    options?: NodeChild;
-    as: NodeChild;
-  };
-};
-
-export type CreateProcedureStatement = XXXStatement & {
-  node_type: "CreateProcedureStatement";
-  children: {
-    or_replace?: NodeVecChild;
-    what: NodeChild;
-    if_not_exists?: NodeVecChild;
-    ident: NodeChild;
-    group: NodeChild;
-    options?: NodeChild;
-    stmt: NodeChild;
-  };
-};
-
-export type CreateSchemaStatement = XXXStatement & {
-  node_type: "CreateSchemaStatement";
-  children: {
-    what: NodeChild;
-    if_not_exists?: NodeVecChild;
-    ident: NodeChild;
-    options?: NodeChild;
-  };
-};
-
-export type CreateTableStatement = XXXStatement & {
-  node_type: "CreateTableStatement";
-  children: {
-    or_replace?: NodeVecChild;
-    temp?: NodeChild;
-    external?: NodeChild;
-    snapshot?: NodeChild;
-    what: NodeChild;
-    if_not_exists?: NodeVecChild;
-    ident: NodeChild;
-    column_schema_group?: NodeChild;
-    clone?: NodeChild;
-    partitionby?: NodeChild;
-    clusterby?: NodeChild;
-    with_partition_columns?: NodeChild;
-    options?: NodeChild;
-    as?: NodeChild;
-  };
-};
-
-export type CreateViewStatement = XXXStatement & {
-  node_type: "CreateViewStatement";
-  children: {
-    or_replace?: NodeVecChild;
-    materialized?: NodeChild;
-    what: NodeChild;
-    if_not_exists?: NodeVecChild;
-    ident: NodeChild;
-    column_name_list?: NodeChild;
-    partitionby?: NodeChild;
-    clusterby?: NodeChild;
-    options?: NodeChild;
-    as: NodeChild;
-  };
-};
-
-export type DeclareStatement = XXXStatement & {
-  node_type: "DeclareStatement";
-  children: {
-    idents: NodeVecChild;
-    variable_type?: NodeChild;
-    default?: NodeChild;
-  };
-};
-
-export type DeleteStatement = XXXStatement & {
-  node_type: "DeleteStatement";
-  children: {
-    from: NodeChild;
-    table_name: NodeChild;
-    where: NodeChild;
-  };
-};
-
-export type DotOperator = IdentifierGeneral & {
-  node_type: "DotOperator";
-  children: {
-    left: { Node: Identifier | DotOperator };
-    right: { Node: Identifier | DotOperator };
-  };
-};
-
-export type DropColumnClause = BaseNode & {
-  node_type: "DropColumnClause";
-  children: {
-    column: NodeChild;
-    if_exists?: NodeVecChild;
-    ident: NodeChild;
-    comma?: NodeChild;
-  };
-};
-
-export type DropStatement = XXXStatement & {
-  node_type: "DropStatement";
-  children: {
-    external?: NodeChild;
-    materialized?: NodeChild;
-    what: NodeChild;
-    if_exists?: NodeVecChild;
-    ident: NodeChild;
-    cascade_or_restrict?: NodeChild;
-  };
-};
-
-export type ElseIfClause = BaseNode & {
-  token: Token;
-  node_type: "ElseIfClause";
-  children: {
-    condition: NodeChild;
-    then: NodeChild;
-  };
-};
-
-export type EOF = BaseNode & {
-  token: null;
-  node_type: "EOF";
-  children: {
-    trailing_comments: undefined;
-  };
-};
-
-export type ExecuteStatement = XXXStatement & {
-  node_type: "ExecuteStatement";
-  children: {
-    immediate: NodeChild;
-    sql_expr: NodeChild;
-    into: NodeChild;
-    using?: NodeChild;
-  };
-};
-
-export type ExportStatement = XXXStatement & {
-  node_type: "ExportStatement";
-  children: {
-    data: NodeChild;
-    options: NodeChild;
-    as: NodeChild;
-  };
-};
-
-export type ExtractArgument = BaseNode & {
-  token: Token;
-  node_type: "ExtractArgument";
-  children: {
-    extract_datepart: NodeChild;
-    extract_from: NodeChild;
-    at_time_zone: NodeVecChild;
-    time_zone: NodeChild;
-  };
-};
-
-export type ForSystemTimeAsOfClause = BaseNode & {
-  token: Token;
-  node_type: "ForSystemTimeAsOfClause";
-  children: {
-    system_time_as_of: NodeVecChild;
-    expr: NodeChild;
-  };
-};
-
-export type GrantStatement = XXXStatement & {
-  node_type: "GrantStatement";
-  children: {
-    roles: NodeVecChild;
-    on: NodeChild;
-    resource_type: NodeChild;
-    ident: NodeChild;
-    to: NodeChild;
-  };
-};
-
-export type GroupedExpr = FromItemExpr & {
-  node_type: "GroupedExpr";
-  children: {
-    expr: NodeChild;
-    rparen: NodeChild;
-  };
-};
-
-export type GroupedExprs = BaseNode & {
-  token: Token;
-  node_type: "GroupedExprs";
-  children: {
-    exprs: NodeVecChild;
-    rparen: NodeChild;
-    // only in UNPIVOT operator
-    as?: NodeChild;
-    row_value_alias?: NodeChild;
-    // only in INSERT statement
-    comma?: NodeChild;
-  };
-};
-
-export type GroupedStatement = FromItemExpr &
-  XXXStatement & {
-    node_type: "GroupedStatement";
-    children: {
-      stmt: NodeChild;
-      rparen: NodeChild;
-    };
-  };
-
-export type GroupedTypeDeclarations = BaseNode & {
-  node_type: "GroupedTypeDeclarations";
-  children: {
-    declarations: NodeVecChild;
-    rparen: NodeChild;
-  };
-};
-
-export type GroupedType = BaseNode & {
-  node_type: "GroupedType";
-  children: {
-    type: NodeChild;
-    rparen: NodeChild;
-  };
-};
-
-export type Identifier = IdentifierGeneral & {
-  node_type: "Identifier";
-};
-
-export type IfStatement = XXXStatement & {
-  node_type: "IfStatement";
-  children: {
-    condition: NodeChild;
-    then: NodeChild;
-    elseifs: NodeVecChild;
-    else: NodeChild;
-    end_if: NodeVecChild;
-  };
-};
-
-export type InOperator = Expr & {
-  node_type: "InOperator";
-  children: {
-    not?: NodeChild;
-    left: NodeChild;
-    right: NodeChild;
-  };
-};
-
-export type InsertStatement = XXXStatement & {
-  node_type: "InsertStatement";
-  children: {
-    into?: NodeChild;
-    target_name?: NodeChild;
-    columns?: NodeChild;
-    input: NodeChild;
-  };
-};
-
-export type IntervalLiteral = Expr & {
-  node_type: "IntervalLiteral";
-  children: {
-    date_part: NodeChild;
-    right: NodeChild;
-    order: undefined;
-    null_order: undefined;
-  };
-};
-
-export type JoinOperator = FromItemExpr & {
-  node_type: "JoinOperator";
-  children: {
-    join_type: NodeChild;
-    outer: NodeChild;
-    left: NodeChild;
-    right: NodeChild;
-    on: NodeChild;
-    using: NodeChild;
-    order: undefined;
-    null_order: undefined;
-    comma: undefined;
-  };
-};
-
-export type Keyword = BaseNode & {
-  token: Token;
-  node_type: "Keyword";
-};
-
-export type KeywordWithExpr = BaseNode & {
-  node_type: "KeywordWithExpr";
-  children: {
-    expr: NodeChild;
-  };
-};
-
-export type KeywordWithExprs = BaseNode & {
-  node_type: "KeywordWithExprs";
-  children: {
-    exprs: NodeVecChild;
-  };
-};
-
-export type KeywordWithGroupedXXX = BaseNode & {
-  node_type: "KeywordWithGroupedXXX";
-  children: {
-    group: NodeChild;
-  };
-};
-
-export type KeywordWithStatement = BaseNode & {
-  node_type: "KeywordWithStatement";
-  children: {
-    stmt: NodeChild;
-  };
-};
-
-export type KeywordWithStatements = BaseNode & {
-  node_type: "KeywordWithStatements";
-  children: {
-    stmts: NodeVecChild;
-  };
-};
-
-export type KeywordWithType = BaseNode & {
-  node_type: "KeywordWithType";
-  children: {
-    type: NodeChild;
-  };
-};
-
-export type LanguageSpecifier = BaseNode & {
-  node_type: "LanguageSpecifier";
-  children: {
-    language: NodeChild;
-  };
-};
-
-export type LimitClause = BaseNode & {
-  node_type: "LimitClause";
-  children: {
-    expr: NodeChild;
-    offset?: NodeChild;
-  };
-};
-
-export type LoopStatement = XXXStatement & {
-  node_type: "LoopStatement";
-  children: {
-    stmts?: NodeVecChild;
-    end_loop: NodeVecChild;
-  };
-};
-
-export type MergeStatement = XXXStatement & {
-  node_type: "MergeStatement";
-  children: {
-    into?: NodeChild;
-    table_name: NodeChild;
-    using: NodeChild;
-    on: NodeChild;
-    whens: NodeVecChild;
-  };
-};
-
-export type NullLiteral = Expr & {
-  node_type: "NullLiteral";
-};
-
-export type NumericLiteral = Expr & {
-  node_type: "NumericLiteral";
-};
-
-export type OverClause = BaseNode & {
-  token: Token;
-  node_type: "OverClause";
-  children: {
-    window: NodeChild;
-  };
-};
-
-export type Parameter = IdentifierGeneral & {
-  node_type: "Parameter";
-};
-
-export type PivotOperator = BaseNode & {
-  token: Token;
-  node_type: "PivotOperator";
-  children: {
-    config: NodeChild;
-    as?: NodeChild;
-    alias?: NodeChild;
-  };
-};
-
-export type PivotConfig = BaseNode & {
-  token: Token;
-  node_type: "PivotConfig";
-  children: {
-    exprs: NodeVecChild;
-    for: NodeChild;
-    in: NodeChild;
-    rparen: NodeChild;
-  };
-};
-
-export type SelectStatement = XXXStatement & {
-  token: Token;
-  node_type: "SelectStatement";
-  children: {
-    with?: NodeChild;
-    as_struct_or_value?: NodeVecChild;
-    distinct_or_all?: NodeChild;
-    exprs: NodeVecChild;
-    from?: NodeChild;
-    where?: NodeChild;
-    groupby?: NodeChild;
-    having?: NodeChild;
-    qualify?: NodeChild;
-    window?: NodeChild;
-    orderby?: NodeChild;
-    limit?: NodeChild;
-  };
-};
-
-export type RaiseStatement = XXXStatement & {
-  node_type: "RaiseStatement";
-  children: {
-    using?: NodeChild;
-  };
-};
-
-export type RevokeStatement = XXXStatement & {
-  node_type: "RevokeStatement";
-  children: {
-    roles: NodeVecChild;
-    on: NodeChild;
-    resource_type: NodeChild;
-    ident: NodeChild;
-    from: NodeChild;
-  };
-};
-
-export type SetOperator = XXXStatement & {
-  node_type: "SetOperator";
-  children: {
-    distinct_or_all: NodeChild;
-    left: { Node: SetOperator | SelectStatement };
-    right: { Node: SetOperator | SelectStatement };
-  };
-};
-
-export type SetStatement = XXXStatement & {
-  node_type: "SetStatement";
-  children: {
-    expr: NodeChild;
-  };
-};
-
-export type SingleTokenStatement = XXXStatement & {
-  node_type: "SingleTokenStatement";
-};
-
-export type StringLiteral = Expr & {
-  node_type: "StringLiteral";
-};
-
-export type StructLiteral = Expr & {
-  node_type: "StructLiteral";
-  children: {
-    type?: NodeChild;
-    exprs: NodeVecChild;
-    rparen: NodeChild;
-  };
-};
-
-export type Symbol_ = BaseNode & {
-  token: Token;
-  node_type: "Symbol";
-};
-
-export type TableSampleClause = BaseNode & {
-  token: Token;
-  node_type: "TableSampleClause";
-  children: {
-    system: NodeChild;
-    group: NodeChild;
-  };
-};
-
-export type TableSampleRatio = BaseNode & {
-  token: Token;
-  node_type: "TableSampleRatio";
-  children: {
-    expr: NodeChild;
-    percent: NodeChild;
-    rparen: NodeChild;
-  };
-};
-
-export type TransactionStatement = XXXStatement & {
-  node_type: "TransactionStatement";
-  children: {
-    transaction?: NodeChild;
-  };
-};
-
-export type TruncateStatement = XXXStatement & {
-  node_type: "TruncateStatement";
-  children: {
-    table: NodeChild;
-    table_name: NodeChild;
-  };
-};
-
-export type Type = BaseNode & {
-  token: Token;
-  node_type: "Type";
-  children: {
-    type?: NodeChild; // ANY TYPE
-    type_declaration?: NodeChild;
-    parameter?: NodeChild;
-    not_null?: NodeVecChild;
-    options?: NodeChild;
-  };
-};
-
-export type TypeDeclaration = BaseNode & {
-  token: Token;
-  node_type: "TypeDeclaration";
-  children: {
-    in_out: NodeChild;
-    type: NodeChild;
-    comma?: NodeChild;
-  };
-};
-
-export type UnaryOperator = Expr & {
-  token: Token;
-  node_type: "UnaryOperator";
-  children: {
-    right: NodeChild;
-  };
-};
-
-export type UnpivotConfig = BaseNode & {
-  token: Token;
-  node_type: "UnpivotConfig";
-  children: {
-    expr: NodeChild;
-    for: NodeChild;
-    in: NodeChild;
-    rparen: NodeChild;
-  };
-};
-
-export type UnpivotOperator = BaseNode & {
-  token: Token;
-  node_type: "UnpivotOperator";
-  children: {
-    include_or_exclude_nulls: NodeVecChild;
-    config: NodeChild;
-    as?: NodeChild;
-    alias?: NodeChild;
-  };
-};
-
-export type UpdateStatement = XXXStatement & {
-  node_type: "UpdateStatement";
-  children: {
-    table_name?: NodeChild;
-    set: NodeChild;
-    from?: NodeChild;
-    where: NodeChild;
-  };
-};
-
-export type WhenClause = BaseNode & {
-  node_type: "WhenClause";
-  children: {
-    not?: NodeChild;
-    matched: NodeChild;
-    by_target_or_source: NodeVecChild;
-    and: NodeChild;
-    then: NodeChild;
-  };
-};
-
-export type WhileStatement = XXXStatement & {
-  node_type: "WhileStatement";
-  children: {
-    condition: NodeChild;
-    do: NodeChild;
-    end_while: NodeVecChild;
-  };
-};
-
-export type WindowClause = BaseNode & {
-  token: Token;
-  node_type: "WindowClause";
-  children: {
-    window_exprs: NodeVecChild;
-  };
-};
-
-export type WindowExpr = BaseNode & {
-  token: Token;
-  node_type: "WindowExpr";
-  children: {
-    as: NodeChild;
-    window: NodeChild;
-    comma?: NodeChild;
-  };
-};
-
-export type WindowFrameClause = BaseNode & {
-  token: Token;
-  node_type: "WindowFrameClause";
-  children: {
-    between?: NodeChild;
-    start: NodeVecChild;
-    and?: NodeChild;
-    end?: NodeVecChild;
-  };
-};
-
-export type WindowSpecification = BaseNode & {
-  token: Token;
-  node_type: "WindowSpecification";
-  children: {
-    name?: NodeChild;
-    partitionby: NodeChild;
-    orderby: NodeChild;
-    frame: NodeChild;
-    rparen: NodeChild;
-  };
-};
-
-export type WithClause = BaseNode & {
-  token: Token;
-  node_type: "WithClause";
-  children: {
-    queries: NodeVecChild;
-  };
-};
-
-export type WithPartitionColumnsClause = BaseNode & {
-  token: Token;
-  node_type: "WithPartitionColumnsClause";
-  children: {
-    partition_columns: NodeVecChild;
-    column_schema_group: NodeChild;
-  };
-};
-
-export type WithQuery = BaseNode & {
-  token: Token;
-  node_type: "WithQuery";
-  children: {
-    as: NodeChild;
-    stmt: NodeChild;
-    comma: NodeChild;
-  };
-};
-
-export type XXXByExprs = BaseNode & {
-  token: Token;
-  node_type: "XXXByExprs";
-  children: {
-    by: NodeChild;
-    exprs: { NodeVec: Expr[] };
-  };
+  expected: string[];
 };
 "#;
+
+// the types of nodes are generated from `crate::cst::schema` by build.rs
+include!(concat!(env!("OUT_DIR"), "/nodes.rs"));
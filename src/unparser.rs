@@ -0,0 +1,15 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::Node;
+
+pub fn unparse(stmts: &[Node]) -> String {
+    let mut res = String::new();
+    for stmt in stmts {
+        for t in stmt.tokens() {
+            res.push_str(&t.leading_whitespace);
+            res.push_str(&t.literal);
+        }
+    }
+    res
+}
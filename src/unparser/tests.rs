@@ -0,0 +1,45 @@
+use super::*;
+use crate::parser::Parser;
+
+#[test]
+fn test_unparse() {
+    let codes = vec![
+        "",
+        "\n\n",
+        "SELECT 1;",
+        "\
+#standardSQL
+SELECT
+  'multi
+line' AS s, -- trailing comment
+  /* multi
+  line */ 2
+FROM t;
+
+-- EOF
+",
+        "\tSELECT 1 ;  -- comment  \r\n\r\n",
+    ];
+    for code in codes {
+        let stmts = Parser::new(code.to_string()).unwrap().parse_code().unwrap();
+        assert_eq!(code, unparse(&stmts));
+    }
+}
+
+#[test]
+fn test_unparse_edited() {
+    use crate::cst::NodeType;
+    use crate::token::Token;
+    let mut stmts = Parser::new("SELECT a + b FROM t;".to_string())
+        .unwrap()
+        .parse_code()
+        .unwrap();
+    let mut exprs = stmts[0].get_node_vec("exprs").unwrap().clone();
+    exprs[0].token.as_mut().unwrap().literal = "*".to_string();
+    // `Token::start` of the new node is 0
+    let mut two = Token::from_str0("2");
+    two.leading_whitespace = " ".to_string();
+    exprs[0].push_node("right", Node::new(two, NodeType::NumericLiteral));
+    stmts[0].push_node_vec("exprs", exprs);
+    assert_eq!("SELECT a * 2 FROM t;", unparse(&stmts));
+}
@@ -1,3 +1,7 @@
+use crate::error::ParseError;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
 pub fn set_panic_hook() {
     // When the `console_error_panic_hook` feature is enabled, we can call the
     // `set_panic_hook` function at least once during initialization, and then
@@ -9,3 +13,36 @@ pub fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
+// convert `ParseError` into JavaScript `Error`
+// which has `line`, `column`, `token` and `expected` properties in addition to `message`
+pub fn to_js_error(error: &ParseError) -> JsValue {
+    let js_error = js_sys::Error::new(&error.message);
+    #[allow(deprecated)]
+    let token = JsValue::from_serde(&error.token).unwrap_or(JsValue::NULL);
+    let expected: js_sys::Array = error
+        .expected
+        .iter()
+        .map(|s| JsValue::from_str(s))
+        .collect();
+    let props: [(&str, JsValue); 4] = [
+        ("line", JsValue::from_f64(error.line as f64)),
+        ("column", JsValue::from_f64(error.column as f64)),
+        ("token", token),
+        ("expected", expected.into()),
+    ];
+    for (key, value) in props.iter() {
+        // `Reflect::set` fails only if the target is not an object
+        let _ = js_sys::Reflect::set(&js_error, &JsValue::from_str(key), value);
+    }
+    js_error.into()
+}
+
+// serialize the result into a JavaScript value
+// failures are returned as JavaScript `Error` instead of panicking across the wasm boundary
+pub fn to_js_value<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    #[allow(deprecated)]
+    JsValue::from_serde(value).map_err(|error| {
+        let message = format!("Problem converting struct to json: {}", error);
+        js_sys::Error::new(&message).into()
+    })
+}
@@ -0,0 +1,203 @@
+#![cfg(feature = "cli")]
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn bq2cst(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bq2cst"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn sql_file(name: &str, code: &str) -> String {
+    let mut path = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    path.push(name);
+    std::fs::write(&path, code).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_parse() {
+    let output = bq2cst(&["parse", "-", "--format", "tree"], "SELECT 1;");
+    assert!(output.status.success());
+    assert_eq!(
+        "\
+self: SELECT (SelectStatement)
+exprs:
+- self: 1 (NumericLiteral)
+semicolon:
+  self: ; (Symbol)
+self:  (EOF)
+",
+        String::from_utf8(output.stdout).unwrap()
+    );
+    let output = bq2cst(&["parse", "-"], "SELECT 1;");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!("SelectStatement", json[0]["node_type"]);
+    // keys of children are in source order
+    let json = String::from_utf8(bq2cst(&["parse", "-"], "SELECT 1 FROM t;").stdout).unwrap();
+    let positions: Vec<usize> = ["\"exprs\"", "\"from\"", "\"semicolon\""]
+        .iter()
+        .map(|key| json.find(key).unwrap())
+        .collect();
+    assert!(positions.windows(2).all(|w| w[0] < w[1]));
+    let stmts: Vec<bq2cst::Node> = serde_json::from_str(&json).unwrap();
+    assert_eq!(bq2cst::parse_str("SELECT 1 FROM t;").unwrap(), stmts);
+    let output = bq2cst(&["parse", "-", "--format", "yaml"], "SELECT 1;");
+    let yaml: serde_yaml::Value = serde_yaml::from_slice(&output.stdout).unwrap();
+    assert_eq!("SelectStatement", yaml[0]["node_type"]);
+    let output = bq2cst(&["parse", "-", "--format", "xml"], "SELECT 1;");
+    assert_eq!(Some(2), output.status.code());
+}
+
+#[test]
+fn test_check() {
+    let ok = sql_file("ok.sql", "SELECT 1;");
+    let ng = sql_file("ng.sql", "SELECT 1;\nSELECT 1 NOT 2;");
+    let output = bq2cst(&["check", &ok], "");
+    assert!(output.status.success());
+    let output = bq2cst(&["check", &ok, &ng], "");
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        format!(
+            "{}:2:14: Expected `IN`, `LIKE` or `BETWEEN` but got: token `2`\n",
+            ng
+        ),
+        String::from_utf8(output.stderr).unwrap()
+    );
+    let output = bq2cst(&["check", &ng, "not_found.sql"], "");
+    assert_eq!(Some(2), output.status.code());
+}
+
+#[test]
+fn test_lint() {
+    let warning = sql_file("warning.sql", "SELECT a + 1 FROM t;");
+    let error = sql_file("error.sql", "SELECT 1;\nUPDATE t SET a = 1;\nDELETE t;");
+    let output = bq2cst(&["lint", &warning], "");
+    assert!(output.status.success());
+    assert_eq!(
+        format!(
+            "{}:1:8: warning: computed column should have an alias [unaliased-expression]\n",
+            warning
+        ),
+        String::from_utf8(output.stdout).unwrap()
+    );
+    let output = bq2cst(&["lint", &warning, &error], "");
+    assert_eq!(Some(1), output.status.code());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .ends_with(&format!(
+            "{0}:2:1: error: `UPDATE` should have `WHERE` [dml-without-where]\n\
+             {0}:3:1: error: `DELETE` should have `WHERE` [dml-without-where]\n",
+            error
+        )));
+    let output = bq2cst(
+        &["lint", "-", "--rule", "unaliased-expression=off"],
+        "SELECT a + 1 FROM t;",
+    );
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    let output = bq2cst(&["lint", "-", "--rule", "unknown=off"], "");
+    assert_eq!(Some(2), output.status.code());
+    // the types of columns are inferred from the catalog
+    let ddl = sql_file("types.sql", "CREATE TABLE ds.t (id INT64);");
+    let output = bq2cst(
+        &["lint", "-", "--ddl", &ddl],
+        "SELECT id FROM ds.t WHERE id = 'a';",
+    );
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        "-:1:32: error: `INT64` cannot be compared with `STRING` [incompatible-comparison]\n",
+        String::from_utf8(output.stdout).unwrap()
+    );
+    let output = bq2cst(&["lint", "--list-rules"], "");
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("select-star-in-view\t"));
+}
+
+#[test]
+fn test_validate() {
+    let schema = sql_file(
+        "users.json",
+        r#"[{"name": "id", "type": "INTEGER"}, {"name": "tags", "type": "STRING", "mode": "REPEATED"}]"#,
+    );
+    let ddl = sql_file(
+        "orders.sql",
+        "CREATE TABLE ds.orders (id INT64, amount NUMERIC);",
+    );
+    let table = format!("ds.users={}", schema);
+    let output = bq2cst(
+        &["validate", "-", "--table", &table, "--ddl", &ddl],
+        "SELECT id, tags[OFFSET(0)] FROM ds.users;\nSELECT amont FROM ds.orders;\nSELECT * FROM ds.user;\nSELECT foo(id) FROM ds.users;",
+    );
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        "\
+-:2:8: Unrecognized name: amont
+-:3:15: Table not found: ds.user
+-:4:8: Function not found: foo
+",
+        String::from_utf8(output.stdout).unwrap()
+    );
+    let output = bq2cst(
+        &["validate", "-", "--table", &table],
+        "SELECT id FROM ds.users;",
+    );
+    assert!(output.status.success());
+    let output = bq2cst(&["validate", "-", "--table", "ds.users"], "");
+    assert_eq!(Some(2), output.status.code());
+}
+
+#[test]
+fn test_tokens() {
+    let output = bq2cst(&["tokens", "-"], "SELECT\n  'a'; -- comment");
+    assert!(output.status.success());
+    assert_eq!(
+        "\
+1:1\t\"SELECT\"
+2:3\t\"'a'\"
+2:6\t\";\"
+2:8\t\"-- comment\"
+2:18\t\"\"
+",
+        String::from_utf8(output.stdout).unwrap()
+    );
+}
+
+#[test]
+fn test_schema() {
+    let ddl = sql_file(
+        "upstream.sql",
+        "CREATE TABLE ds.a (id INT64, tags ARRAY<STRING>, s STRUCT<x DATE>);",
+    );
+    let output = bq2cst(
+        &["schema", "-", "--ddl", &ddl],
+        "CREATE TEMP TABLE tmp AS SELECT 1 AS x;\nCREATE VIEW ds.v AS SELECT id, tags, s, x FROM ds.a, tmp;",
+    );
+    assert!(output.status.success());
+    let expected = r#"[{"name":"id","type":"INT64"},{"name":"tags","type":"STRING","mode":"REPEATED"},{"name":"s","type":"RECORD","fields":[{"name":"x","type":"DATE"}]},{"name":"x","type":"INT64"}]"#;
+    let expected: serde_json::Value = serde_json::from_str(expected).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(expected, json);
+    // the output can be used as `--table`
+    let schema = sql_file("v.json", &String::from_utf8(output.stdout).unwrap());
+    let table = format!("ds.v={}", schema);
+    let output = bq2cst(&["schema", "-", "--table", &table], "SELECT s.x FROM ds.v");
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains(r#""type": "DATE""#));
+    let output = bq2cst(&["schema", "-"], "SELECT * FROM t");
+    assert_eq!(Some(1), output.status.code());
+}
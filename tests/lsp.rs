@@ -0,0 +1,278 @@
+#![cfg(feature = "lsp")]
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdout, Command, Stdio};
+
+// scripted LSP client
+struct Client {
+    child: Child,
+    reader: BufReader<ChildStdout>,
+    id: u64,
+}
+
+impl Client {
+    fn new() -> Client {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_bq2cst-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let reader = BufReader::new(child.stdout.take().unwrap());
+        Client {
+            child,
+            reader,
+            id: 0,
+        }
+    }
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        let stdin = self.child.stdin.as_mut().unwrap();
+        write!(stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        stdin.flush().unwrap();
+    }
+    fn receive(&mut self) -> Value {
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            self.reader.read_line(&mut line).unwrap();
+            match line.trim_end().split_once(": ") {
+                Some(("Content-Length", value)) => length = value.parse().unwrap(),
+                _ if line.trim_end().is_empty() => break,
+                _ => (),
+            }
+        }
+        let mut body = vec![0; length];
+        self.reader.read_exact(&mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.id += 1;
+        let id = self.id;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+        let response = self.receive();
+        assert_eq!(json!(id), response["id"]);
+        response
+    }
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+}
+
+const URI: &str = "file:///query.sql";
+
+fn position(line: u64, character: u64) -> Value {
+    json!({ "line": line, "character": character })
+}
+
+fn range(start: (u64, u64), end: (u64, u64)) -> Value {
+    json!({ "start": position(start.0, start.1), "end": position(end.0, end.1) })
+}
+
+#[test]
+fn test_lsp() {
+    let mut client = Client::new();
+    let response = client.request("initialize", json!({ "capabilities": {} }));
+    assert_eq!(
+        json!(true),
+        response["result"]["capabilities"]["definitionProvider"]
+    );
+    client.notify("initialized", json!({}));
+
+    // diagnostics
+    client.notify(
+        "textDocument/didOpen",
+        json!({
+            "textDocument": { "uri": URI, "languageId": "sql", "version": 1, "text": "SELECT 1 NOT 2;" },
+        }),
+    );
+    let notification = client.receive();
+    assert_eq!("textDocument/publishDiagnostics", notification["method"]);
+    let diagnostics = &notification["params"]["diagnostics"];
+    assert_eq!(range((0, 13), (0, 14)), diagnostics[0]["range"]);
+    assert_eq!(
+        "Expected `IN`, `LIKE` or `BETWEEN` but got: token `2`",
+        diagnostics[0]["message"]
+    );
+
+    let code = "\
+DECLARE x INT64;
+-- 'あ' is a single UTF-16 code unit, '😀' is two
+WITH cte AS (
+  SELECT '😀' AS a, x
+)
+SELECT cte.a, 'あ' AS b FROM cte;
+BEGIN
+  CREATE TEMP TABLE t AS SELECT x;
+END;";
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": URI, "version": 2 },
+            "contentChanges": [{ "text": code }],
+        }),
+    );
+    let notification = client.receive();
+    assert_eq!(json!([]), notification["params"]["diagnostics"]);
+
+    // document symbols
+    let response = client.request(
+        "textDocument/documentSymbol",
+        json!({ "textDocument": { "uri": URI } }),
+    );
+    let symbols = summary(&response["result"]);
+    assert_eq!(
+        vec![
+            "x DECLARE 0:0-0:16",
+            "WITH cte AS ( 2:0-5:32",
+            "  cte WITH 2:5-4:1",
+            "BEGIN 6:0-8:4",
+            "  t CREATE TABLE 7:2-7:34",
+        ],
+        symbols
+    );
+
+    // go to definition
+    let response = client.request(
+        "textDocument/definition",
+        json!({ "textDocument": { "uri": URI }, "position": position(5, 28) }),
+    );
+    assert_eq!(
+        json!({ "uri": URI, "range": range((2, 5), (2, 8)) }),
+        response["result"]
+    );
+    let response = client.request(
+        "textDocument/definition",
+        json!({ "textDocument": { "uri": URI }, "position": position(7, 33) }),
+    );
+    assert_eq!(range((0, 8), (0, 9)), response["result"]["range"]);
+    let response = client.request(
+        "textDocument/definition",
+        json!({ "textDocument": { "uri": URI }, "position": position(3, 20) }),
+    );
+    assert_eq!(range((0, 8), (0, 9)), response["result"]["range"]);
+    let response = client.request(
+        "textDocument/definition",
+        json!({ "textDocument": { "uri": URI }, "position": position(5, 0) }),
+    );
+    assert_eq!(Value::Null, response["result"]);
+
+    // folding ranges
+    let response = client.request(
+        "textDocument/foldingRange",
+        json!({ "textDocument": { "uri": URI } }),
+    );
+    assert_eq!(
+        json!([
+            { "startLine": 2, "endLine": 4 },
+            { "startLine": 6, "endLine": 8 },
+        ]),
+        response["result"]
+    );
+
+    // incremental synchronization
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": URI, "version": 3 },
+            "contentChanges": [
+                { "range": range((0, 8), (0, 9)), "text": "y" },
+                // after '😀'
+                { "range": range((3, 17), (3, 18)), "text": "a, 1 NOT 2" },
+            ],
+        }),
+    );
+    let notification = client.receive();
+    let diagnostics = &notification["params"]["diagnostics"];
+    assert_eq!(range((3, 26), (3, 27)), diagnostics[0]["range"]);
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": URI, "version": 4 },
+            "contentChanges": [{ "range": range((3, 18), (3, 27)), "text": "" }],
+        }),
+    );
+    let notification = client.receive();
+    assert_eq!(json!([]), notification["params"]["diagnostics"]);
+    let response = client.request(
+        "textDocument/documentSymbol",
+        json!({ "textDocument": { "uri": URI } }),
+    );
+    assert_eq!("y DECLARE 0:0-0:16", summary(&response["result"])[0]);
+    let response = client.request(
+        "textDocument/definition",
+        json!({ "textDocument": { "uri": URI }, "position": position(5, 28) }),
+    );
+    assert_eq!(range((2, 5), (2, 8)), response["result"]["range"]);
+
+    // formatting
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": URI, "version": 5 },
+            "contentChanges": [{ "text": "select 1\nfrom t" }],
+        }),
+    );
+    client.receive();
+    let response = client.request(
+        "textDocument/formatting",
+        json!({
+            "textDocument": { "uri": URI },
+            "options": { "tabSize": 4, "insertSpaces": true },
+        }),
+    );
+    assert_eq!(
+        json!([{ "range": range((0, 0), (1, 6)), "newText": "SELECT 1\nFROM t\n" }]),
+        response["result"]
+    );
+
+    // errors
+    let response = client.request(
+        "textDocument/documentSymbol",
+        json!({ "textDocument": { "uri": "file:///unknown.sql" } }),
+    );
+    assert_eq!(json!(-32602), response["error"]["code"]);
+    let response = client.request("unknown/method", json!({}));
+    assert_eq!(json!(-32601), response["error"]["code"]);
+
+    client.notify(
+        "textDocument/didClose",
+        json!({ "textDocument": { "uri": URI } }),
+    );
+    let notification = client.receive();
+    assert_eq!(json!([]), notification["params"]["diagnostics"]);
+    let response = client.request("shutdown", Value::Null);
+    assert_eq!(Value::Null, response["result"]);
+    client.notify("exit", Value::Null);
+    assert!(client.child.wait().unwrap().success());
+}
+
+// `name detail range` (children are indented)
+fn summary(symbols: &Value) -> Vec<String> {
+    let mut res = Vec::new();
+    for symbol in symbols.as_array().unwrap() {
+        let range = &symbol["range"];
+        let mut line = format!(
+            "{} {}",
+            symbol["name"].as_str().unwrap(),
+            symbol["detail"].as_str().unwrap()
+        )
+        .trim_end()
+        .to_string();
+        line += &format!(
+            " {}:{}-{}:{}",
+            range["start"]["line"],
+            range["start"]["character"],
+            range["end"]["line"],
+            range["end"]["character"]
+        );
+        res.push(line);
+        if !symbol["children"].is_null() {
+            res.extend(
+                summary(&symbol["children"])
+                    .into_iter()
+                    .map(|s| format!("  {}", s)),
+            );
+        }
+    }
+    res
+}
@@ -0,0 +1,149 @@
+use bq2cst::catalog::{validate, Catalog};
+use bq2cst::format::KeywordCase;
+use bq2cst::infer::infer;
+use bq2cst::lint::Severity;
+use bq2cst::token::categorize;
+use bq2cst::{
+    format_str, lint_str, parse_str, parse_str_with_recovery, reparse_str,
+    reparse_str_with_recovery, tokenize_str, FormatOptions, Linter, NodeType, TextEdit,
+    TokenCategory,
+};
+
+#[test]
+fn test_parse_str() {
+    let stmts = parse_str("SELECT 1 AS one;").unwrap();
+    assert_eq!(2, stmts.len());
+    assert_eq!(NodeType::SelectStatement, stmts[0].node_type);
+    assert_eq!(NodeType::EOF, stmts[1].node_type);
+    let exprs = stmts[0].get_node_vec("exprs").unwrap();
+    let alias = exprs[0].get_node("alias").unwrap();
+    assert_eq!("one", alias.token.as_ref().unwrap().literal);
+}
+
+#[test]
+fn test_parse_str_error() {
+    let error = parse_str("SELECT 1 NOT 2;").unwrap_err();
+    assert_eq!((1, 14), (error.line, error.column));
+    assert_eq!(
+        "1:14: Expected `IN`, `LIKE` or `BETWEEN` but got: token `2`",
+        error.to_string()
+    );
+}
+
+#[test]
+fn test_parse_str_with_recovery() {
+    let (stmts, errors) = parse_str_with_recovery("SELEC 1; SELECT 2;").unwrap();
+    let node_types: Vec<&NodeType> = stmts.iter().map(|n| &n.node_type).collect();
+    assert_eq!(
+        vec![&NodeType::Error, &NodeType::SelectStatement, &NodeType::EOF],
+        node_types
+    );
+    assert_eq!(1, errors.len());
+    assert!(parse_str_with_recovery("SELECT 'abc").is_err());
+}
+
+#[test]
+fn test_reparse_str() {
+    let code = "SELECT 1; SELECT 2;";
+    let stmts = parse_str(code).unwrap();
+    let edit = TextEdit::new(17, 18, "x\nFROM t");
+    let stmts = reparse_str(code, stmts, &edit).unwrap();
+    assert_eq!("SELECT 1; SELECT x\nFROM t;", edit.apply(code));
+    assert_eq!(parse_str(&edit.apply(code)).unwrap(), stmts);
+    assert!(reparse_str(code, parse_str(code).unwrap(), &TextEdit::new(7, 8, "'")).is_err());
+
+    let (stmts, _) = parse_str_with_recovery(code).unwrap();
+    let edit = TextEdit::new(0, 0, "SELEC 0;\n");
+    let (stmts, errors) = reparse_str_with_recovery(code, stmts, &edit).unwrap();
+    assert_eq!(NodeType::Error, stmts[0].node_type);
+    assert_eq!((1, 1), (errors[0].line, errors[0].column));
+}
+
+#[test]
+fn test_format_str() {
+    let options = FormatOptions {
+        keyword_case: KeywordCase::Lower,
+        ..Default::default()
+    };
+    let formatted = format_str("SELECT a, b FROM t; -- comment", &options).unwrap();
+    assert_eq!("select a, b\nfrom t; -- comment\n", formatted);
+    assert!(format_str("SELECT 1 NOT 2;", &options).is_err());
+}
+
+#[test]
+fn test_lint_str() {
+    let mut linter = Linter::default();
+    let diagnostics = lint_str("SELECT a + 1 FROM t, u;", &linter).unwrap();
+    let rules: Vec<&str> = diagnostics.iter().map(|d| d.rule.as_str()).collect();
+    assert_eq!(vec!["unaliased-expression", "comma-join"], rules);
+    assert!(linter.set_severity("comma-join", Severity::Off));
+    assert_eq!(
+        1,
+        lint_str("SELECT a + 1 FROM t, u;", &linter).unwrap().len()
+    );
+    assert!(lint_str("SELECT 1 NOT 2;", &linter).is_err());
+}
+
+#[test]
+fn test_validate() {
+    let mut catalog = Catalog::new();
+    catalog.add_ddl(&parse_str("CREATE TABLE ds.t (a INT64, s STRUCT<x STRING>);").unwrap());
+    let stmts =
+        parse_str("SELECT b, s.y FROM ds.t;\nSELECT * FROM ds.u;\nSELECT foo(a) FROM ds.t;")
+            .unwrap();
+    let errors = validate(&stmts, &catalog);
+    let errors: Vec<(usize, &str)> = errors
+        .iter()
+        .map(|e| (e.span.line, e.message.as_str()))
+        .collect();
+    assert_eq!(
+        vec![
+            (1, "Unrecognized name: b"),
+            (1, "Field name y does not exist in STRUCT<x STRING>"),
+            (2, "Table not found: ds.u"),
+            (3, "Function not found: foo"),
+        ],
+        errors
+    );
+}
+
+#[test]
+fn test_tokenize_str() {
+    let tokens = tokenize_str("SELECT r'x' -- comment").unwrap();
+    let literals: Vec<&str> = tokens.iter().map(|t| t.literal.as_str()).collect();
+    assert_eq!(vec!["SELECT", "r'x'", "-- comment", ""], literals);
+    assert_eq!(
+        vec![
+            TokenCategory::ReservedKeyword,
+            TokenCategory::RawString,
+            TokenCategory::Comment,
+            TokenCategory::EOF,
+        ],
+        categorize(&tokens)
+    );
+    assert!(tokenize_str("SELECT 'abc").is_err());
+}
+
+#[test]
+fn test_infer() {
+    let mut catalog = Catalog::new();
+    catalog.add_ddl(&parse_str("CREATE TABLE ds.t (a INT64, s STRUCT<x STRING>);").unwrap());
+    let stmts = parse_str("SELECT a / 2, s FROM ds.t;").unwrap();
+    let types = infer(&stmts, &catalog);
+    let exprs = stmts[0].get_node_vec("exprs").unwrap();
+    assert_eq!("FLOAT64", types.type_of(&exprs[0]).unwrap().to_string());
+    assert_eq!(
+        "STRUCT<x STRING>",
+        types.type_of(&exprs[1]).unwrap().to_string()
+    );
+    let columns = types.schema_of(&stmts[0]).unwrap();
+    assert_eq!("f0_", columns[0].name);
+    assert_eq!("s", columns[1].name);
+    let mut linter = Linter::default();
+    linter.set_catalog(catalog);
+    let diagnostics = lint_str("SELECT a FROM ds.t WHERE a = 'x'", &linter).unwrap();
+    assert_eq!(
+        "`INT64` cannot be compared with `STRING`",
+        diagnostics[0].message
+    );
+}
@@ -1,7 +1,7 @@
 // to run the following tests
 // you have to run `wasm-pack test --node`
 
-#![cfg(target_arch = "wasm32")]
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
 
 use bq2cst;
 use wasm_bindgen_test::*;